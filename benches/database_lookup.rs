@@ -0,0 +1,121 @@
+// Per-read cost of finding an object in the example database, comparing the string keys the
+// callbacks used to format ("analog_input-0") with ObjectKey lookups.
+//
+// Run with: cargo bench --bench database_lookup
+
+// The server is a binary crate, so its modules are compiled into the benchmark directly
+#![allow(dead_code)]
+
+#[path = "../src/bacnet_types.rs"]
+mod bacnet_types;
+#[path = "../src/cas_bacnet_stack_adapters.rs"]
+mod cas_bacnet_stack_adapters;
+#[path = "../src/cas_bacnet_stack_example_constants.rs"]
+mod cas_bacnet_stack_example_constants;
+#[path = "../src/clock.rs"]
+mod clock;
+#[path = "../src/config.rs"]
+mod config;
+#[path = "../src/database.rs"]
+mod database;
+#[path = "../src/intrinsic_reporting.rs"]
+mod intrinsic_reporting;
+#[path = "../src/pid.rs"]
+mod pid;
+#[path = "../src/schedule.rs"]
+mod schedule;
+#[path = "../src/trend_log.rs"]
+mod trend_log;
+
+use std::collections::HashMap;
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use bacnet_types::ObjectType;
+use config::{DatabaseConfig, DeviceConfig, ObjectConfig};
+use database::{Database, ExampleDatabaseObject, ObjectKey};
+
+const DEVICE_INSTANCE: u32 = 389001;
+const OBJECT_COUNTS: [u32; 2] = [1000, 4000];
+
+// Half analog inputs and half character string values, the object types the callbacks serve
+fn database_config(object_count: u32) -> DatabaseConfig {
+	let objects = (0..object_count).map(|instance| ObjectConfig {
+		object_type: if instance % 2 == 0 { ObjectType::AnalogInput } else { ObjectType::CharacterstringValue },
+		instance,
+		name: format!("Object {instance}"),
+		values: HashMap::new(),
+		enabled_properties: Vec::new(),
+		writable_properties: Vec::new(),
+		proprietary_properties: Vec::new(),
+		character_sets: HashMap::new(),
+		serve: true
+	}).collect();
+	DatabaseConfig {
+		device: DeviceConfig {
+			instance: DEVICE_INSTANCE,
+			name: "Benchmark Device".to_string(),
+			description: String::new(),
+			utc_offset: 0,
+			time_zone: None,
+			enabled_properties: Vec::new(),
+			writable_properties: Vec::new(),
+			character_sets: HashMap::new()
+		},
+		objects,
+		journal: None
+	}
+}
+
+// The raw values a callback receives for every object in the database
+fn lookups(object_count: u32) -> Vec<(u32, u16, u32)> {
+	(0..object_count).map(|instance| {
+		let object_type = if instance % 2 == 0 { ObjectType::AnalogInput } else { ObjectType::CharacterstringValue };
+		(DEVICE_INSTANCE, object_type.value(), instance)
+	}).collect()
+}
+
+// The key format the database used before ObjectKey
+fn string_key(object_type: u16, object_instance: u32) -> String {
+	match ObjectType::try_from(object_type) {
+		Ok(ObjectType::AnalogInput) => format!("analog_input-{object_instance}"),
+		Ok(ObjectType::CharacterstringValue) => format!("character_string_value-{object_instance}"),
+		_ => format!("device-{object_instance}")
+	}
+}
+
+fn string_keyed(database: Database) -> HashMap<String, ExampleDatabaseObject> {
+	database.into_iter().map(|(key, object)| (string_key(key.object_type.value(), key.object_instance), object)).collect()
+}
+
+fn bench_lookup(c: &mut Criterion) {
+	let mut group = c.benchmark_group("database_lookup");
+	for object_count in OBJECT_COUNTS {
+		let lookups = lookups(object_count);
+		let database = database::setup_database(&database_config(object_count)).expect("benchmark database");
+		let legacy_database = string_keyed(database::setup_database(&database_config(object_count)).expect("benchmark database"));
+
+		group.bench_with_input(BenchmarkId::new("string_key", object_count), &lookups, |b, lookups| {
+			let mut index = 0;
+			b.iter(|| {
+				let (_, object_type, object_instance) = lookups[index % lookups.len()];
+				index += 1;
+				black_box(legacy_database.get(string_key(black_box(object_type), black_box(object_instance)).as_str()));
+			});
+		});
+		group.bench_with_input(BenchmarkId::new("object_key", object_count), &lookups, |b, lookups| {
+			let mut index = 0;
+			b.iter(|| {
+				let (device_instance, object_type, object_instance) = lookups[index % lookups.len()];
+				index += 1;
+				let key = ObjectKey::from_raw(black_box(device_instance), black_box(object_type), black_box(object_instance));
+				black_box(key.and_then(|key| database.get(&key)));
+			});
+		});
+	}
+	group.finish();
+}
+
+criterion_group!(benches, bench_lookup);
+criterion_main!(benches);
@@ -0,0 +1,1593 @@
+// Typed BACnet enumerations from ASHRAE 135, converting to and from the raw integers used by the
+// adapter layer. Display and FromStr use the standard names ("analog-input", "present-value").
+// FromStr also accepts underscores or spaces instead of hyphens, any letter case, a plain integer,
+// and "proprietary-<n>" for values in the vendor range.
+
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+use serde::{Serialize, Serializer};
+
+// Returned when a raw value or a name does not match any value of an enumeration
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EnumerationError {
+	UnknownValue { enumeration: &'static str, value: u32 },
+	UnknownName { enumeration: &'static str, name: String }
+}
+
+impl fmt::Display for EnumerationError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			EnumerationError::UnknownValue { enumeration, value } => write!(f, "{value} is not a known {enumeration} value"),
+			EnumerationError::UnknownName { enumeration, name } => write!(f, "\"{name}\" is not a known {enumeration}")
+		}
+	}
+}
+
+impl std::error::Error for EnumerationError {}
+
+// Prefix used to display and parse vendor defined values
+const PROPRIETARY_PREFIX: &str = "proprietary-";
+
+// Lowercases the name and turns underscores and spaces into hyphens
+fn normalize_name(name: &str) -> String {
+	name.trim().chars().map(|character| match character {
+		'_' | ' ' => '-',
+		_ => character.to_ascii_lowercase()
+	}).collect()
+}
+
+// Deserializes an enumeration from its name or raw number, used by the config file and journal.
+// Enumerations serialize as their Display name.
+struct EnumerationVisitor<T>(PhantomData<T>);
+
+impl<'de, T: FromStr<Err = EnumerationError>> Visitor<'de> for EnumerationVisitor<T> {
+	type Value = T;
+
+	fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str("a BACnet enumeration name or number")
+	}
+
+	fn visit_str<E: de::Error>(self, value: &str) -> Result<T, E> {
+		value.parse().map_err(E::custom)
+	}
+
+	fn visit_u64<E: de::Error>(self, value: u64) -> Result<T, E> {
+		self.visit_str(&value.to_string())
+	}
+
+	fn visit_i64<E: de::Error>(self, value: i64) -> Result<T, E> {
+		self.visit_str(&value.to_string())
+	}
+}
+
+// Defines an enumeration with its standard values and, when a proprietary range is given, a
+// Proprietary variant holding any vendor defined value in that range.
+macro_rules! bacnet_enumeration {
+	(
+		$(#[$meta:meta])*
+		pub enum $name:ident: $raw:ty, proprietary $min:literal..=$max:literal {
+			$($variant:ident = $value:literal => $text:literal,)*
+		}
+	) => {
+		$(#[$meta])*
+		#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+		pub enum $name {
+			$($variant,)*
+			Proprietary($raw)
+		}
+
+		impl $name {
+			pub const PROPRIETARY_RANGE: RangeInclusive<$raw> = $min..=$max;
+
+			pub fn value(self) -> $raw {
+				match self {
+					$($name::$variant => $value,)*
+					$name::Proprietary(value) => value
+				}
+			}
+
+			pub fn is_proprietary(self) -> bool {
+				matches!(self, $name::Proprietary(_))
+			}
+
+			fn from_unlisted(value: $raw) -> Option<$name> {
+				$name::PROPRIETARY_RANGE.contains(&value).then_some($name::Proprietary(value))
+			}
+		}
+
+		bacnet_enumeration!(@common $name: $raw { $($variant = $value => $text,)* });
+	};
+	(
+		$(#[$meta:meta])*
+		pub enum $name:ident: $raw:ty {
+			$($variant:ident = $value:literal => $text:literal,)*
+		}
+	) => {
+		$(#[$meta])*
+		#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+		pub enum $name {
+			$($variant,)*
+		}
+
+		impl $name {
+			pub fn value(self) -> $raw {
+				match self {
+					$($name::$variant => $value,)*
+				}
+			}
+
+			pub fn is_proprietary(self) -> bool {
+				false
+			}
+
+			fn from_unlisted(_value: $raw) -> Option<$name> {
+				None
+			}
+		}
+
+		bacnet_enumeration!(@common $name: $raw { $($variant = $value => $text,)* });
+	};
+	(@common $name:ident: $raw:ty { $($variant:ident = $value:literal => $text:literal,)* }) => {
+		impl $name {
+			// Every standard value, in table order
+			pub const STANDARD: &'static [$name] = &[$($name::$variant,)*];
+
+			// The standard name, None for proprietary values
+			#[allow(unreachable_patterns)]
+			pub fn standard_name(self) -> Option<&'static str> {
+				match self {
+					$($name::$variant => Some($text),)*
+					_ => None
+				}
+			}
+
+			fn from_standard_name(name: &str) -> Option<$name> {
+				match name {
+					$($text => Some($name::$variant),)*
+					_ => None
+				}
+			}
+		}
+
+		impl TryFrom<$raw> for $name {
+			type Error = EnumerationError;
+
+			fn try_from(value: $raw) -> Result<$name, EnumerationError> {
+				match value {
+					$($value => Ok($name::$variant),)*
+					_ => $name::from_unlisted(value).ok_or(EnumerationError::UnknownValue { enumeration: stringify!($name), value: value.into() })
+				}
+			}
+		}
+
+		impl From<$name> for $raw {
+			fn from(value: $name) -> $raw {
+				value.value()
+			}
+		}
+
+		impl fmt::Display for $name {
+			fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+				match self.standard_name() {
+					Some(name) => f.write_str(name),
+					None => write!(f, "{PROPRIETARY_PREFIX}{}", self.value())
+				}
+			}
+		}
+
+		impl Serialize for $name {
+			fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+				serializer.collect_str(self)
+			}
+		}
+
+		impl<'de> Deserialize<'de> for $name {
+			fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<$name, D::Error> {
+				deserializer.deserialize_any(EnumerationVisitor(PhantomData))
+			}
+		}
+
+		impl FromStr for $name {
+			type Err = EnumerationError;
+
+			fn from_str(name: &str) -> Result<$name, EnumerationError> {
+				let normalized = normalize_name(name);
+				if let Some(value) = $name::from_standard_name(&normalized) {
+					return Ok(value);
+				}
+				// "proprietary-<n>" must name a value in the vendor range, a plain number may be either
+				let (number, proprietary) = match normalized.strip_prefix(PROPRIETARY_PREFIX) {
+					Some(number) => (number, true),
+					None => (normalized.as_str(), false)
+				};
+				match number.parse::<$raw>().ok().map($name::try_from) {
+					Some(Ok(value)) if !proprietary || value.is_proprietary() => Ok(value),
+					_ => Err(EnumerationError::UnknownName { enumeration: stringify!($name), name: name.to_string() })
+				}
+			}
+		}
+	};
+}
+
+bacnet_enumeration! {
+	// BACnetObjectType, 0-127 are reserved for ASHRAE
+	pub enum ObjectType: u16, proprietary 128..=1023 {
+		AnalogInput = 0 => "analog-input",
+		AnalogOutput = 1 => "analog-output",
+		AnalogValue = 2 => "analog-value",
+		BinaryInput = 3 => "binary-input",
+		BinaryOutput = 4 => "binary-output",
+		BinaryValue = 5 => "binary-value",
+		Calendar = 6 => "calendar",
+		Command = 7 => "command",
+		Device = 8 => "device",
+		EventEnrollment = 9 => "event-enrollment",
+		File = 10 => "file",
+		Group = 11 => "group",
+		Loop = 12 => "loop",
+		MultiStateInput = 13 => "multi-state-input",
+		MultiStateOutput = 14 => "multi-state-output",
+		NotificationClass = 15 => "notification-class",
+		Program = 16 => "program",
+		Schedule = 17 => "schedule",
+		Averaging = 18 => "averaging",
+		MultiStateValue = 19 => "multi-state-value",
+		TrendLog = 20 => "trend-log",
+		LifeSafetyPoint = 21 => "life-safety-point",
+		LifeSafetyZone = 22 => "life-safety-zone",
+		Accumulator = 23 => "accumulator",
+		PulseConverter = 24 => "pulse-converter",
+		EventLog = 25 => "event-log",
+		GlobalGroup = 26 => "global-group",
+		TrendLogMultiple = 27 => "trend-log-multiple",
+		LoadControl = 28 => "load-control",
+		StructuredView = 29 => "structured-view",
+		AccessDoor = 30 => "access-door",
+		Timer = 31 => "timer",
+		AccessCredential = 32 => "access-credential",
+		AccessPoint = 33 => "access-point",
+		AccessRights = 34 => "access-rights",
+		AccessUser = 35 => "access-user",
+		AccessZone = 36 => "access-zone",
+		CredentialDataInput = 37 => "credential-data-input",
+		NetworkSecurity = 38 => "network-security",
+		BitstringValue = 39 => "bitstring-value",
+		CharacterstringValue = 40 => "characterstring-value",
+		DatepatternValue = 41 => "datepattern-value",
+		DateValue = 42 => "date-value",
+		DatetimepatternValue = 43 => "datetimepattern-value",
+		DatetimeValue = 44 => "datetime-value",
+		IntegerValue = 45 => "integer-value",
+		LargeAnalogValue = 46 => "large-analog-value",
+		OctetstringValue = 47 => "octetstring-value",
+		PositiveIntegerValue = 48 => "positive-integer-value",
+		TimepatternValue = 49 => "timepattern-value",
+		TimeValue = 50 => "time-value",
+		NotificationForwarder = 51 => "notification-forwarder",
+		AlertEnrollment = 52 => "alert-enrollment",
+		Channel = 53 => "channel",
+		LightingOutput = 54 => "lighting-output",
+		BinaryLightingOutput = 55 => "binary-lighting-output",
+		NetworkPort = 56 => "network-port",
+		ElevatorGroup = 57 => "elevator-group",
+		Escalator = 58 => "escalator",
+		Lift = 59 => "lift",
+		Staging = 60 => "staging",
+		AuditLog = 61 => "audit-log",
+		AuditReporter = 62 => "audit-reporter",
+		Color = 63 => "color",
+		ColorTemperature = 64 => "color-temperature",
+	}
+}
+
+bacnet_enumeration! {
+	// BACnetPropertyIdentifier, 0-511 and 4194304 and up are reserved for ASHRAE
+	pub enum PropertyIdentifier: u32, proprietary 512..=4194303 {
+		AckedTransitions = 0 => "acked-transitions",
+		AckRequired = 1 => "ack-required",
+		Action = 2 => "action",
+		ActionText = 3 => "action-text",
+		ActiveText = 4 => "active-text",
+		ActiveVtSessions = 5 => "active-vt-sessions",
+		AlarmValue = 6 => "alarm-value",
+		AlarmValues = 7 => "alarm-values",
+		All = 8 => "all",
+		AllWritesSuccessful = 9 => "all-writes-successful",
+		ApduSegmentTimeout = 10 => "apdu-segment-timeout",
+		ApduTimeout = 11 => "apdu-timeout",
+		ApplicationSoftwareVersion = 12 => "application-software-version",
+		Archive = 13 => "archive",
+		Bias = 14 => "bias",
+		ChangeOfStateCount = 15 => "change-of-state-count",
+		ChangeOfStateTime = 16 => "change-of-state-time",
+		NotificationClass = 17 => "notification-class",
+		ControlledVariableReference = 19 => "controlled-variable-reference",
+		ControlledVariableUnits = 20 => "controlled-variable-units",
+		ControlledVariableValue = 21 => "controlled-variable-value",
+		CovIncrement = 22 => "cov-increment",
+		DateList = 23 => "date-list",
+		DaylightSavingsStatus = 24 => "daylight-savings-status",
+		Deadband = 25 => "deadband",
+		DerivativeConstant = 26 => "derivative-constant",
+		DerivativeConstantUnits = 27 => "derivative-constant-units",
+		Description = 28 => "description",
+		DescriptionOfHalt = 29 => "description-of-halt",
+		DeviceAddressBinding = 30 => "device-address-binding",
+		DeviceType = 31 => "device-type",
+		EffectivePeriod = 32 => "effective-period",
+		ElapsedActiveTime = 33 => "elapsed-active-time",
+		ErrorLimit = 34 => "error-limit",
+		EventEnable = 35 => "event-enable",
+		EventState = 36 => "event-state",
+		EventType = 37 => "event-type",
+		ExceptionSchedule = 38 => "exception-schedule",
+		FaultValues = 39 => "fault-values",
+		FeedbackValue = 40 => "feedback-value",
+		FileAccessMethod = 41 => "file-access-method",
+		FileSize = 42 => "file-size",
+		FileType = 43 => "file-type",
+		FirmwareRevision = 44 => "firmware-revision",
+		HighLimit = 45 => "high-limit",
+		InactiveText = 46 => "inactive-text",
+		InProcess = 47 => "in-process",
+		InstanceOf = 48 => "instance-of",
+		IntegralConstant = 49 => "integral-constant",
+		IntegralConstantUnits = 50 => "integral-constant-units",
+		LimitEnable = 52 => "limit-enable",
+		ListOfGroupMembers = 53 => "list-of-group-members",
+		ListOfObjectPropertyReferences = 54 => "list-of-object-property-references",
+		LocalDate = 56 => "local-date",
+		LocalTime = 57 => "local-time",
+		Location = 58 => "location",
+		LowLimit = 59 => "low-limit",
+		ManipulatedVariableReference = 60 => "manipulated-variable-reference",
+		MaximumOutput = 61 => "maximum-output",
+		MaxApduLengthAccepted = 62 => "max-apdu-length-accepted",
+		MaxInfoFrames = 63 => "max-info-frames",
+		MaxMaster = 64 => "max-master",
+		MaxPresValue = 65 => "max-pres-value",
+		MinimumOffTime = 66 => "minimum-off-time",
+		MinimumOnTime = 67 => "minimum-on-time",
+		MinimumOutput = 68 => "minimum-output",
+		MinPresValue = 69 => "min-pres-value",
+		ModelName = 70 => "model-name",
+		ModificationDate = 71 => "modification-date",
+		NotifyType = 72 => "notify-type",
+		NumberOfApduRetries = 73 => "number-of-apdu-retries",
+		NumberOfStates = 74 => "number-of-states",
+		ObjectIdentifier = 75 => "object-identifier",
+		ObjectList = 76 => "object-list",
+		ObjectName = 77 => "object-name",
+		ObjectPropertyReference = 78 => "object-property-reference",
+		ObjectType = 79 => "object-type",
+		Optional = 80 => "optional",
+		OutOfService = 81 => "out-of-service",
+		OutputUnits = 82 => "output-units",
+		EventParameters = 83 => "event-parameters",
+		Polarity = 84 => "polarity",
+		PresentValue = 85 => "present-value",
+		Priority = 86 => "priority",
+		PriorityArray = 87 => "priority-array",
+		PriorityForWriting = 88 => "priority-for-writing",
+		ProcessIdentifier = 89 => "process-identifier",
+		ProgramChange = 90 => "program-change",
+		ProgramLocation = 91 => "program-location",
+		ProgramState = 92 => "program-state",
+		ProportionalConstant = 93 => "proportional-constant",
+		ProportionalConstantUnits = 94 => "proportional-constant-units",
+		ProtocolObjectTypesSupported = 96 => "protocol-object-types-supported",
+		ProtocolServicesSupported = 97 => "protocol-services-supported",
+		ProtocolVersion = 98 => "protocol-version",
+		ReadOnly = 99 => "read-only",
+		ReasonForHalt = 100 => "reason-for-halt",
+		RecipientList = 102 => "recipient-list",
+		Reliability = 103 => "reliability",
+		RelinquishDefault = 104 => "relinquish-default",
+		Required = 105 => "required",
+		Resolution = 106 => "resolution",
+		SegmentationSupported = 107 => "segmentation-supported",
+		Setpoint = 108 => "setpoint",
+		SetpointReference = 109 => "setpoint-reference",
+		StateText = 110 => "state-text",
+		StatusFlags = 111 => "status-flags",
+		SystemStatus = 112 => "system-status",
+		TimeDelay = 113 => "time-delay",
+		TimeOfActiveTimeReset = 114 => "time-of-active-time-reset",
+		TimeOfStateCountReset = 115 => "time-of-state-count-reset",
+		TimeSynchronizationRecipients = 116 => "time-synchronization-recipients",
+		Units = 117 => "units",
+		UpdateInterval = 118 => "update-interval",
+		UtcOffset = 119 => "utc-offset",
+		VendorIdentifier = 120 => "vendor-identifier",
+		VendorName = 121 => "vendor-name",
+		VtClassesSupported = 122 => "vt-classes-supported",
+		WeeklySchedule = 123 => "weekly-schedule",
+		AttemptedSamples = 124 => "attempted-samples",
+		AverageValue = 125 => "average-value",
+		BufferSize = 126 => "buffer-size",
+		ClientCovIncrement = 127 => "client-cov-increment",
+		CovResubscriptionInterval = 128 => "cov-resubscription-interval",
+		EventTimeStamps = 130 => "event-time-stamps",
+		LogBuffer = 131 => "log-buffer",
+		LogDeviceObjectProperty = 132 => "log-device-object-property",
+		Enable = 133 => "enable",
+		LogInterval = 134 => "log-interval",
+		MaximumValue = 135 => "maximum-value",
+		MinimumValue = 136 => "minimum-value",
+		NotificationThreshold = 137 => "notification-threshold",
+		ProtocolRevision = 139 => "protocol-revision",
+		RecordsSinceNotification = 140 => "records-since-notification",
+		RecordCount = 141 => "record-count",
+		StartTime = 142 => "start-time",
+		StopTime = 143 => "stop-time",
+		StopWhenFull = 144 => "stop-when-full",
+		TotalRecordCount = 145 => "total-record-count",
+		ValidSamples = 146 => "valid-samples",
+		WindowInterval = 147 => "window-interval",
+		WindowSamples = 148 => "window-samples",
+		MaximumValueTimestamp = 149 => "maximum-value-timestamp",
+		MinimumValueTimestamp = 150 => "minimum-value-timestamp",
+		VarianceValue = 151 => "variance-value",
+		ActiveCovSubscriptions = 152 => "active-cov-subscriptions",
+		BackupFailureTimeout = 153 => "backup-failure-timeout",
+		ConfigurationFiles = 154 => "configuration-files",
+		DatabaseRevision = 155 => "database-revision",
+		DirectReading = 156 => "direct-reading",
+		LastRestoreTime = 157 => "last-restore-time",
+		MaintenanceRequired = 158 => "maintenance-required",
+		MemberOf = 159 => "member-of",
+		Mode = 160 => "mode",
+		OperationExpected = 161 => "operation-expected",
+		Setting = 162 => "setting",
+		Silenced = 163 => "silenced",
+		TrackingValue = 164 => "tracking-value",
+		ZoneMembers = 165 => "zone-members",
+		LifeSafetyAlarmValues = 166 => "life-safety-alarm-values",
+		MaxSegmentsAccepted = 167 => "max-segments-accepted",
+		ProfileName = 168 => "profile-name",
+		AutoSlaveDiscovery = 169 => "auto-slave-discovery",
+		ManualSlaveAddressBinding = 170 => "manual-slave-address-binding",
+		SlaveAddressBinding = 171 => "slave-address-binding",
+		SlaveProxyEnable = 172 => "slave-proxy-enable",
+		LastNotifyRecord = 173 => "last-notify-record",
+		ScheduleDefault = 174 => "schedule-default",
+		AcceptedModes = 175 => "accepted-modes",
+		AdjustValue = 176 => "adjust-value",
+		Count = 177 => "count",
+		CountBeforeChange = 178 => "count-before-change",
+		CountChangeTime = 179 => "count-change-time",
+		CovPeriod = 180 => "cov-period",
+		InputReference = 181 => "input-reference",
+		LimitMonitoringInterval = 182 => "limit-monitoring-interval",
+		LoggingObject = 183 => "logging-object",
+		LoggingRecord = 184 => "logging-record",
+		Prescale = 185 => "prescale",
+		PulseRate = 186 => "pulse-rate",
+		Scale = 187 => "scale",
+		ScaleFactor = 188 => "scale-factor",
+		UpdateTime = 189 => "update-time",
+		ValueBeforeChange = 190 => "value-before-change",
+		ValueSet = 191 => "value-set",
+		ValueChangeTime = 192 => "value-change-time",
+		AlignIntervals = 193 => "align-intervals",
+		IntervalOffset = 195 => "interval-offset",
+		LastRestartReason = 196 => "last-restart-reason",
+		LoggingType = 197 => "logging-type",
+		RestartNotificationRecipients = 202 => "restart-notification-recipients",
+		TimeOfDeviceRestart = 203 => "time-of-device-restart",
+		TimeSynchronizationInterval = 204 => "time-synchronization-interval",
+		Trigger = 205 => "trigger",
+		UtcTimeSynchronizationRecipients = 206 => "utc-time-synchronization-recipients",
+		NodeSubtype = 207 => "node-subtype",
+		NodeType = 208 => "node-type",
+		StructuredObjectList = 209 => "structured-object-list",
+		SubordinateAnnotations = 210 => "subordinate-annotations",
+		SubordinateList = 211 => "subordinate-list",
+		ActualShedLevel = 212 => "actual-shed-level",
+		DutyWindow = 213 => "duty-window",
+		ExpectedShedLevel = 214 => "expected-shed-level",
+		FullDutyBaseline = 215 => "full-duty-baseline",
+		RequestedShedLevel = 218 => "requested-shed-level",
+		ShedDuration = 219 => "shed-duration",
+		ShedLevelDescriptions = 220 => "shed-level-descriptions",
+		ShedLevels = 221 => "shed-levels",
+		StateDescription = 222 => "state-description",
+		DoorAlarmState = 226 => "door-alarm-state",
+		DoorExtendedPulseTime = 227 => "door-extended-pulse-time",
+		DoorMembers = 228 => "door-members",
+		DoorOpenTooLongTime = 229 => "door-open-too-long-time",
+		DoorPulseTime = 230 => "door-pulse-time",
+		DoorStatus = 231 => "door-status",
+		DoorUnlockDelayTime = 232 => "door-unlock-delay-time",
+		LockStatus = 233 => "lock-status",
+		MaskedAlarmValues = 234 => "masked-alarm-values",
+		SecuredStatus = 235 => "secured-status",
+		AbsenteeLimit = 244 => "absentee-limit",
+		AccessAlarmEvents = 245 => "access-alarm-events",
+		AccessDoors = 246 => "access-doors",
+		AccessEvent = 247 => "access-event",
+		AccessEventAuthenticationFactor = 248 => "access-event-authentication-factor",
+		AccessEventCredential = 249 => "access-event-credential",
+		AccessEventTime = 250 => "access-event-time",
+		AccessTransactionEvents = 251 => "access-transaction-events",
+		Accompaniment = 252 => "accompaniment",
+		AccompanimentTime = 253 => "accompaniment-time",
+		ActivationTime = 254 => "activation-time",
+		ActiveAuthenticationPolicy = 255 => "active-authentication-policy",
+		AssignedAccessRights = 256 => "assigned-access-rights",
+		AuthenticationFactors = 257 => "authentication-factors",
+		AuthenticationPolicyList = 258 => "authentication-policy-list",
+		AuthenticationPolicyNames = 259 => "authentication-policy-names",
+		AuthenticationStatus = 260 => "authentication-status",
+		AuthorizationMode = 261 => "authorization-mode",
+		BelongsTo = 262 => "belongs-to",
+		CredentialDisable = 263 => "credential-disable",
+		CredentialStatus = 264 => "credential-status",
+		Credentials = 265 => "credentials",
+		CredentialsInZone = 266 => "credentials-in-zone",
+		DaysRemaining = 267 => "days-remaining",
+		EntryPoints = 268 => "entry-points",
+		ExitPoints = 269 => "exit-points",
+		ExpirationTime = 270 => "expiration-time",
+		ExtendedTimeEnable = 271 => "extended-time-enable",
+		FailedAttemptEvents = 272 => "failed-attempt-events",
+		FailedAttempts = 273 => "failed-attempts",
+		FailedAttemptsTime = 274 => "failed-attempts-time",
+		LastAccessEvent = 275 => "last-access-event",
+		LastAccessPoint = 276 => "last-access-point",
+		LastCredentialAdded = 277 => "last-credential-added",
+		LastCredentialAddedTime = 278 => "last-credential-added-time",
+		LastCredentialRemoved = 279 => "last-credential-removed",
+		LastCredentialRemovedTime = 280 => "last-credential-removed-time",
+		LastUseTime = 281 => "last-use-time",
+		Lockout = 282 => "lockout",
+		LockoutRelinquishTime = 283 => "lockout-relinquish-time",
+		MaxFailedAttempts = 285 => "max-failed-attempts",
+		Members = 286 => "members",
+		MusterPoint = 287 => "muster-point",
+		NegativeAccessRules = 288 => "negative-access-rules",
+		NumberOfAuthenticationPolicies = 289 => "number-of-authentication-policies",
+		OccupancyCount = 290 => "occupancy-count",
+		OccupancyCountAdjust = 291 => "occupancy-count-adjust",
+		OccupancyCountEnable = 292 => "occupancy-count-enable",
+		OccupancyLowerLimit = 294 => "occupancy-lower-limit",
+		OccupancyLowerLimitEnforced = 295 => "occupancy-lower-limit-enforced",
+		OccupancyState = 296 => "occupancy-state",
+		OccupancyUpperLimit = 297 => "occupancy-upper-limit",
+		OccupancyUpperLimitEnforced = 298 => "occupancy-upper-limit-enforced",
+		PassbackMode = 300 => "passback-mode",
+		PassbackTimeout = 301 => "passback-timeout",
+		PositiveAccessRules = 302 => "positive-access-rules",
+		ReasonForDisable = 303 => "reason-for-disable",
+		SupportedFormats = 304 => "supported-formats",
+		SupportedFormatClasses = 305 => "supported-format-classes",
+		ThreatAuthority = 306 => "threat-authority",
+		ThreatLevel = 307 => "threat-level",
+		TraceFlag = 308 => "trace-flag",
+		TransactionNotificationClass = 309 => "transaction-notification-class",
+		UserExternalIdentifier = 310 => "user-external-identifier",
+		UserInformationReference = 311 => "user-information-reference",
+		UserName = 317 => "user-name",
+		UserType = 318 => "user-type",
+		UsesRemaining = 319 => "uses-remaining",
+		ZoneFrom = 320 => "zone-from",
+		ZoneTo = 321 => "zone-to",
+		AccessEventTag = 322 => "access-event-tag",
+		GlobalIdentifier = 323 => "global-identifier",
+		VerificationTime = 326 => "verification-time",
+		BaseDeviceSecurityPolicy = 327 => "base-device-security-policy",
+		DistributionKeyRevision = 328 => "distribution-key-revision",
+		DoNotHide = 329 => "do-not-hide",
+		KeySets = 330 => "key-sets",
+		LastKeyServer = 331 => "last-key-server",
+		NetworkAccessSecurityPolicies = 332 => "network-access-security-policies",
+		PacketReorderTime = 333 => "packet-reorder-time",
+		SecurityPduTimeout = 334 => "security-pdu-timeout",
+		SecurityTimeWindow = 335 => "security-time-window",
+		SupportedSecurityAlgorithms = 336 => "supported-security-algorithms",
+		UpdateKeySetTimeout = 337 => "update-key-set-timeout",
+		BackupAndRestoreState = 338 => "backup-and-restore-state",
+		BackupPreparationTime = 339 => "backup-preparation-time",
+		RestoreCompletionTime = 340 => "restore-completion-time",
+		RestorePreparationTime = 341 => "restore-preparation-time",
+		BitMask = 342 => "bit-mask",
+		BitText = 343 => "bit-text",
+		IsUtc = 344 => "is-utc",
+		GroupMembers = 345 => "group-members",
+		GroupMemberNames = 346 => "group-member-names",
+		MemberStatusFlags = 347 => "member-status-flags",
+		RequestedUpdateInterval = 348 => "requested-update-interval",
+		CovuPeriod = 349 => "covu-period",
+		CovuRecipients = 350 => "covu-recipients",
+		EventMessageTexts = 351 => "event-message-texts",
+		EventMessageTextsConfig = 352 => "event-message-texts-config",
+		EventDetectionEnable = 353 => "event-detection-enable",
+		EventAlgorithmInhibit = 354 => "event-algorithm-inhibit",
+		EventAlgorithmInhibitRef = 355 => "event-algorithm-inhibit-ref",
+		TimeDelayNormal = 356 => "time-delay-normal",
+		ReliabilityEvaluationInhibit = 357 => "reliability-evaluation-inhibit",
+		FaultParameters = 358 => "fault-parameters",
+		FaultType = 359 => "fault-type",
+		LocalForwardingOnly = 360 => "local-forwarding-only",
+		ProcessIdentifierFilter = 361 => "process-identifier-filter",
+		SubscribedRecipients = 362 => "subscribed-recipients",
+		PortFilter = 363 => "port-filter",
+		AuthorizationExemptions = 364 => "authorization-exemptions",
+		AllowGroupDelayInhibit = 365 => "allow-group-delay-inhibit",
+		ChannelNumber = 366 => "channel-number",
+		ControlGroups = 367 => "control-groups",
+		ExecutionDelay = 368 => "execution-delay",
+		LastPriority = 369 => "last-priority",
+		WriteStatus = 370 => "write-status",
+		PropertyList = 371 => "property-list",
+		SerialNumber = 372 => "serial-number",
+		BlinkWarnEnable = 373 => "blink-warn-enable",
+		DefaultFadeTime = 374 => "default-fade-time",
+		DefaultRampRate = 375 => "default-ramp-rate",
+		DefaultStepIncrement = 376 => "default-step-increment",
+		EgressTime = 377 => "egress-time",
+		InProgress = 378 => "in-progress",
+		InstantaneousPower = 379 => "instantaneous-power",
+		LightingCommand = 380 => "lighting-command",
+		LightingCommandDefaultPriority = 381 => "lighting-command-default-priority",
+		MaxActualValue = 382 => "max-actual-value",
+		MinActualValue = 383 => "min-actual-value",
+		Power = 384 => "power",
+		Transition = 385 => "transition",
+		EgressActive = 386 => "egress-active",
+		InterfaceValue = 387 => "interface-value",
+		FaultHighLimit = 388 => "fault-high-limit",
+		FaultLowLimit = 389 => "fault-low-limit",
+		LowDiffLimit = 390 => "low-diff-limit",
+		StrikeCount = 391 => "strike-count",
+		TimeOfStrikeCountReset = 392 => "time-of-strike-count-reset",
+		DefaultTimeout = 393 => "default-timeout",
+		InitialTimeout = 394 => "initial-timeout",
+		LastStateChange = 395 => "last-state-change",
+		StateChangeValues = 396 => "state-change-values",
+		TimerRunning = 397 => "timer-running",
+		TimerState = 398 => "timer-state",
+		ApduLength = 399 => "apdu-length",
+		IpAddress = 400 => "ip-address",
+		IpDefaultGateway = 401 => "ip-default-gateway",
+		IpDhcpEnable = 402 => "ip-dhcp-enable",
+		IpDhcpLeaseTime = 403 => "ip-dhcp-lease-time",
+		IpDhcpLeaseTimeRemaining = 404 => "ip-dhcp-lease-time-remaining",
+		IpDhcpServer = 405 => "ip-dhcp-server",
+		IpDnsServer = 406 => "ip-dns-server",
+		BacnetIpGlobalAddress = 407 => "bacnet-ip-global-address",
+		BacnetIpMode = 408 => "bacnet-ip-mode",
+		BacnetIpMulticastAddress = 409 => "bacnet-ip-multicast-address",
+		BacnetIpNatTraversal = 410 => "bacnet-ip-nat-traversal",
+		IpSubnetMask = 411 => "ip-subnet-mask",
+		BacnetIpUdpPort = 412 => "bacnet-ip-udp-port",
+		BbmdAcceptFdRegistrations = 413 => "bbmd-accept-fd-registrations",
+		BbmdBroadcastDistributionTable = 414 => "bbmd-broadcast-distribution-table",
+		BbmdForeignDeviceTable = 415 => "bbmd-foreign-device-table",
+		ChangesPending = 416 => "changes-pending",
+		Command = 417 => "command",
+		FdBbmdAddress = 418 => "fd-bbmd-address",
+		FdSubscriptionLifetime = 419 => "fd-subscription-lifetime",
+		LinkSpeed = 420 => "link-speed",
+		LinkSpeeds = 421 => "link-speeds",
+		LinkSpeedAutonegotiate = 422 => "link-speed-autonegotiate",
+		MacAddress = 423 => "mac-address",
+		NetworkInterfaceName = 424 => "network-interface-name",
+		NetworkNumber = 425 => "network-number",
+		NetworkNumberQuality = 426 => "network-number-quality",
+		NetworkType = 427 => "network-type",
+		RoutingTable = 428 => "routing-table",
+		VirtualMacAddressTable = 429 => "virtual-mac-address-table",
+		CommandTimeArray = 430 => "command-time-array",
+		CurrentCommandPriority = 431 => "current-command-priority",
+		LastCommandTime = 432 => "last-command-time",
+		ValueSource = 433 => "value-source",
+		ValueSourceArray = 434 => "value-source-array",
+		BacnetIpv6Mode = 435 => "bacnet-ipv6-mode",
+		Ipv6Address = 436 => "ipv6-address",
+		Ipv6PrefixLength = 437 => "ipv6-prefix-length",
+		BacnetIpv6UdpPort = 438 => "bacnet-ipv6-udp-port",
+		Ipv6DefaultGateway = 439 => "ipv6-default-gateway",
+		BacnetIpv6MulticastAddress = 440 => "bacnet-ipv6-multicast-address",
+		Ipv6DnsServer = 441 => "ipv6-dns-server",
+		Ipv6AutoAddressingEnable = 442 => "ipv6-auto-addressing-enable",
+		Ipv6DhcpLeaseTime = 443 => "ipv6-dhcp-lease-time",
+		Ipv6DhcpLeaseTimeRemaining = 444 => "ipv6-dhcp-lease-time-remaining",
+		Ipv6DhcpServer = 445 => "ipv6-dhcp-server",
+		Ipv6ZoneIndex = 446 => "ipv6-zone-index",
+		AssignedLandingCalls = 447 => "assigned-landing-calls",
+		CarAssignedDirection = 448 => "car-assigned-direction",
+		CarDoorCommand = 449 => "car-door-command",
+		CarDoorStatus = 450 => "car-door-status",
+		CarDoorText = 451 => "car-door-text",
+		CarDoorZone = 452 => "car-door-zone",
+		CarDriverStatus = 453 => "car-driver-status",
+		CarLoad = 454 => "car-load",
+		CarLoadUnits = 455 => "car-load-units",
+		CarMode = 456 => "car-mode",
+		CarMovingDirection = 457 => "car-moving-direction",
+		CarPosition = 458 => "car-position",
+		ElevatorGroup = 459 => "elevator-group",
+		EnergyMeter = 460 => "energy-meter",
+		EnergyMeterRef = 461 => "energy-meter-ref",
+		EscalatorMode = 462 => "escalator-mode",
+		FaultSignals = 463 => "fault-signals",
+		FloorText = 464 => "floor-text",
+		GroupId = 465 => "group-id",
+		GroupMode = 467 => "group-mode",
+		HigherDeck = 468 => "higher-deck",
+		InstallationId = 469 => "installation-id",
+		LandingCalls = 470 => "landing-calls",
+		LandingCallControl = 471 => "landing-call-control",
+		LandingDoorStatus = 472 => "landing-door-status",
+		LowerDeck = 473 => "lower-deck",
+		MachineRoomId = 474 => "machine-room-id",
+		MakingCarCall = 475 => "making-car-call",
+		NextStoppingFloor = 476 => "next-stopping-floor",
+		OperationDirection = 477 => "operation-direction",
+		PassengerAlarm = 478 => "passenger-alarm",
+		PowerMode = 479 => "power-mode",
+		RegisteredCarCall = 480 => "registered-car-call",
+		ActiveCovMultipleSubscriptions = 481 => "active-cov-multiple-subscriptions",
+		ProtocolLevel = 482 => "protocol-level",
+		ReferencePort = 483 => "reference-port",
+		DeployedProfileLocation = 484 => "deployed-profile-location",
+		ProfileLocation = 485 => "profile-location",
+		Tags = 486 => "tags",
+		SubordinateNodeTypes = 487 => "subordinate-node-types",
+		SubordinateTags = 488 => "subordinate-tags",
+		SubordinateRelationships = 489 => "subordinate-relationships",
+		DefaultSubordinateRelationship = 490 => "default-subordinate-relationship",
+		Represents = 491 => "represents",
+		DefaultPresentValue = 492 => "default-present-value",
+		PresentStage = 493 => "present-stage",
+		Stages = 494 => "stages",
+		StageNames = 495 => "stage-names",
+		TargetReferences = 496 => "target-references",
+		AuditSourceReporter = 497 => "audit-source-reporter",
+		AuditLevel = 498 => "audit-level",
+		AuditNotificationRecipient = 499 => "audit-notification-recipient",
+		AuditPriorityFilter = 500 => "audit-priority-filter",
+		AuditableOperations = 501 => "auditable-operations",
+		DeleteOnForward = 502 => "delete-on-forward",
+		MaximumSendDelay = 503 => "maximum-send-delay",
+		MonitoredObjects = 504 => "monitored-objects",
+		SendNow = 505 => "send-now",
+		FloorNumber = 506 => "floor-number",
+		DeviceUuid = 507 => "device-uuid",
+		AdditionalReferencePorts = 508 => "additional-reference-ports",
+		CertificateSigningRequestFile = 509 => "certificate-signing-request-file",
+		CommandValidationResult = 510 => "command-validation-result",
+		IssuerCertificateFiles = 511 => "issuer-certificate-files",
+		MaxBvlcLengthAccepted = 4194304 => "max-bvlc-length-accepted",
+		MaxNpduLengthAccepted = 4194305 => "max-npdu-length-accepted",
+		OperationalCertificateFile = 4194306 => "operational-certificate-file",
+		CurrentHealth = 4194307 => "current-health",
+		ScConnectWaitTimeout = 4194308 => "sc-connect-wait-timeout",
+		ScDirectConnectAcceptEnable = 4194309 => "sc-direct-connect-accept-enable",
+		ScDirectConnectAcceptUris = 4194310 => "sc-direct-connect-accept-uris",
+		ScDirectConnectBinding = 4194311 => "sc-direct-connect-binding",
+		ScDirectConnectConnectionStatus = 4194312 => "sc-direct-connect-connection-status",
+		ScDirectConnectInitiateEnable = 4194313 => "sc-direct-connect-initiate-enable",
+		ScDisconnectWaitTimeout = 4194314 => "sc-disconnect-wait-timeout",
+		ScFailedConnectionRequests = 4194315 => "sc-failed-connection-requests",
+		ScFailoverHubConnectionStatus = 4194316 => "sc-failover-hub-connection-status",
+		ScFailoverHubUri = 4194317 => "sc-failover-hub-uri",
+		ScHubConnectorState = 4194318 => "sc-hub-connector-state",
+		ScHubFunctionAcceptUris = 4194319 => "sc-hub-function-accept-uris",
+		ScHubFunctionBinding = 4194320 => "sc-hub-function-binding",
+		ScHubFunctionConnectionStatus = 4194321 => "sc-hub-function-connection-status",
+		ScHubFunctionEnable = 4194322 => "sc-hub-function-enable",
+		ScHeartbeatTimeout = 4194323 => "sc-heartbeat-timeout",
+		ScPrimaryHubConnectionStatus = 4194324 => "sc-primary-hub-connection-status",
+		ScPrimaryHubUri = 4194325 => "sc-primary-hub-uri",
+		ScMaximumReconnectTime = 4194326 => "sc-maximum-reconnect-time",
+		ScMinimumReconnectTime = 4194327 => "sc-minimum-reconnect-time",
+		ColorOverride = 4194328 => "color-override",
+		ColorReference = 4194329 => "color-reference",
+		DefaultColor = 4194330 => "default-color",
+		DefaultColorTemperature = 4194331 => "default-color-temperature",
+		OverrideColorReference = 4194332 => "override-color-reference",
+		ColorCommand = 4194334 => "color-command",
+		HighEndTrim = 4194335 => "high-end-trim",
+		LowEndTrim = 4194336 => "low-end-trim",
+		TrimFadeTime = 4194337 => "trim-fade-time",
+	}
+}
+
+bacnet_enumeration! {
+	// BACnetServicesSupported bit positions, as passed to BACnetStack_SetServiceEnabled
+	pub enum ServiceSupported: u8 {
+		AcknowledgeAlarm = 0 => "acknowledge-alarm",
+		ConfirmedCovNotification = 1 => "confirmed-cov-notification",
+		ConfirmedEventNotification = 2 => "confirmed-event-notification",
+		GetAlarmSummary = 3 => "get-alarm-summary",
+		GetEnrollmentSummary = 4 => "get-enrollment-summary",
+		SubscribeCov = 5 => "subscribe-cov",
+		AtomicReadFile = 6 => "atomic-read-file",
+		AtomicWriteFile = 7 => "atomic-write-file",
+		AddListElement = 8 => "add-list-element",
+		RemoveListElement = 9 => "remove-list-element",
+		CreateObject = 10 => "create-object",
+		DeleteObject = 11 => "delete-object",
+		ReadProperty = 12 => "read-property",
+		ReadPropertyConditional = 13 => "read-property-conditional",
+		ReadPropertyMultiple = 14 => "read-property-multiple",
+		WriteProperty = 15 => "write-property",
+		WritePropertyMultiple = 16 => "write-property-multiple",
+		DeviceCommunicationControl = 17 => "device-communication-control",
+		ConfirmedPrivateTransfer = 18 => "confirmed-private-transfer",
+		ConfirmedTextMessage = 19 => "confirmed-text-message",
+		ReinitializeDevice = 20 => "reinitialize-device",
+		VtOpen = 21 => "vt-open",
+		VtClose = 22 => "vt-close",
+		VtData = 23 => "vt-data",
+		Authenticate = 24 => "authenticate",
+		RequestKey = 25 => "request-key",
+		IAm = 26 => "i-am",
+		IHave = 27 => "i-have",
+		UnconfirmedCovNotification = 28 => "unconfirmed-cov-notification",
+		UnconfirmedEventNotification = 29 => "unconfirmed-event-notification",
+		UnconfirmedPrivateTransfer = 30 => "unconfirmed-private-transfer",
+		UnconfirmedTextMessage = 31 => "unconfirmed-text-message",
+		TimeSynchronization = 32 => "time-synchronization",
+		WhoHas = 33 => "who-has",
+		WhoIs = 34 => "who-is",
+		ReadRange = 35 => "read-range",
+		UtcTimeSynchronization = 36 => "utc-time-synchronization",
+		LifeSafetyOperation = 37 => "life-safety-operation",
+		SubscribeCovProperty = 38 => "subscribe-cov-property",
+		GetEventInformation = 39 => "get-event-information",
+		WriteGroup = 40 => "write-group",
+		SubscribeCovPropertyMultiple = 41 => "subscribe-cov-property-multiple",
+		ConfirmedCovNotificationMultiple = 42 => "confirmed-cov-notification-multiple",
+		UnconfirmedCovNotificationMultiple = 43 => "unconfirmed-cov-notification-multiple",
+		ConfirmedAuditNotification = 44 => "confirmed-audit-notification",
+		AuditLogQuery = 45 => "audit-log-query",
+		UnconfirmedAuditNotification = 46 => "unconfirmed-audit-notification",
+		WhoAmI = 47 => "who-am-i",
+		YouAre = 48 => "you-are",
+	}
+}
+
+bacnet_enumeration! {
+	// BACnetErrorCode, 0-255 are reserved for ASHRAE
+	pub enum ErrorCode: u32, proprietary 256..=65535 {
+		Other = 0 => "other",
+		AuthenticationFailed = 1 => "authentication-failed",
+		ConfigurationInProgress = 2 => "configuration-in-progress",
+		DeviceBusy = 3 => "device-busy",
+		DynamicCreationNotSupported = 4 => "dynamic-creation-not-supported",
+		FileAccessDenied = 5 => "file-access-denied",
+		IncompatibleSecurityLevels = 6 => "incompatible-security-levels",
+		InconsistentParameters = 7 => "inconsistent-parameters",
+		InconsistentSelectionCriterion = 8 => "inconsistent-selection-criterion",
+		InvalidDataType = 9 => "invalid-data-type",
+		InvalidFileAccessMethod = 10 => "invalid-file-access-method",
+		InvalidFileStartPosition = 11 => "invalid-file-start-position",
+		InvalidOperatorName = 12 => "invalid-operator-name",
+		InvalidParameterDataType = 13 => "invalid-parameter-data-type",
+		InvalidTimeStamp = 14 => "invalid-time-stamp",
+		KeyGenerationError = 15 => "key-generation-error",
+		MissingRequiredParameter = 16 => "missing-required-parameter",
+		NoObjectsOfSpecifiedType = 17 => "no-objects-of-specified-type",
+		NoSpaceForObject = 18 => "no-space-for-object",
+		NoSpaceToAddListElement = 19 => "no-space-to-add-list-element",
+		NoSpaceToWriteProperty = 20 => "no-space-to-write-property",
+		NoVtSessionsAvailable = 21 => "no-vt-sessions-available",
+		PropertyIsNotAList = 22 => "property-is-not-a-list",
+		ObjectDeletionNotPermitted = 23 => "object-deletion-not-permitted",
+		ObjectIdentifierAlreadyExists = 24 => "object-identifier-already-exists",
+		OperationalProblem = 25 => "operational-problem",
+		PasswordFailure = 26 => "password-failure",
+		ReadAccessDenied = 27 => "read-access-denied",
+		SecurityNotSupported = 28 => "security-not-supported",
+		ServiceRequestDenied = 29 => "service-request-denied",
+		Timeout = 30 => "timeout",
+		UnknownObject = 31 => "unknown-object",
+		UnknownProperty = 32 => "unknown-property",
+		UnknownVtClass = 34 => "unknown-vt-class",
+		UnknownVtSession = 35 => "unknown-vt-session",
+		UnsupportedObjectType = 36 => "unsupported-object-type",
+		ValueOutOfRange = 37 => "value-out-of-range",
+		VtSessionAlreadyClosed = 38 => "vt-session-already-closed",
+		VtSessionTerminationFailure = 39 => "vt-session-termination-failure",
+		WriteAccessDenied = 40 => "write-access-denied",
+		CharacterSetNotSupported = 41 => "character-set-not-supported",
+		InvalidArrayIndex = 42 => "invalid-array-index",
+		CovSubscriptionFailed = 43 => "cov-subscription-failed",
+		NotCovProperty = 44 => "not-cov-property",
+		OptionalFunctionalityNotSupported = 45 => "optional-functionality-not-supported",
+		InvalidConfigurationData = 46 => "invalid-configuration-data",
+		DatatypeNotSupported = 47 => "datatype-not-supported",
+		DuplicateName = 48 => "duplicate-name",
+		DuplicateObjectId = 49 => "duplicate-object-id",
+		PropertyIsNotAnArray = 50 => "property-is-not-an-array",
+		AbortBufferOverflow = 51 => "abort-buffer-overflow",
+		AbortInvalidApduInThisState = 52 => "abort-invalid-apdu-in-this-state",
+		AbortPreemptedByHigherPriorityTask = 53 => "abort-preempted-by-higher-priority-task",
+		AbortSegmentationNotSupported = 54 => "abort-segmentation-not-supported",
+		AbortProprietary = 55 => "abort-proprietary",
+		AbortOther = 56 => "abort-other",
+		InvalidTag = 57 => "invalid-tag",
+		NetworkDown = 58 => "network-down",
+		RejectBufferOverflow = 59 => "reject-buffer-overflow",
+		RejectInconsistentParameters = 60 => "reject-inconsistent-parameters",
+		RejectInvalidParameterDataType = 61 => "reject-invalid-parameter-data-type",
+		RejectInvalidTag = 62 => "reject-invalid-tag",
+		RejectMissingRequiredParameter = 63 => "reject-missing-required-parameter",
+		RejectParameterOutOfRange = 64 => "reject-parameter-out-of-range",
+		RejectTooManyArguments = 65 => "reject-too-many-arguments",
+		RejectUndefinedEnumeration = 66 => "reject-undefined-enumeration",
+		RejectUnrecognizedService = 67 => "reject-unrecognized-service",
+		RejectProprietary = 68 => "reject-proprietary",
+		RejectOther = 69 => "reject-other",
+		UnknownDevice = 70 => "unknown-device",
+		UnknownRoute = 71 => "unknown-route",
+		ValueNotInitialized = 72 => "value-not-initialized",
+		InvalidEventState = 73 => "invalid-event-state",
+		NoAlarmConfigured = 74 => "no-alarm-configured",
+		LogBufferFull = 75 => "log-buffer-full",
+		LoggedValuePurged = 76 => "logged-value-purged",
+		NoPropertySpecified = 77 => "no-property-specified",
+		NotConfiguredForTriggeredLogging = 78 => "not-configured-for-triggered-logging",
+		UnknownSubscription = 79 => "unknown-subscription",
+		ParameterOutOfRange = 80 => "parameter-out-of-range",
+		ListElementNotFound = 81 => "list-element-not-found",
+		Busy = 82 => "busy",
+		CommunicationDisabled = 83 => "communication-disabled",
+		Success = 84 => "success",
+		AccessDenied = 85 => "access-denied",
+		BadDestinationAddress = 86 => "bad-destination-address",
+		BadDestinationDeviceId = 87 => "bad-destination-device-id",
+		BadSignature = 88 => "bad-signature",
+		BadSourceAddress = 89 => "bad-source-address",
+		BadTimestamp = 90 => "bad-timestamp",
+		CannotUseKey = 91 => "cannot-use-key",
+		CannotVerifyMessageId = 92 => "cannot-verify-message-id",
+		CorrectKeyRevision = 93 => "correct-key-revision",
+		DestinationDeviceIdRequired = 94 => "destination-device-id-required",
+		DuplicateMessage = 95 => "duplicate-message",
+		EncryptionNotConfigured = 96 => "encryption-not-configured",
+		EncryptionRequired = 97 => "encryption-required",
+		IncorrectKey = 98 => "incorrect-key",
+		InvalidKeyData = 99 => "invalid-key-data",
+		KeyUpdateInProgress = 100 => "key-update-in-progress",
+		MalformedMessage = 101 => "malformed-message",
+		NotKeyServer = 102 => "not-key-server",
+		SecurityNotConfigured = 103 => "security-not-configured",
+		SourceSecurityRequired = 104 => "source-security-required",
+		TooManyKeys = 105 => "too-many-keys",
+		UnknownAuthenticationType = 106 => "unknown-authentication-type",
+		UnknownKey = 107 => "unknown-key",
+		UnknownKeyRevision = 108 => "unknown-key-revision",
+		UnknownSourceMessage = 109 => "unknown-source-message",
+		NotRouterToDnet = 110 => "not-router-to-dnet",
+		RouterBusy = 111 => "router-busy",
+		UnknownNetworkMessage = 112 => "unknown-network-message",
+		MessageTooLong = 113 => "message-too-long",
+		SecurityError = 114 => "security-error",
+		AddressingError = 115 => "addressing-error",
+		WriteBdtFailed = 116 => "write-bdt-failed",
+		ReadBdtFailed = 117 => "read-bdt-failed",
+		RegisterForeignDeviceFailed = 118 => "register-foreign-device-failed",
+		ReadFdtFailed = 119 => "read-fdt-failed",
+		DeleteFdtEntryFailed = 120 => "delete-fdt-entry-failed",
+		DistributeBroadcastFailed = 121 => "distribute-broadcast-failed",
+		UnknownFileSize = 122 => "unknown-file-size",
+		AbortApduTooLong = 123 => "abort-apdu-too-long",
+		AbortApplicationExceededReplyTime = 124 => "abort-application-exceeded-reply-time",
+		AbortOutOfResources = 125 => "abort-out-of-resources",
+		AbortTsmTimeout = 126 => "abort-tsm-timeout",
+		AbortWindowSizeOutOfRange = 127 => "abort-window-size-out-of-range",
+		FileFull = 128 => "file-full",
+		InconsistentConfiguration = 129 => "inconsistent-configuration",
+		InconsistentObjectType = 130 => "inconsistent-object-type",
+		InternalError = 131 => "internal-error",
+		NotConfigured = 132 => "not-configured",
+		OutOfMemory = 133 => "out-of-memory",
+		ValueTooLong = 134 => "value-too-long",
+		AbortInsufficientSecurity = 135 => "abort-insufficient-security",
+		AbortSecurityError = 136 => "abort-security-error",
+		DuplicateEntry = 137 => "duplicate-entry",
+		InvalidValueInThisState = 138 => "invalid-value-in-this-state",
+		InvalidOperationInThisState = 139 => "invalid-operation-in-this-state",
+		ListItemNotNumbered = 140 => "list-item-not-numbered",
+		ListItemNotTimestamped = 141 => "list-item-not-timestamped",
+		InvalidDataEncoding = 142 => "invalid-data-encoding",
+		BvlcFunctionUnknown = 143 => "bvlc-function-unknown",
+		BvlcProprietaryFunctionUnknown = 144 => "bvlc-proprietary-function-unknown",
+		HeaderEncodingError = 145 => "header-encoding-error",
+		HeaderNotUnderstood = 146 => "header-not-understood",
+		MessageIncomplete = 147 => "message-incomplete",
+		NotABacnetScHub = 148 => "not-a-bacnet-sc-hub",
+		PayloadExpected = 149 => "payload-expected",
+		UnexpectedData = 150 => "unexpected-data",
+		NodeDuplicateVmac = 151 => "node-duplicate-vmac",
+		HttpUnexpectedResponseCode = 152 => "http-unexpected-response-code",
+		HttpNoUpgrade = 153 => "http-no-upgrade",
+		HttpResourceNotLocal = 154 => "http-resource-not-local",
+		HttpProxyAuthenticationFailed = 155 => "http-proxy-authentication-failed",
+		HttpResponseTimeout = 156 => "http-response-timeout",
+		HttpResponseSyntaxError = 157 => "http-response-syntax-error",
+		HttpResponseValueError = 158 => "http-response-value-error",
+		HttpResponseMissingHeader = 159 => "http-response-missing-header",
+		HttpWebsocketHeaderError = 160 => "http-websocket-header-error",
+		HttpUpgradeRequired = 161 => "http-upgrade-required",
+		HttpUpgradeError = 162 => "http-upgrade-error",
+		HttpTemporaryUnavailable = 163 => "http-temporary-unavailable",
+		HttpNotAServer = 164 => "http-not-a-server",
+		HttpError = 165 => "http-error",
+		WebsocketSchemeNotSupported = 166 => "websocket-scheme-not-supported",
+		WebsocketUnknownControlMessage = 167 => "websocket-unknown-control-message",
+		WebsocketCloseError = 168 => "websocket-close-error",
+		WebsocketClosedByPeer = 169 => "websocket-closed-by-peer",
+		WebsocketEndpointLeaves = 170 => "websocket-endpoint-leaves",
+		WebsocketProtocolError = 171 => "websocket-protocol-error",
+		WebsocketDataNotAccepted = 172 => "websocket-data-not-accepted",
+		WebsocketClosedAbnormally = 173 => "websocket-closed-abnormally",
+		WebsocketDataInconsistent = 174 => "websocket-data-inconsistent",
+		WebsocketDataAgainstPolicy = 175 => "websocket-data-against-policy",
+		WebsocketFrameTooLong = 176 => "websocket-frame-too-long",
+		WebsocketExtensionMissing = 177 => "websocket-extension-missing",
+		WebsocketRequestUnavailable = 178 => "websocket-request-unavailable",
+		WebsocketError = 179 => "websocket-error",
+		TlsClientCertificateError = 180 => "tls-client-certificate-error",
+		TlsServerCertificateError = 181 => "tls-server-certificate-error",
+		TlsClientAuthenticationFailed = 182 => "tls-client-authentication-failed",
+		TlsServerAuthenticationFailed = 183 => "tls-server-authentication-failed",
+		TlsClientCertificateExpired = 184 => "tls-client-certificate-expired",
+		TlsServerCertificateExpired = 185 => "tls-server-certificate-expired",
+		TlsClientCertificateRevoked = 186 => "tls-client-certificate-revoked",
+		TlsServerCertificateRevoked = 187 => "tls-server-certificate-revoked",
+		TlsError = 188 => "tls-error",
+		DnsUnavailable = 189 => "dns-unavailable",
+		DnsNameResolutionFailed = 190 => "dns-name-resolution-failed",
+		DnsResolverFailure = 191 => "dns-resolver-failure",
+		DnsError = 192 => "dns-error",
+		TcpConnectTimeout = 193 => "tcp-connect-timeout",
+		TcpConnectionRefused = 194 => "tcp-connection-refused",
+		TcpClosedByLocal = 195 => "tcp-closed-by-local",
+		TcpClosedOther = 196 => "tcp-closed-other",
+		TcpError = 197 => "tcp-error",
+		IpAddressNotReachable = 198 => "ip-address-not-reachable",
+		IpError = 199 => "ip-error",
+		CertificateExpired = 200 => "certificate-expired",
+		CertificateInvalid = 201 => "certificate-invalid",
+		CertificateMalformed = 202 => "certificate-malformed",
+		CertificateRevoked = 203 => "certificate-revoked",
+		UnknownSecurityKey = 204 => "unknown-security-key",
+		ReferencedPortInError = 205 => "referenced-port-in-error",
+	}
+}
+
+bacnet_enumeration! {
+	// BACnetReliability, 0-63 are reserved for ASHRAE
+	pub enum Reliability: u32, proprietary 64..=65535 {
+		NoFaultDetected = 0 => "no-fault-detected",
+		NoSensor = 1 => "no-sensor",
+		OverRange = 2 => "over-range",
+		UnderRange = 3 => "under-range",
+		OpenLoop = 4 => "open-loop",
+		ShortedLoop = 5 => "shorted-loop",
+		NoOutput = 6 => "no-output",
+		UnreliableOther = 7 => "unreliable-other",
+		ProcessError = 8 => "process-error",
+		MultiStateFault = 9 => "multi-state-fault",
+		ConfigurationError = 10 => "configuration-error",
+		CommunicationFailure = 12 => "communication-failure",
+		MemberFault = 13 => "member-fault",
+		MonitoredObjectFault = 14 => "monitored-object-fault",
+		Tripped = 15 => "tripped",
+		LampFailure = 16 => "lamp-failure",
+		ActivationFailure = 17 => "activation-failure",
+		RenewDhcpFailure = 18 => "renew-dhcp-failure",
+		RenewFdRegistrationFailure = 19 => "renew-fd-registration-failure",
+		RestartAutoNegotiationFailure = 20 => "restart-auto-negotiation-failure",
+		RestartFailure = 21 => "restart-failure",
+		ProprietaryCommandFailure = 22 => "proprietary-command-failure",
+		FaultsListed = 23 => "faults-listed",
+		ReferencedObjectFault = 24 => "referenced-object-fault",
+		MultiStateOutOfRange = 25 => "multi-state-out-of-range",
+	}
+}
+
+bacnet_enumeration! {
+	// BACnetPolarity, whether the physical state of a binary object is inverted from its present value
+	pub enum Polarity: u32 {
+		Normal = 0 => "normal",
+		Reverse = 1 => "reverse",
+	}
+}
+
+bacnet_enumeration! {
+	// BACnetAction, the direction a loop drives its output as the controlled variable rises
+	pub enum Action: u32 {
+		Direct = 0 => "direct",
+		Reverse = 1 => "reverse",
+	}
+}
+
+bacnet_enumeration! {
+	// The data type of a property value, as the CAS BACnet Stack numbers them
+	pub enum DataType: u32 {
+		Null = 0 => "null",
+		Boolean = 1 => "boolean",
+		UnsignedInteger = 2 => "unsigned-integer",
+		SignedInteger = 3 => "signed-integer",
+		Real = 4 => "real",
+		Double = 5 => "double",
+		OctetString = 6 => "octet-string",
+		CharacterString = 7 => "character-string",
+		BitString = 8 => "bit-string",
+		Enumerated = 9 => "enumerated",
+		Date = 10 => "date",
+		Time = 11 => "time",
+		BacnetObjectIdentifier = 12 => "bacnet-object-identifier",
+		DateTime = 27 => "datetime",
+	}
+}
+
+bacnet_enumeration! {
+	// The character set of a BACnet character string
+	pub enum CharacterSet: u8 {
+		Utf8 = 0 => "utf-8",
+		IbmMicrosoftDbcs = 1 => "ibm-microsoft-dbcs",
+		JisX0208 = 2 => "jis-x-0208",
+		Ucs4 = 3 => "iso-10646-ucs-4",
+		Ucs2 = 4 => "iso-10646-ucs-2",
+		Iso8859Latin1 = 5 => "iso-8859-1",
+	}
+}
+
+// UTF-8, ISO-8859-1 and UCS-2 are supported. UCS-2 is two bytes per character, most significant
+// first, for the characters of the Basic Multilingual Plane.
+impl CharacterSet {
+	pub fn is_supported(self) -> bool {
+		matches!(self, CharacterSet::Utf8 | CharacterSet::Ucs2 | CharacterSet::Iso8859Latin1)
+	}
+
+	// The bytes of the text, None when the character set has no code for one of its characters
+	pub fn encode(self, text: &str) -> Option<Vec<u8>> {
+		match self {
+			CharacterSet::Utf8 => Some(text.as_bytes().to_vec()),
+			CharacterSet::Iso8859Latin1 => text.chars().map(|character| u8::try_from(character).ok()).collect(),
+			CharacterSet::Ucs2 => {
+				let mut bytes = Vec::with_capacity(text.len() * 2);
+				for character in text.chars() {
+					bytes.extend_from_slice(&u16::try_from(character).ok()?.to_be_bytes());
+				}
+				Some(bytes)
+			},
+			_ => None
+		}
+	}
+
+	// The text of the bytes, None when they are not valid in the character set
+	pub fn decode(self, bytes: &[u8]) -> Option<String> {
+		match self {
+			CharacterSet::Utf8 => String::from_utf8(bytes.to_vec()).ok(),
+			CharacterSet::Iso8859Latin1 => Some(bytes.iter().map(|&byte| char::from(byte)).collect()),
+			CharacterSet::Ucs2 if bytes.len().is_multiple_of(2) => bytes.chunks_exact(2).map(|pair| char::from_u32(u16::from_be_bytes([pair[0], pair[1]]).into())).collect(),
+			_ => None
+		}
+	}
+
+	// The length of a null terminator, which is one character
+	pub fn terminator_length(self) -> usize {
+		match self {
+			CharacterSet::Ucs2 => 2,
+			_ => 1
+		}
+	}
+
+	// The longest prefix of encoded bytes up to length that ends on a whole character
+	pub fn character_boundary(self, bytes: &[u8], length: usize) -> usize {
+		if length >= bytes.len() {
+			return bytes.len();
+		}
+		match self {
+			// A UTF-8 continuation byte is 0b10xxxxxx
+			CharacterSet::Utf8 => (0..=length).rev().find(|&index| bytes[index] & 0xC0 != 0x80).unwrap_or(0),
+			_ => length - length % self.terminator_length()
+		}
+	}
+}
+
+bacnet_enumeration! {
+	// BACnetLoggingType, how a trend log decides when to record
+	pub enum LoggingType: u32 {
+		Polled = 0 => "polled",
+		Cov = 1 => "cov",
+		Triggered = 2 => "triggered",
+	}
+}
+
+bacnet_enumeration! {
+	// BACnetEventState, 0-63 are reserved for ASHRAE
+	pub enum EventState: u32, proprietary 64..=65535 {
+		Normal = 0 => "normal",
+		Fault = 1 => "fault",
+		Offnormal = 2 => "offnormal",
+		HighLimit = 3 => "high-limit",
+		LowLimit = 4 => "low-limit",
+		LifeSafetyAlarm = 5 => "life-safety-alarm",
+	}
+}
+
+bacnet_enumeration! {
+	// BACnetNotifyType, whether event notifications are sent as alarms or events
+	pub enum NotifyType: u32 {
+		Alarm = 0 => "alarm",
+		Event = 1 => "event",
+		AckNotification = 2 => "ack-notification",
+	}
+}
+
+bacnet_enumeration! {
+	// BACnetEngineeringUnits. 0-255 and 47808-49999 are reserved for ASHRAE, only the vendor range
+	// between them is accepted as proprietary.
+	pub enum EngineeringUnits: u32, proprietary 256..=47807 {
+		SquareMeters = 0 => "square-meters",
+		SquareFeet = 1 => "square-feet",
+		Milliamperes = 2 => "milliamperes",
+		Amperes = 3 => "amperes",
+		Ohms = 4 => "ohms",
+		Volts = 5 => "volts",
+		Kilovolts = 6 => "kilovolts",
+		Megavolts = 7 => "megavolts",
+		VoltAmperes = 8 => "volt-amperes",
+		KilovoltAmperes = 9 => "kilovolt-amperes",
+		MegavoltAmperes = 10 => "megavolt-amperes",
+		VoltAmperesReactive = 11 => "volt-amperes-reactive",
+		KilovoltAmperesReactive = 12 => "kilovolt-amperes-reactive",
+		MegavoltAmperesReactive = 13 => "megavolt-amperes-reactive",
+		DegreesPhase = 14 => "degrees-phase",
+		PowerFactor = 15 => "power-factor",
+		Joules = 16 => "joules",
+		Kilojoules = 17 => "kilojoules",
+		WattHours = 18 => "watt-hours",
+		KilowattHours = 19 => "kilowatt-hours",
+		Btus = 20 => "btus",
+		Therms = 21 => "therms",
+		TonHours = 22 => "ton-hours",
+		JoulesPerKilogramDryAir = 23 => "joules-per-kilogram-dry-air",
+		BtusPerPoundDryAir = 24 => "btus-per-pound-dry-air",
+		CyclesPerHour = 25 => "cycles-per-hour",
+		CyclesPerMinute = 26 => "cycles-per-minute",
+		Hertz = 27 => "hertz",
+		GramsOfWaterPerKilogramDryAir = 28 => "grams-of-water-per-kilogram-dry-air",
+		PercentRelativeHumidity = 29 => "percent-relative-humidity",
+		Millimeters = 30 => "millimeters",
+		Meters = 31 => "meters",
+		Inches = 32 => "inches",
+		Feet = 33 => "feet",
+		WattsPerSquareFoot = 34 => "watts-per-square-foot",
+		WattsPerSquareMeter = 35 => "watts-per-square-meter",
+		Lumens = 36 => "lumens",
+		Luxes = 37 => "luxes",
+		FootCandles = 38 => "foot-candles",
+		Kilograms = 39 => "kilograms",
+		PoundsMass = 40 => "pounds-mass",
+		Tons = 41 => "tons",
+		KilogramsPerSecond = 42 => "kilograms-per-second",
+		KilogramsPerMinute = 43 => "kilograms-per-minute",
+		KilogramsPerHour = 44 => "kilograms-per-hour",
+		PoundsMassPerMinute = 45 => "pounds-mass-per-minute",
+		PoundsMassPerHour = 46 => "pounds-mass-per-hour",
+		Watts = 47 => "watts",
+		Kilowatts = 48 => "kilowatts",
+		Megawatts = 49 => "megawatts",
+		BtusPerHour = 50 => "btus-per-hour",
+		Horsepower = 51 => "horsepower",
+		TonsRefrigeration = 52 => "tons-refrigeration",
+		Pascals = 53 => "pascals",
+		Kilopascals = 54 => "kilopascals",
+		Bars = 55 => "bars",
+		PoundsForcePerSquareInch = 56 => "pounds-force-per-square-inch",
+		CentimetersOfWater = 57 => "centimeters-of-water",
+		InchesOfWater = 58 => "inches-of-water",
+		MillimetersOfMercury = 59 => "millimeters-of-mercury",
+		CentimetersOfMercury = 60 => "centimeters-of-mercury",
+		InchesOfMercury = 61 => "inches-of-mercury",
+		DegreesCelsius = 62 => "degrees-celsius",
+		DegreesKelvin = 63 => "degrees-kelvin",
+		DegreesFahrenheit = 64 => "degrees-fahrenheit",
+		DegreeDaysCelsius = 65 => "degree-days-celsius",
+		DegreeDaysFahrenheit = 66 => "degree-days-fahrenheit",
+		Years = 67 => "years",
+		Months = 68 => "months",
+		Weeks = 69 => "weeks",
+		Days = 70 => "days",
+		Hours = 71 => "hours",
+		Minutes = 72 => "minutes",
+		Seconds = 73 => "seconds",
+		MetersPerSecond = 74 => "meters-per-second",
+		KilometersPerHour = 75 => "kilometers-per-hour",
+		FeetPerSecond = 76 => "feet-per-second",
+		FeetPerMinute = 77 => "feet-per-minute",
+		MilesPerHour = 78 => "miles-per-hour",
+		CubicFeet = 79 => "cubic-feet",
+		CubicMeters = 80 => "cubic-meters",
+		ImperialGallons = 81 => "imperial-gallons",
+		Liters = 82 => "liters",
+		UsGallons = 83 => "us-gallons",
+		CubicFeetPerMinute = 84 => "cubic-feet-per-minute",
+		CubicMetersPerSecond = 85 => "cubic-meters-per-second",
+		ImperialGallonsPerMinute = 86 => "imperial-gallons-per-minute",
+		LitersPerSecond = 87 => "liters-per-second",
+		LitersPerMinute = 88 => "liters-per-minute",
+		UsGallonsPerMinute = 89 => "us-gallons-per-minute",
+		DegreesAngular = 90 => "degrees-angular",
+		DegreesCelsiusPerHour = 91 => "degrees-celsius-per-hour",
+		DegreesCelsiusPerMinute = 92 => "degrees-celsius-per-minute",
+		DegreesFahrenheitPerHour = 93 => "degrees-fahrenheit-per-hour",
+		DegreesFahrenheitPerMinute = 94 => "degrees-fahrenheit-per-minute",
+		NoUnits = 95 => "no-units",
+		PartsPerMillion = 96 => "parts-per-million",
+		PartsPerBillion = 97 => "parts-per-billion",
+		Percent = 98 => "percent",
+		PercentPerSecond = 99 => "percent-per-second",
+		PerMinute = 100 => "per-minute",
+		PerSecond = 101 => "per-second",
+		PsiPerDegreeFahrenheit = 102 => "psi-per-degree-fahrenheit",
+		Radians = 103 => "radians",
+		RevolutionsPerMinute = 104 => "revolutions-per-minute",
+		Currency1 = 105 => "currency1",
+		Currency2 = 106 => "currency2",
+		Currency3 = 107 => "currency3",
+		Currency4 = 108 => "currency4",
+		Currency5 = 109 => "currency5",
+		Currency6 = 110 => "currency6",
+		Currency7 = 111 => "currency7",
+		Currency8 = 112 => "currency8",
+		Currency9 = 113 => "currency9",
+		Currency10 = 114 => "currency10",
+		SquareInches = 115 => "square-inches",
+		SquareCentimeters = 116 => "square-centimeters",
+		BtusPerPound = 117 => "btus-per-pound",
+		Centimeters = 118 => "centimeters",
+		PoundsMassPerSecond = 119 => "pounds-mass-per-second",
+		DeltaDegreesFahrenheit = 120 => "delta-degrees-fahrenheit",
+		DeltaDegreesKelvin = 121 => "delta-degrees-kelvin",
+		Kilohms = 122 => "kilohms",
+		Megohms = 123 => "megohms",
+		Millivolts = 124 => "millivolts",
+		KilojoulesPerKilogram = 125 => "kilojoules-per-kilogram",
+		Megajoules = 126 => "megajoules",
+		JoulesPerDegreeKelvin = 127 => "joules-per-degree-kelvin",
+		JoulesPerKilogramDegreeKelvin = 128 => "joules-per-kilogram-degree-kelvin",
+		Kilohertz = 129 => "kilohertz",
+		Megahertz = 130 => "megahertz",
+		PerHour = 131 => "per-hour",
+		Milliwatts = 132 => "milliwatts",
+		Hectopascals = 133 => "hectopascals",
+		Millibars = 134 => "millibars",
+		CubicMetersPerHour = 135 => "cubic-meters-per-hour",
+		LitersPerHour = 136 => "liters-per-hour",
+		KilowattHoursPerSquareMeter = 137 => "kilowatt-hours-per-square-meter",
+		KilowattHoursPerSquareFoot = 138 => "kilowatt-hours-per-square-foot",
+		MegajoulesPerSquareMeter = 139 => "megajoules-per-square-meter",
+		MegajoulesPerSquareFoot = 140 => "megajoules-per-square-foot",
+		WattsPerSquareMeterDegreeKelvin = 141 => "watts-per-square-meter-degree-kelvin",
+		CubicFeetPerSecond = 142 => "cubic-feet-per-second",
+		PercentObscurationPerFoot = 143 => "percent-obscuration-per-foot",
+		PercentObscurationPerMeter = 144 => "percent-obscuration-per-meter",
+		Milliohms = 145 => "milliohms",
+		MegawattHours = 146 => "megawatt-hours",
+		KiloBtus = 147 => "kilo-btus",
+		MegaBtus = 148 => "mega-btus",
+		KilojoulesPerKilogramDryAir = 149 => "kilojoules-per-kilogram-dry-air",
+		MegajoulesPerKilogramDryAir = 150 => "megajoules-per-kilogram-dry-air",
+		KilojoulesPerDegreeKelvin = 151 => "kilojoules-per-degree-kelvin",
+		MegajoulesPerDegreeKelvin = 152 => "megajoules-per-degree-kelvin",
+		Newton = 153 => "newton",
+		GramsPerSecond = 154 => "grams-per-second",
+		GramsPerMinute = 155 => "grams-per-minute",
+		TonsPerHour = 156 => "tons-per-hour",
+		KiloBtusPerHour = 157 => "kilo-btus-per-hour",
+		HundredthsSeconds = 158 => "hundredths-seconds",
+		Milliseconds = 159 => "milliseconds",
+		NewtonMeters = 160 => "newton-meters",
+		MillimetersPerSecond = 161 => "millimeters-per-second",
+		MillimetersPerMinute = 162 => "millimeters-per-minute",
+		MetersPerMinute = 163 => "meters-per-minute",
+		MetersPerHour = 164 => "meters-per-hour",
+		CubicMetersPerMinute = 165 => "cubic-meters-per-minute",
+		MetersPerSecondPerSecond = 166 => "meters-per-second-per-second",
+		AmperesPerMeter = 167 => "amperes-per-meter",
+		AmperesPerSquareMeter = 168 => "amperes-per-square-meter",
+		AmpereSquareMeters = 169 => "ampere-square-meters",
+		Farads = 170 => "farads",
+		Henrys = 171 => "henrys",
+		OhmMeters = 172 => "ohm-meters",
+		Siemens = 173 => "siemens",
+		SiemensPerMeter = 174 => "siemens-per-meter",
+		Teslas = 175 => "teslas",
+		VoltsPerDegreeKelvin = 176 => "volts-per-degree-kelvin",
+		VoltsPerMeter = 177 => "volts-per-meter",
+		Webers = 178 => "webers",
+		Candelas = 179 => "candelas",
+		CandelasPerSquareMeter = 180 => "candelas-per-square-meter",
+		DegreesKelvinPerHour = 181 => "degrees-kelvin-per-hour",
+		DegreesKelvinPerMinute = 182 => "degrees-kelvin-per-minute",
+		JouleSeconds = 183 => "joule-seconds",
+		RadiansPerSecond = 184 => "radians-per-second",
+		SquareMetersPerNewton = 185 => "square-meters-per-newton",
+		KilogramsPerCubicMeter = 186 => "kilograms-per-cubic-meter",
+		NewtonSeconds = 187 => "newton-seconds",
+		NewtonsPerMeter = 188 => "newtons-per-meter",
+		WattsPerMeterPerDegreeKelvin = 189 => "watts-per-meter-per-degree-kelvin",
+		Microsiemens = 190 => "microsiemens",
+		CubicFeetPerHour = 191 => "cubic-feet-per-hour",
+		UsGallonsPerHour = 192 => "us-gallons-per-hour",
+		Kilometers = 193 => "kilometers",
+		Micrometers = 194 => "micrometers",
+		Grams = 195 => "grams",
+		Milligrams = 196 => "milligrams",
+		Milliliters = 197 => "milliliters",
+		MillilitersPerSecond = 198 => "milliliters-per-second",
+		Decibels = 199 => "decibels",
+		DecibelsMillivolt = 200 => "decibels-millivolt",
+		DecibelsVolt = 201 => "decibels-volt",
+		Millisiemens = 202 => "millisiemens",
+		WattHoursReactive = 203 => "watt-hours-reactive",
+		KilowattHoursReactive = 204 => "kilowatt-hours-reactive",
+		MegawattHoursReactive = 205 => "megawatt-hours-reactive",
+		MillimetersOfWater = 206 => "millimeters-of-water",
+		PerMille = 207 => "per-mille",
+		GramsPerGram = 208 => "grams-per-gram",
+		KilogramsPerKilogram = 209 => "kilograms-per-kilogram",
+		GramsPerKilogram = 210 => "grams-per-kilogram",
+		MilligramsPerGram = 211 => "milligrams-per-gram",
+		MilligramsPerKilogram = 212 => "milligrams-per-kilogram",
+		GramsPerMilliliter = 213 => "grams-per-milliliter",
+		GramsPerLiter = 214 => "grams-per-liter",
+		MilligramsPerLiter = 215 => "milligrams-per-liter",
+		MicrogramsPerLiter = 216 => "micrograms-per-liter",
+		GramsPerCubicMeter = 217 => "grams-per-cubic-meter",
+		MilligramsPerCubicMeter = 218 => "milligrams-per-cubic-meter",
+		MicrogramsPerCubicMeter = 219 => "micrograms-per-cubic-meter",
+		NanogramsPerCubicMeter = 220 => "nanograms-per-cubic-meter",
+		GramsPerCubicCentimeter = 221 => "grams-per-cubic-centimeter",
+		Becquerels = 222 => "becquerels",
+		Kilobecquerels = 223 => "kilobecquerels",
+		Megabecquerels = 224 => "megabecquerels",
+		Gray = 225 => "gray",
+		Milligray = 226 => "milligray",
+		Microgray = 227 => "microgray",
+		Sieverts = 228 => "sieverts",
+		Millisieverts = 229 => "millisieverts",
+		Microsieverts = 230 => "microsieverts",
+		MicrosievertsPerHour = 231 => "microsieverts-per-hour",
+		DecibelsA = 232 => "decibels-a",
+		NephelometricTurbidityUnit = 233 => "nephelometric-turbidity-unit",
+		Ph = 234 => "ph",
+		GramsPerSquareMeter = 235 => "grams-per-square-meter",
+		MinutesPerDegreeKelvin = 236 => "minutes-per-degree-kelvin",
+		OhmMeterSquaredPerMeter = 237 => "ohm-meter-squared-per-meter",
+		AmpereSeconds = 238 => "ampere-seconds",
+		VoltAmpereHours = 239 => "volt-ampere-hours",
+		KilovoltAmpereHours = 240 => "kilovolt-ampere-hours",
+		MegavoltAmpereHours = 241 => "megavolt-ampere-hours",
+		VoltAmpereHoursReactive = 242 => "volt-ampere-hours-reactive",
+		KilovoltAmpereHoursReactive = 243 => "kilovolt-ampere-hours-reactive",
+		MegavoltAmpereHoursReactive = 244 => "megavolt-ampere-hours-reactive",
+		VoltSquareHours = 245 => "volt-square-hours",
+		AmpereSquareHours = 246 => "ampere-square-hours",
+		JoulePerHours = 247 => "joule-per-hours",
+		CubicFeetPerDay = 248 => "cubic-feet-per-day",
+		CubicMetersPerDay = 249 => "cubic-meters-per-day",
+		WattHoursPerCubicMeter = 250 => "watt-hours-per-cubic-meter",
+		JoulesPerCubicMeter = 251 => "joules-per-cubic-meter",
+		MolePercent = 252 => "mole-percent",
+		PascalSeconds = 253 => "pascal-seconds",
+		MillionStandardCubicFeetPerMinute = 254 => "million-standard-cubic-feet-per-minute",
+		StandardCubicFeetPerDay = 47808 => "standard-cubic-feet-per-day",
+		MillionStandardCubicFeetPerDay = 47809 => "million-standard-cubic-feet-per-day",
+		ThousandCubicFeetPerDay = 47810 => "thousand-cubic-feet-per-day",
+		ThousandStandardCubicFeetPerDay = 47811 => "thousand-standard-cubic-feet-per-day",
+		PoundsMassPerDay = 47812 => "pounds-mass-per-day",
+		Millirems = 47814 => "millirems",
+		MilliremsPerHour = 47815 => "millirems-per-hour",
+		DegreesLovibond = 47816 => "degrees-lovibond",
+		AlcoholByVolume = 47817 => "alcohol-by-volume",
+		InternationalBitteringUnits = 47818 => "international-bittering-units",
+		EuropeanBitternessUnits = 47819 => "european-bitterness-units",
+		DegreesPlato = 47820 => "degrees-plato",
+		SpecificGravity = 47821 => "specific-gravity",
+		EuropeanBrewingConvention = 47822 => "european-brewing-convention",
+	}
+}
+
+// BACnetEventTransitionBits, as in Event_Enable and Ack_Required
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EventTransitions {
+	pub to_offnormal: bool,
+	pub to_fault: bool,
+	pub to_normal: bool
+}
+
+impl EventTransitions {
+	pub fn to_bits(self) -> Vec<bool> {
+		vec![self.to_offnormal, self.to_fault, self.to_normal]
+	}
+
+	pub fn from_bits(bits: &[bool]) -> Result<EventTransitions, ErrorCode> {
+		match *bits {
+			[to_offnormal, to_fault, to_normal] => Ok(EventTransitions { to_offnormal, to_fault, to_normal }),
+			_ => Err(ErrorCode::ValueOutOfRange)
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const SUPPORTED: [CharacterSet; 3] = [CharacterSet::Utf8, CharacterSet::Iso8859Latin1, CharacterSet::Ucs2];
+
+	#[test]
+	fn character_sets_round_trip() {
+		for (character_set, text) in [(CharacterSet::Utf8, "Grüße € 😀"), (CharacterSet::Iso8859Latin1, "Grüße ÿ"), (CharacterSet::Ucs2, "Grüße € ひらがな")] {
+			let bytes = character_set.encode(text).unwrap();
+			assert_eq!(character_set.decode(&bytes).as_deref(), Some(text), "{character_set}");
+		}
+		assert_eq!(CharacterSet::Iso8859Latin1.encode("Grüße"), Some(b"Gr\xFC\xDFe".to_vec()));
+		assert_eq!(CharacterSet::Ucs2.encode("A€"), Some(vec![0x00, 0x41, 0x20, 0xAC]));
+	}
+
+	#[test]
+	fn characters_outside_a_character_set_are_rejected() {
+		assert_eq!(CharacterSet::Iso8859Latin1.encode("price in €"), None);
+		assert_eq!(CharacterSet::Iso8859Latin1.encode("ひらがな"), None);
+		// UCS-2 has no surrogate pairs for characters outside the Basic Multilingual Plane
+		assert_eq!(CharacterSet::Ucs2.encode("😀"), None);
+		assert_eq!(CharacterSet::Ucs2.decode(&[0xD8, 0x3D, 0xDE, 0x00]), None);
+		assert_eq!(CharacterSet::Ucs2.decode(&[0x00, 0x41, 0x00]), None);
+		assert_eq!(CharacterSet::Utf8.decode(&[0x47, 0xC3]), None);
+		for character_set in [CharacterSet::IbmMicrosoftDbcs, CharacterSet::JisX0208, CharacterSet::Ucs4] {
+			assert!(!character_set.is_supported());
+			assert_eq!(character_set.encode("text"), None);
+			assert_eq!(character_set.decode(b"text"), None);
+		}
+	}
+
+	#[test]
+	fn truncation_keeps_whole_characters() {
+		for character_set in SUPPORTED {
+			let text = if character_set == CharacterSet::Iso8859Latin1 { "aéÿ ß" } else { "aé€ ひ" };
+			let bytes = character_set.encode(text).unwrap();
+			for length in 0..=bytes.len() + 1 {
+				let boundary = character_set.character_boundary(&bytes, length);
+				assert!(boundary <= length.min(bytes.len()), "{character_set} {length}");
+				let prefix = character_set.decode(&bytes[..boundary]).unwrap_or_else(|| panic!("{character_set} split a character at {length}"));
+				assert!(text.starts_with(&prefix));
+				// Nothing more fits without splitting the next character
+				if boundary < length.min(bytes.len()) {
+					assert_eq!(character_set.decode(&bytes[..length.min(bytes.len())]), None, "{character_set} {length}");
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn truncation_never_splits_a_utf8_sequence() {
+		// 1, 2, 3 and 4 byte sequences
+		let bytes = "aé€😀".as_bytes();
+		let boundaries: Vec<usize> = (0..=bytes.len()).map(|length| CharacterSet::Utf8.character_boundary(bytes, length)).collect();
+		assert_eq!(boundaries, [0, 1, 1, 3, 3, 3, 6, 6, 6, 6, 10]);
+	}
+
+	#[test]
+	fn truncation_never_splits_a_ucs2_code_unit() {
+		let bytes = CharacterSet::Ucs2.encode("ab€").unwrap();
+		let boundaries: Vec<usize> = (0..=bytes.len()).map(|length| CharacterSet::Ucs2.character_boundary(&bytes, length)).collect();
+		assert_eq!(boundaries, [0, 0, 2, 2, 4, 4, 6]);
+		assert_eq!(CharacterSet::Ucs2.terminator_length(), 2);
+	}
+}
@@ -1,452 +1,666 @@
 use std::env;
+use std::fmt;
 use std::os::raw::c_char;
-use once_cell::sync::Lazy;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
-// Picks the correct extension depending on OS
-fn get_extension(system: &str) -> &str {
-    if system == "windows" {
-        ".dll"
-    } else {
-        ".so"
-    }
+use crate::bacnet_types::{DataType, EventTransitions, NotifyType, ObjectType, PropertyIdentifier, ServiceSupported};
+
+// Environment variables used to locate the CAS BACnet Stack library
+pub const ENV_STACK_LIBRARY: &str = "CAS_BACNET_STACK_LIBRARY";
+pub const ENV_STACK_VARIANT: &str = "CAS_BACNET_STACK_VARIANT";
+pub const ENV_STACK_ARCHITECTURE: &str = "CAS_BACNET_STACK_ARCHITECTURE";
+pub const ENV_STACK_SEARCH_PATH: &str = "CAS_BACNET_STACK_SEARCH_PATH";
+
+// Directories searched after any user supplied search paths
+const DEFAULT_SEARCH_DIRECTORIES: [&str; 2] = ["./bin", "."];
+#[cfg(unix)]
+const SYSTEM_SEARCH_DIRECTORIES: [&str; 4] = ["/opt/cas-bacnet-stack/lib", "/opt/cas-bacnet-stack", "/usr/local/lib", "/usr/lib"];
+#[cfg(not(unix))]
+const SYSTEM_SEARCH_DIRECTORIES: [&str; 0] = [];
+
+// Build variant of the CAS BACnet Stack library
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LibraryVariant {
+    Debug,
+    Release
 }
 
-// Load library for CAS BACnet Stack once
-fn load_library() -> libloading::Library {
-    unsafe {
-        return libloading::Library::new("./bin/CASBACnetStack_x64_Debug".to_owned() + get_extension(env::consts::OS)).unwrap();
+impl fmt::Display for LibraryVariant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LibraryVariant::Debug => write!(f, "Debug"),
+            LibraryVariant::Release => write!(f, "Release")
+        }
     }
 }
 
-static lib: Lazy<libloading::Library> = Lazy::new(|| {
-    load_library()
-});
+impl FromStr for LibraryVariant {
+    type Err = String;
 
-// Versioning
-pub fn get_api_major_version() -> Result<u32, Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn() -> u32> = lib.get(b"BACnetStack_GetAPIMajorVersion")?;
-        Ok(func())
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "debug" => Ok(LibraryVariant::Debug),
+            "release" => Ok(LibraryVariant::Release),
+            _ => Err(format!("unknown library variant \"{s}\", expected Debug or Release"))
+        }
     }
 }
-pub fn get_api_minor_version() -> Result<u32, Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn() -> u32> = lib.get(b"BACnetStack_GetAPIMinorVersion")?;
-        Ok(func())
-    }
-}
-pub fn get_api_patch_version() -> Result<u32, Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn() -> u32> = lib.get(b"BACnetStack_GetAPIPatchVersion")?;
-        Ok(func())
-    }
+
+// Target architecture of the CAS BACnet Stack library
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LibraryArchitecture {
+    X86,
+    X64
 }
-pub fn get_api_build_version() -> Result<u32, Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn() -> u32> = lib.get(b"BACnetStack_GetAPIBuildVersion")?;
-        Ok(func())
+
+impl fmt::Display for LibraryArchitecture {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LibraryArchitecture::X86 => write!(f, "x86"),
+            LibraryArchitecture::X64 => write!(f, "x64")
+        }
     }
 }
 
-// Main Loop
-pub fn bacnet_loop() -> Result<(), Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn()> = lib.get(b"BACnetStack_Loop")?;
-        Ok(func())
+impl FromStr for LibraryArchitecture {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "x86" | "win32" | "i686" => Ok(LibraryArchitecture::X86),
+            "x64" | "x86_64" | "amd64" => Ok(LibraryArchitecture::X64),
+            _ => Err(format!("unknown library architecture \"{s}\", expected x86 or x64"))
+        }
     }
 }
-/*
-pub fn bacnet_tick() -> Result<bool, Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn() -> bool> = lib.get(b"BACnetStack_Tick")?;
-        Ok(func())
-    }
+
+// Where to look for the CAS BACnet Stack library. Unset fields fall back to the defaults.
+#[derive(Clone, Debug, Default)]
+pub struct LibraryOptions {
+    pub path: Option<PathBuf>,
+    pub variant: Option<LibraryVariant>,
+    pub architecture: Option<LibraryArchitecture>,
+    pub search_paths: Vec<PathBuf>
 }
-*/
 
-// Device Setup Functions
-pub fn add_device(device_instance: u32) -> Result<bool, Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn(u32) -> bool> = lib.get(b"BACnetStack_AddDevice")?;
-        Ok(func(device_instance))
+impl LibraryOptions {
+    // Reads the CAS_BACNET_STACK_* environment variables
+    pub fn from_env() -> Result<LibraryOptions, String> {
+        let mut options = LibraryOptions::default();
+        if let Some(path) = env::var_os(ENV_STACK_LIBRARY) {
+            options.path = Some(PathBuf::from(path));
+        }
+        if let Ok(variant) = env::var(ENV_STACK_VARIANT) {
+            options.variant = Some(variant.parse()?);
+        }
+        if let Ok(architecture) = env::var(ENV_STACK_ARCHITECTURE) {
+            options.architecture = Some(architecture.parse()?);
+        }
+        if let Some(search_path) = env::var_os(ENV_STACK_SEARCH_PATH) {
+            options.search_paths = env::split_paths(&search_path).collect();
+        }
+        Ok(options)
     }
-}
-pub fn add_object(device_instance: u32, object_type: u16, object_instance: u32) -> Result<bool, Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn(u32, u16, u32) -> bool> = lib.get(b"BACnetStack_AddObject")?;
-        Ok(func(device_instance, object_type, object_instance))
+
+    // Returns these options with every field set in `other` taking precedence
+    pub fn overridden_by(self, other: LibraryOptions) -> LibraryOptions {
+        LibraryOptions {
+            path: other.path.or(self.path),
+            variant: other.variant.or(self.variant),
+            architecture: other.architecture.or(self.architecture),
+            search_paths: if other.search_paths.is_empty() { self.search_paths } else { other.search_paths }
+        }
     }
-}
-/*
-pub fn add_network_port_object(a: u32, b: u16, c: u8, d: u8, e: u32) -> Result<bool, Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn(u32, u16, u8, u8, u32) -> bool> = lib.get(b"BACnetStack_AddNetworkPortObject")?;
-        Ok(func(a, b, c, d, e))
+
+    // File names for the selected variant, e.g. CASBACnetStack_x64_Debug.so
+    fn file_names(&self) -> Vec<String> {
+        let variant = self.variant.unwrap_or(LibraryVariant::Debug);
+        let architecture = self.architecture.unwrap_or(LibraryArchitecture::X64);
+        let file_name = format!("CASBACnetStack_{architecture}_{variant}{}", get_extension(env::consts::OS));
+        if env::consts::OS == "windows" {
+            vec![file_name]
+        } else {
+            vec![file_name.clone(), format!("lib{file_name}")]
+        }
     }
-}
-pub fn add_trend_log_object(a: u32, b: u32, c: u16, d: u32, e: u32, f: u32, g: bool, h: u32) -> Result<bool, Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn(u32, u32, u16, u32, u32, u32, bool, u32) -> bool> = lib.get(b"BACnetStack_AddTrendLogObject")?;
-        Ok(func(a, b, c, d, e, f, g, h))
+
+    // Every path that will be tried, in order
+    pub fn candidates(&self) -> Vec<PathBuf> {
+        if let Some(path) = &self.path {
+            return vec![path.clone()];
+        }
+
+        let mut directories: Vec<PathBuf> = self.search_paths.clone();
+        directories.extend(DEFAULT_SEARCH_DIRECTORIES.iter().map(PathBuf::from));
+        if let Some(exe_directory) = env::current_exe().ok().and_then(|exe| exe.parent().map(|dir| dir.to_path_buf())) {
+            directories.push(exe_directory.join("bin"));
+            directories.push(exe_directory);
+        }
+        directories.extend(SYSTEM_SEARCH_DIRECTORIES.iter().map(PathBuf::from));
+
+        let file_names = self.file_names();
+        directories.iter()
+            .flat_map(|directory| file_names.iter().map(move |file_name| directory.join(file_name)))
+            .collect()
     }
 }
-pub fn add_trend_log_multiple_object(a: u32, b: u32, c: u32) -> Result<bool, Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn(u32, u32, u32) -> bool> = lib.get(b"BACnetStack_AddTrendLogMultipleObject")?;
-        Ok(func(a, b, c))
-    }
+
+// Returned when none of the candidate paths could be loaded
+#[derive(Debug)]
+pub struct LibraryLoadError {
+    pub attempts: Vec<(PathBuf, String)>
 }
-pub fn add_notification_class_object(a: u32, b: u32, c: u8, d: u8, e: u8, f: bool, g: bool, h: bool) -> Result<bool, Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn(u32, u32, u8, u8, u8, bool, bool, bool) -> bool> = lib.get(b"BACnetStack_AddNotificationClassObject")?;
-        Ok(func(a, b, c, d, e, f, g, h))
+
+impl fmt::Display for LibraryLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Unable to load the CAS BACnet Stack library. Paths tried:")?;
+        for (path, reason) in &self.attempts {
+            write!(f, "\n  {}: {}", path.display(), reason)?;
+        }
+        Ok(())
     }
 }
-*/
 
-// Property Setup Functions
-pub fn set_property_enabled(device_instance: u32, object_type: u16, object_instance: u32, property_identifier: u32, enabled: bool) -> Result<bool, Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn(u32, u16, u32, u32, bool) -> bool> = lib.get(b"BACnetStack_SetPropertyEnabled")?;
-        Ok(func(device_instance, object_type, object_instance, property_identifier, enabled))
+impl std::error::Error for LibraryLoadError {}
+
+// Picks the correct extension depending on OS
+fn get_extension(system: &str) -> &str {
+    if system == "windows" {
+        ".dll"
+    } else {
+        ".so"
     }
 }
-pub fn set_property_by_object_type_enabled(device_instance: u32, object_type: u16, property_identifier: u32, enabled: bool) -> Result<bool, Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn(u32, u16, u32, bool) -> bool> = lib.get(b"BACnetStack_SetPropertyByObjectTypeEnabled")?;
-        Ok(func(device_instance, object_type, property_identifier, enabled))
-    }
+
+// Load library for CAS BACnet Stack. Returns the library and the path that was loaded.
+fn load_library(options: &LibraryOptions) -> Result<(libloading::Library, PathBuf), LibraryLoadError> {
+    let mut attempts = Vec::new();
+    for path in options.candidates() {
+        if !path.is_file() {
+            attempts.push((path, "file not found".to_string()));
+            continue;
+        }
+        match unsafe { libloading::Library::new(&path) } {
+            Ok(library) => return Ok((library, path)),
+            Err(err) => attempts.push((path, err.to_string()))
+        }
+    }
+    Err(LibraryLoadError { attempts })
 }
-/*
-pub fn set_proprietary_property(a: u32, b: u16, c: u32, d: u32, e: bool, f: bool, g: u32, h: bool, i: bool, j: bool) -> Result<bool, Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn(u32, u16, u32, u32, bool, bool, u32, bool, bool, bool) -> bool> = lib.get(b"BACnetStack_SetProprietaryProperty")?;
-        Ok(func(a, b, c, d, e, f, g, h, i, j))
-    }
+
+// What a failed stack call was operating on
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StackCallContext {
+    pub device_instance: u32,
+    pub object_type: Option<ObjectType>,
+    pub object_instance: Option<u32>,
+    pub property_identifier: Option<PropertyIdentifier>,
+    pub service: Option<ServiceSupported>
 }
-pub fn set_property_writable(a: u32, b: u16, c: u32, d: u32, e: bool) -> Result<bool, Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn(u32, u16, u32, u32, bool) -> bool> = lib.get(b"BACnetStack_SetPropertyWritable")?;
-        Ok(func(a, b, c, d, e))
+
+impl fmt::Display for StackCallContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "device {}", self.device_instance)?;
+        if let Some(object_type) = self.object_type {
+            write!(f, ", object type {object_type}")?;
+        }
+        if let Some(object_instance) = self.object_instance {
+            write!(f, ", object instance {object_instance}")?;
+        }
+        if let Some(property_identifier) = self.property_identifier {
+            write!(f, ", property {property_identifier}")?;
+        }
+        if let Some(service) = self.service {
+            write!(f, ", service {service}")?;
+        }
+        Ok(())
     }
 }
-pub fn set_property_by_object_type_writable(a: u32, b: u16, c: u32, d: bool) -> Result<bool, Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn(u32, u16, u32, bool) -> bool> = lib.get(b"BACnetStack_SetPropertyByObjectTypeWritable")?;
-        Ok(func(a, b, c, d))
-    }
+
+// Errors returned by the adapter layer
+#[derive(Debug)]
+pub enum AdapterError {
+    // None of the candidate library paths could be loaded
+    LibraryLoad(LibraryLoadError),
+    // The library was loaded but does not export these functions
+    MissingSymbols { library_path: PathBuf, symbols: Vec<&'static str> },
+    // A stack function returned false
+    CallFailed { function: &'static str, context: StackCallContext }
 }
-pub fn set_property_subscribable(a: u32, b: u16, c: u32, d: u32, e: bool) -> Result<bool, Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn(u32, u16, u32, u32, bool) -> bool> = lib.get(b"BACnetStack_SetPropertySubscribable")?;
-        Ok(func(a, b, c, d, e))
+
+impl fmt::Display for AdapterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AdapterError::LibraryLoad(err) => write!(f, "{err}"),
+            AdapterError::MissingSymbols { library_path, symbols } => {
+                write!(f, "The CAS BACnet Stack library {} is missing {} function(s):", library_path.display(), symbols.len())?;
+                for symbol in symbols {
+                    write!(f, "\n  {symbol}")?;
+                }
+                Ok(())
+            },
+            AdapterError::CallFailed { function, context } => write!(f, "{function} failed for {context}")
+        }
     }
 }
-pub fn set_property_by_object_type_subscribable(a: u32, b: u16, c: u32, d: bool) -> Result<bool, Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn(u32, u16, u32, bool) -> bool> = lib.get(b"BACnetStack_SetPropertyByObjectTypeSubscribable")?;
-        Ok(func(a, b, c, d))
+
+impl std::error::Error for AdapterError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            AdapterError::LibraryLoad(err) => Some(err),
+            _ => None
+        }
     }
 }
-pub fn set_object_type_creatable(a: u32, b: u16, c: bool) -> Result<bool, Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn(u32, u16, bool) -> bool> = lib.get(b"BACnetStack_SetObjectTypeCreatable")?;
-        Ok(func(a, b, c))
+
+impl From<LibraryLoadError> for AdapterError {
+    fn from(err: LibraryLoadError) -> Self {
+        AdapterError::LibraryLoad(err)
     }
 }
-pub fn set_object_type_supported(a: u32, b: u16, c: bool) -> Result<bool, Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn(u32, u16, bool) -> bool> = lib.get(b"BACnetStack_SetObjectTypeSupported")?;
-        Ok(func(a, b, c))
+
+// Converts the bool returned by a stack function into a Result
+pub fn check_stack_call(succeeded: bool, function: &'static str, context: StackCallContext) -> Result<(), AdapterError> {
+    if succeeded {
+        Ok(())
+    } else {
+        Err(AdapterError::CallFailed { function, context })
     }
 }
-*/
-pub fn set_service_enabled(device_instance: u32, service: u32, enabled: bool) -> Result<bool, Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn(u32, u32, bool) -> bool> = lib.get(b"BACnetStack_SetServiceEnabled")?;
-        Ok(func(device_instance, service, enabled))
+
+// Callback signatures. The stack calls these through the C ABI.
+// Send and Receive Message Functions
+pub type ReceiveMessageCallback = extern "C" fn(*mut u8, u16, *mut u8, u8, *mut u8, *mut u8) -> u16;
+pub type SendMessageCallback = extern "C" fn(*const u8, u16, *const u8, u8, u8, bool) -> u16;
+
+// System Functions
+pub type GetSystemTimeCallback = extern "C" fn() -> u64;
+pub type SetSystemTimeCallback = extern "C" fn(u32, u8, u8, u8, u8, u8, u8, u8, u8) -> bool;
+
+// Get Data Functions
+pub type GetPropertyBitStringCallback = extern "C" fn(u32, u16, u32, u32, *mut bool, *mut u32, u32, bool, u32) -> bool;
+pub type GetPropertyBoolCallback = extern "C" fn(u32, u16, u32, u32, *mut bool, bool, u32) -> bool;
+pub type GetPropertyCharacterStringCallback = extern "C" fn(u32, u16, u32, u32, *mut c_char, *mut u32, u32, *mut u8, bool, u32) -> bool;
+pub type GetPropertyDateCallback = extern "C" fn(u32, u16, u32, u32, *mut u8, *mut u8, *mut u8, *mut u8, bool, u32) -> bool;
+pub type GetPropertyDoubleCallback = extern "C" fn(u32, u16, u32, u32, *mut f64, bool, u32) -> bool;
+pub type GetPropertyEnumeratedCallback = extern "C" fn(u32, u16, u32, u32, *mut u32, bool, u32) -> bool;
+pub type GetPropertyOctetStringCallback = extern "C" fn(u32, u16, u32, u32, *mut u8, *mut u32, u32, bool, u32) -> bool;
+pub type GetPropertyRealCallback = extern "C" fn(u32, u16, u32, u32, *mut f32, bool, u32) -> bool;
+pub type GetPropertySignedIntegerCallback = extern "C" fn(u32, u16, u32, u32, *mut i32, bool, u32) -> bool;
+pub type GetPropertyTimeCallback = extern "C" fn(u32, u16, u32, u32, *mut u8, *mut u8, *mut u8, *mut u8, bool, u32) -> bool;
+pub type GetPropertyUnsignedIntegerCallback = extern "C" fn(u32, u16, u32, u32, *mut u32, bool, u32) -> bool;
+
+// Set Data Functions
+pub type SetPropertyBitStringCallback = extern "C" fn(u32, u16, u32, u32, *const bool, u32, bool, u32, u8, *mut u32) -> bool;
+pub type SetPropertyBoolCallback = extern "C" fn(u32, u16, u32, u32, bool, bool, u32, u8, *mut u32) -> bool;
+pub type SetPropertyCharacterStringCallback = extern "C" fn(u32, u16, u32, u32, *const c_char, u32, u8, bool, u32, u8, *mut u32) -> bool;
+pub type SetPropertyDateCallback = extern "C" fn(u32, u16, u32, u32, u8, u8, u8, u8, bool, u32, u8, *mut u32) -> bool;
+pub type SetPropertyDoubleCallback = extern "C" fn(u32, u16, u32, u32, f64, bool, u32, u8, *mut u32) -> bool;
+pub type SetPropertyEnumeratedCallback = extern "C" fn(u32, u16, u32, u32, u32, bool, u32, u8, *mut u32) -> bool;
+pub type SetPropertyNullCallback = extern "C" fn(u32, u16, u32, u32, bool, u32, u8, *mut u32) -> bool;
+pub type SetPropertyOctetStringCallback = extern "C" fn(u32, u16, u32, u32, *const u8, u32, bool, u32, u8, *mut u32) -> bool;
+pub type SetPropertyRealCallback = extern "C" fn(u32, u16, u32, u32, f32, bool, u32, u8, *mut u32) -> bool;
+pub type SetPropertySignedIntegerCallback = extern "C" fn(u32, u16, u32, u32, i32, bool, u32, u8, *mut u32) -> bool;
+pub type SetPropertyTimeCallback = extern "C" fn(u32, u16, u32, u32, u8, u8, u8, u8, bool, u32, u8, *mut u32) -> bool;
+pub type SetPropertyUnsignedIntegerCallback = extern "C" fn(u32, u16, u32, u32, u32, bool, u32, u8, *mut u32) -> bool;
+
+// Object Creation Functions
+pub type CreateObjectCallback = extern "C" fn(u32, u16, u32) -> bool;
+pub type DeleteObjectCallback = extern "C" fn(u32, u16, u32) -> bool;
+
+// Remote Device Management Functions
+pub type ReinitializeDeviceCallback = extern "C" fn(u32, u32, *const c_char, u32, *mut u32) -> bool;
+pub type DeviceCommunicationControlCallback = extern "C" fn(u32, u8, *const c_char, u8, bool, u16, *mut u32) -> bool;
+
+// Debug Message Functions
+pub type LogDebugMessageCallback = extern "C" fn(*const c_char, u16, u8);
+
+// Client Hooks
+pub type HookTextMessageCallback = extern "C" fn(u32, bool, u32, *const c_char, u32, u8, *const c_char, u32, *const u8, u8, u8, u16, *const u8, u8, *mut u16, *mut u16) -> bool;
+
+// Runs the body of a registered callback. A panic must not unwind into the C++ stack, so it is
+// logged and `on_panic` is returned to the stack as the failure value instead.
+pub fn catch_callback_panic<R>(callback_name: &str, on_panic: R, callback: impl FnOnce() -> R) -> R {
+    match panic::catch_unwind(AssertUnwindSafe(callback)) {
+        Ok(result) => result,
+        Err(payload) => {
+            let message = payload.downcast_ref::<&str>().copied()
+                .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+                .unwrap_or("unknown panic");
+            eprintln!("ERROR: Panic in {callback_name}: {message}");
+            on_panic
+        }
     }
 }
-/*
-pub fn set_max_active_cov_subscriptions(a: u32, b: u32) -> Result<bool, Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn(u32, u32) -> bool> = lib.get(b"BACnetStack_SetMaxActiveCOVSubscriptions")?;
-        Ok(func(a, b))
-    }
+
+// Declares the BacnetStackApi function table. Every entry is resolved once when the library is
+// loaded and all missing symbols are reported together.
+macro_rules! bacnet_stack_api {
+    ($($field:ident: $symbol:literal => $signature:ty),* $(,)?) => {
+        pub struct BacnetStackApi {
+            library_path: PathBuf,
+            $($field: $signature,)*
+            _library: libloading::Library
+        }
+
+        impl BacnetStackApi {
+            fn resolve(library: libloading::Library, library_path: PathBuf) -> Result<BacnetStackApi, AdapterError> {
+                let mut missing_symbols = Vec::new();
+                $(
+                    let $field = match unsafe { library.get::<$signature>($symbol.as_bytes()) } {
+                        Ok(symbol) => Some(*symbol),
+                        Err(_) => {
+                            missing_symbols.push($symbol);
+                            None
+                        }
+                    };
+                )*
+                if !missing_symbols.is_empty() {
+                    return Err(AdapterError::MissingSymbols { library_path, symbols: missing_symbols });
+                }
+                Ok(BacnetStackApi {
+                    library_path,
+                    $($field: $field.unwrap(),)*
+                    _library: library
+                })
+            }
+        }
+    };
 }
-pub fn set_cov_settings(a: u32, b: u32, c: u32) -> Result<bool, Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn(u32, u32, u32) -> bool> = lib.get(b"BACnetStack_SetCOVSettings")?;
-        Ok(func(a, b, c))
-    }
+
+// A function needs an entry here before the BacnetStack impl can call it
+bacnet_stack_api! {
+    // Versioning
+    get_api_major_version: "BACnetStack_GetAPIMajorVersion" => unsafe extern "C" fn() -> u32,
+    get_api_minor_version: "BACnetStack_GetAPIMinorVersion" => unsafe extern "C" fn() -> u32,
+    get_api_patch_version: "BACnetStack_GetAPIPatchVersion" => unsafe extern "C" fn() -> u32,
+    get_api_build_version: "BACnetStack_GetAPIBuildVersion" => unsafe extern "C" fn() -> u32,
+
+    // Main Loop
+    bacnet_loop: "BACnetStack_Loop" => unsafe extern "C" fn(),
+
+    // Device Setup Functions
+    add_device: "BACnetStack_AddDevice" => unsafe extern "C" fn(u32) -> bool,
+    add_object: "BACnetStack_AddObject" => unsafe extern "C" fn(u32, u16, u32) -> bool,
+    add_notification_class_object: "BACnetStack_AddNotificationClassObject" => unsafe extern "C" fn(u32, u32, u8, u8, u8, bool, bool, bool) -> bool,
+
+    // Property Setup Functions
+    set_property_enabled: "BACnetStack_SetPropertyEnabled" => unsafe extern "C" fn(u32, u16, u32, u32, bool) -> bool,
+    set_property_by_object_type_enabled: "BACnetStack_SetPropertyByObjectTypeEnabled" => unsafe extern "C" fn(u32, u16, u32, bool) -> bool,
+    set_property_writable: "BACnetStack_SetPropertyWritable" => unsafe extern "C" fn(u32, u16, u32, u32, bool) -> bool,
+    set_proprietary_property: "BACnetStack_SetProprietaryProperty" => unsafe extern "C" fn(u32, u16, u32, u32, bool, bool, u32, bool, bool, bool) -> bool,
+    set_service_enabled: "BACnetStack_SetServiceEnabled" => unsafe extern "C" fn(u32, u32, bool) -> bool,
+
+    // Alarm and Event Setup Functions
+    enable_alarms_and_events_for_object: "BACnetStack_EnableAlarmsAndEventsForObject" => unsafe extern "C" fn(u32, u16, u32, u32, u8, bool, bool, bool, bool) -> bool,
+
+    // Data Notification Functions
+    value_updated: "BACnetStack_ValueUpdated" => unsafe extern "C" fn(u32, u16, u32, u32),
+
+    // Callback Registration Functions
+    register_callback_receive_message: "BACnetStack_RegisterCallbackReceiveMessage" => unsafe extern "C" fn(ReceiveMessageCallback),
+    register_callback_send_message: "BACnetStack_RegisterCallbackSendMessage" => unsafe extern "C" fn(SendMessageCallback),
+    register_callback_get_system_time: "BACnetStack_RegisterCallbackGetSystemTime" => unsafe extern "C" fn(GetSystemTimeCallback),
+    register_callback_set_system_time: "BACnetStack_RegisterCallbackSetSystemTime" => unsafe extern "C" fn(SetSystemTimeCallback),
+    register_callback_get_property_bitstring: "BACnetStack_RegisterCallbackGetPropertyBitString" => unsafe extern "C" fn(GetPropertyBitStringCallback),
+    register_callback_get_property_bool: "BACnetStack_RegisterCallbackGetPropertyBool" => unsafe extern "C" fn(GetPropertyBoolCallback),
+    register_callback_get_property_character_string: "BACnetStack_RegisterCallbackGetPropertyCharacterString" => unsafe extern "C" fn(GetPropertyCharacterStringCallback),
+    register_callback_get_property_date: "BACnetStack_RegisterCallbackGetPropertyDate" => unsafe extern "C" fn(GetPropertyDateCallback),
+    register_callback_get_property_double: "BACnetStack_RegisterCallbackGetPropertyDouble" => unsafe extern "C" fn(GetPropertyDoubleCallback),
+    register_callback_get_property_enumerated: "BACnetStack_RegisterCallbackGetPropertyEnumerated" => unsafe extern "C" fn(GetPropertyEnumeratedCallback),
+    register_callback_get_property_octet_string: "BACnetStack_RegisterCallbackGetPropertyOctetString" => unsafe extern "C" fn(GetPropertyOctetStringCallback),
+    register_callback_get_property_real: "BACnetStack_RegisterCallbackGetPropertyReal" => unsafe extern "C" fn(GetPropertyRealCallback),
+    register_callback_get_property_signed_integer: "BACnetStack_RegisterCallbackGetPropertySignedInteger" => unsafe extern "C" fn(GetPropertySignedIntegerCallback),
+    register_callback_get_property_time: "BACnetStack_RegisterCallbackGetPropertyTime" => unsafe extern "C" fn(GetPropertyTimeCallback),
+    register_callback_get_property_unsigned_integer: "BACnetStack_RegisterCallbackGetPropertyUnsignedInteger" => unsafe extern "C" fn(GetPropertyUnsignedIntegerCallback),
+    register_callback_set_property_bitstring: "BACnetStack_RegisterCallbackSetPropertyBitString" => unsafe extern "C" fn(SetPropertyBitStringCallback),
+    register_callback_set_property_bool: "BACnetStack_RegisterCallbackSetPropertyBool" => unsafe extern "C" fn(SetPropertyBoolCallback),
+    register_callback_set_property_character_string: "BACnetStack_RegisterCallbackSetPropertyCharacterString" => unsafe extern "C" fn(SetPropertyCharacterStringCallback),
+    register_callback_set_property_date: "BACnetStack_RegisterCallbackSetPropertyDate" => unsafe extern "C" fn(SetPropertyDateCallback),
+    register_callback_set_property_double: "BACnetStack_RegisterCallbackSetPropertyDouble" => unsafe extern "C" fn(SetPropertyDoubleCallback),
+    register_callback_set_property_enumerated: "BACnetStack_RegisterCallbackSetPropertyEnumerated" => unsafe extern "C" fn(SetPropertyEnumeratedCallback),
+    register_callback_set_property_null: "BACnetStack_RegisterCallbackSetPropertyNull" => unsafe extern "C" fn(SetPropertyNullCallback),
+    register_callback_set_property_octet_string: "BACnetStack_RegisterCallbackSetPropertyOctetString" => unsafe extern "C" fn(SetPropertyOctetStringCallback),
+    register_callback_set_property_real: "BACnetStack_RegisterCallbackSetPropertyReal" => unsafe extern "C" fn(SetPropertyRealCallback),
+    register_callback_set_property_signed_integer: "BACnetStack_RegisterCallbackSetPropertySignedInteger" => unsafe extern "C" fn(SetPropertySignedIntegerCallback),
+    register_callback_set_property_time: "BACnetStack_RegisterCallbackSetPropertyTime" => unsafe extern "C" fn(SetPropertyTimeCallback),
+    register_callback_set_property_unsigned_integer: "BACnetStack_RegisterCallbackSetPropertyUnsignedInteger" => unsafe extern "C" fn(SetPropertyUnsignedIntegerCallback),
 }
-pub fn remove_device(a: u32) -> Result<(), Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn(u32)> = lib.get(b"BACnetStack_RemoveDevice")?;
-        Ok(func(a))
+
+impl BacnetStackApi {
+    // Loads the CAS BACnet Stack library and resolves every function used by this application
+    pub fn load(options: &LibraryOptions) -> Result<BacnetStackApi, AdapterError> {
+        let (library, library_path) = load_library(options)?;
+        BacnetStackApi::resolve(library, library_path)
     }
-}
-pub fn remove_object(a: u32, b: u16, c: u32) -> Result<bool, Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn(u32, u16, u32) -> bool> = lib.get(b"BACnetStack_RemoveObject")?;
-        Ok(func(a, b, c))
+
+    pub fn library_path(&self) -> &Path {
+        &self.library_path
     }
 }
-*/
 
+// The BACnet stack operations used by this application. Implemented by BacnetStackApi for the
+// CAS BACnet Stack library and by MockBacnetStack for running without the library.
+pub trait BacnetStack {
+    // Versioning
+    fn get_api_major_version(&self) -> u32;
+    fn get_api_minor_version(&self) -> u32;
+    fn get_api_patch_version(&self) -> u32;
+    fn get_api_build_version(&self) -> u32;
 
-// Alarm and Event Setup Functions
-/*
-pub fn enable_alarms_and_events_for_object(a: u32, b: u16, c: u32, d: u32, e: u8, f: bool, g: bool, h: bool, i: bool) -> Result<bool, Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn(u32, u16, u32, u32, u8, bool, bool, bool, bool) -> bool> = lib.get(b"BACnetStack_EnableAlarmsAndEventsForObject")?;
-        Ok(func(a, b, c, d, e, f, g, h, i))
-    }
-}
-*/
+    // Main Loop
+    fn bacnet_loop(&self);
 
-// Data Notification Functions
-/*
-pub fn value_updated(a: u32, b: u16, c: u32, d: u32) -> Result<(), Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn(u32, u16, u32, u32)> = lib.get(b"BACnetStack_ValueUpdated")?;
-        Ok(func(a, b, c, d))
-    }
+    // Device Setup Functions
+    fn add_device(&self, device_instance: u32) -> Result<(), AdapterError>;
+    fn add_object(&self, device_instance: u32, object_type: ObjectType, object_instance: u32) -> Result<(), AdapterError>;
+    // Priorities are to-offnormal, to-fault and to-normal
+    fn add_notification_class_object(&self, device_instance: u32, object_instance: u32, priority: [u8; 3], ack_required: EventTransitions) -> Result<(), AdapterError>;
+
+    // Property Setup Functions
+    fn set_property_enabled(&self, device_instance: u32, object_type: ObjectType, object_instance: u32, property_identifier: PropertyIdentifier, enabled: bool) -> Result<(), AdapterError>;
+    fn set_property_by_object_type_enabled(&self, device_instance: u32, object_type: ObjectType, property_identifier: PropertyIdentifier, enabled: bool) -> Result<(), AdapterError>;
+    fn set_property_writable(&self, device_instance: u32, object_type: ObjectType, object_instance: u32, property_identifier: PropertyIdentifier, writable: bool) -> Result<(), AdapterError>;
+    // A vendor specific property of the object, read and written through the typed callbacks
+    #[allow(clippy::too_many_arguments)]
+    fn set_proprietary_property(&self, device_instance: u32, object_type: ObjectType, object_instance: u32, property_identifier: PropertyIdentifier, is_array: bool, data_type: DataType, writable: bool) -> Result<(), AdapterError>;
+    fn set_service_enabled(&self, device_instance: u32, service: ServiceSupported, enabled: bool) -> Result<(), AdapterError>;
+
+    // Alarm and Event Setup Functions
+    #[allow(clippy::too_many_arguments)]
+    fn enable_alarms_and_events_for_object(&self, device_instance: u32, object_type: ObjectType, object_instance: u32, notification_class: u32, notify_type: NotifyType, event_enable: EventTransitions, event_detection_enable: bool) -> Result<(), AdapterError>;
+
+    // Data Notification Functions
+    // Tells the stack that a property changed outside of a write, e.g. the event state
+    fn value_updated(&self, device_instance: u32, object_type: ObjectType, object_instance: u32, property_identifier: PropertyIdentifier);
+
+    // Callback Registration Functions
+    fn register_callback_receive_message(&self, callback: ReceiveMessageCallback);
+    fn register_callback_send_message(&self, callback: SendMessageCallback);
+    fn register_callback_get_system_time(&self, callback: GetSystemTimeCallback);
+    fn register_callback_set_system_time(&self, callback: SetSystemTimeCallback);
+    fn register_callback_get_property_bitstring(&self, callback: GetPropertyBitStringCallback);
+    fn register_callback_get_property_bool(&self, callback: GetPropertyBoolCallback);
+    fn register_callback_get_property_character_string(&self, callback: GetPropertyCharacterStringCallback);
+    fn register_callback_get_property_date(&self, callback: GetPropertyDateCallback);
+    fn register_callback_get_property_double(&self, callback: GetPropertyDoubleCallback);
+    fn register_callback_get_property_enumerated(&self, callback: GetPropertyEnumeratedCallback);
+    fn register_callback_get_property_octet_string(&self, callback: GetPropertyOctetStringCallback);
+    fn register_callback_get_property_real(&self, callback: GetPropertyRealCallback);
+    fn register_callback_get_property_signed_integer(&self, callback: GetPropertySignedIntegerCallback);
+    fn register_callback_get_property_time(&self, callback: GetPropertyTimeCallback);
+    fn register_callback_get_property_unsigned_integer(&self, callback: GetPropertyUnsignedIntegerCallback);
+    fn register_callback_set_property_bitstring(&self, callback: SetPropertyBitStringCallback);
+    fn register_callback_set_property_bool(&self, callback: SetPropertyBoolCallback);
+    fn register_callback_set_property_character_string(&self, callback: SetPropertyCharacterStringCallback);
+    fn register_callback_set_property_date(&self, callback: SetPropertyDateCallback);
+    fn register_callback_set_property_double(&self, callback: SetPropertyDoubleCallback);
+    fn register_callback_set_property_enumerated(&self, callback: SetPropertyEnumeratedCallback);
+    fn register_callback_set_property_null(&self, callback: SetPropertyNullCallback);
+    fn register_callback_set_property_octet_string(&self, callback: SetPropertyOctetStringCallback);
+    fn register_callback_set_property_real(&self, callback: SetPropertyRealCallback);
+    fn register_callback_set_property_signed_integer(&self, callback: SetPropertySignedIntegerCallback);
+    fn register_callback_set_property_time(&self, callback: SetPropertyTimeCallback);
+    fn register_callback_set_property_unsigned_integer(&self, callback: SetPropertyUnsignedIntegerCallback);
 }
-*/
 
-// Callback Registration Functions
-// Send and Receive Message Functions
-pub fn register_callback_receive_message(callback: fn(*mut u8, u16, *mut u8, u8, *mut u8, *mut u8) -> u16) -> Result<(), Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn(fn(*mut u8, u16, *mut u8, u8, *mut u8, *mut u8) -> u16)> = lib.get(b"BACnetStack_RegisterCallbackReceiveMessage")?;
-        Ok(func(callback))
+impl BacnetStack for BacnetStackApi {
+    // Versioning
+    fn get_api_major_version(&self) -> u32 {
+        unsafe { (self.get_api_major_version)() }
     }
-}
-pub fn register_callback_send_message(callback: fn(*const u8, u16, *const u8, u8, u8, bool) -> u16) -> Result<(), Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn(fn(*const u8, u16, *const u8, u8, u8, bool) -> u16)> = lib.get(b"BACnetStack_RegisterCallbackSendMessage")?;
-        Ok(func(callback))
+    fn get_api_minor_version(&self) -> u32 {
+        unsafe { (self.get_api_minor_version)() }
     }
-}
-
-// System Functions
-pub fn register_callback_get_system_time(callback: fn() -> u64) -> Result<(), Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn(fn() -> u64)> = lib.get(b"BACnetStack_RegisterCallbackGetSystemTime")?;
-        Ok(func(callback))
+    fn get_api_patch_version(&self) -> u32 {
+        unsafe { (self.get_api_patch_version)() }
     }
-}
-/*
-pub fn register_callback_set_system_time(callback: fn(u32, u8, u8, u8, u8, u8, u8, u8, u8) -> bool) -> Result<(), Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn(fn(u32, u8, u8, u8, u8, u8, u8, u8, u8) -> bool)> = lib.get(b"BACnetStack_RegisterCallbackSetSystemTime")?;
-        Ok(func(callback))
+    fn get_api_build_version(&self) -> u32 {
+        unsafe { (self.get_api_build_version)() }
     }
-}
-*/
 
-// Get Data Functions
-/*
-pub fn register_callback_get_property_bitstring(callback: fn(u32, u16, u32, u32, *mut bool, *mut u32, u32, bool, u32) -> bool) -> Result<(), Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn(fn(u32, u16, u32, u32, *mut bool, *mut u32, u32, bool, u32) -> bool)> = lib.get(b"BACnetStack_RegisterCallbackGetPropertyBitString")?;
-        Ok(func(callback))
+    // Main Loop
+    fn bacnet_loop(&self) {
+        unsafe { (self.bacnet_loop)() }
     }
-}
-pub fn register_callback_get_property_bool(callback: fn(u32, u16, u32, u32, *mut bool, bool, u32) -> bool) -> Result<(), Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn(fn(u32, u16, u32, u32, *mut bool, bool, u32) -> bool)> = lib.get(b"BACnetStack_RegisterCallbackGetPropertyBool")?;
-        Ok(func(callback))
+
+    // Device Setup Functions
+    fn add_device(&self, device_instance: u32) -> Result<(), AdapterError> {
+        let added = unsafe { (self.add_device)(device_instance) };
+        check_stack_call(added, "BACnetStack_AddDevice", StackCallContext { device_instance, ..Default::default() })
     }
-}
-*/
-pub fn register_callback_get_property_character_string(callback: fn(u32, u16, u32, u32, *mut c_char, *mut u32, u32, *mut u8, bool, u32) -> bool) -> Result<(), Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn(fn(u32, u16, u32, u32, *mut c_char, *mut u32, u32, *mut u8, bool, u32) -> bool)> = lib.get(b"BACnetStack_RegisterCallbackGetPropertyCharacterString")?;
-        Ok(func(callback))
+    fn add_object(&self, device_instance: u32, object_type: ObjectType, object_instance: u32) -> Result<(), AdapterError> {
+        let added = unsafe { (self.add_object)(device_instance, object_type.into(), object_instance) };
+        check_stack_call(added, "BACnetStack_AddObject", StackCallContext { device_instance, object_type: Some(object_type), object_instance: Some(object_instance), ..Default::default() })
     }
-}
-/*
-pub fn register_callback_get_property_date(callback: fn(u32, u16, u32, u32, *mut u8, *mut u8, *mut u8, *mut u8, bool, u32) -> bool) -> Result<(), Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn(fn(u32, u16, u32, u32, *mut u8, *mut u8, *mut u8, *mut u8, bool, u32) -> bool)> = lib.get(b"BACnetStack_RegisterCallbackGetPropertyDate")?;
-        Ok(func(callback))
+    fn add_notification_class_object(&self, device_instance: u32, object_instance: u32, priority: [u8; 3], ack_required: EventTransitions) -> Result<(), AdapterError> {
+        let added = unsafe { (self.add_notification_class_object)(device_instance, object_instance, priority[0], priority[1], priority[2], ack_required.to_offnormal, ack_required.to_fault, ack_required.to_normal) };
+        check_stack_call(added, "BACnetStack_AddNotificationClassObject", StackCallContext { device_instance, object_type: Some(ObjectType::NotificationClass), object_instance: Some(object_instance), ..Default::default() })
     }
-}
-pub fn register_callback_get_property_double(callback: fn(u32, u16, u32, u32, *mut f64, bool, u32) -> bool) -> Result<(), Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn(fn(u32, u16, u32, u32, *mut f64, bool, u32) -> bool)> = lib.get(b"BACnetStack_RegisterCallbackGetPropertyDouble")?;
-        Ok(func(callback))
+
+    // Property Setup Functions
+    fn set_property_enabled(&self, device_instance: u32, object_type: ObjectType, object_instance: u32, property_identifier: PropertyIdentifier, enabled: bool) -> Result<(), AdapterError> {
+        let set = unsafe { (self.set_property_enabled)(device_instance, object_type.into(), object_instance, property_identifier.into(), enabled) };
+        check_stack_call(set, "BACnetStack_SetPropertyEnabled", StackCallContext { device_instance, object_type: Some(object_type), object_instance: Some(object_instance), property_identifier: Some(property_identifier), ..Default::default() })
     }
-}
-*/
-pub fn register_callback_get_property_enumerated(callback: fn(u32, u16, u32, u32, *mut u32, bool, u32) -> bool) -> Result<(), Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn(fn(u32, u16, u32, u32, *mut u32, bool, u32) -> bool)> = lib.get(b"BACnetStack_RegisterCallbackGetPropertyEnumerated")?;
-        Ok(func(callback))
+    fn set_property_by_object_type_enabled(&self, device_instance: u32, object_type: ObjectType, property_identifier: PropertyIdentifier, enabled: bool) -> Result<(), AdapterError> {
+        let set = unsafe { (self.set_property_by_object_type_enabled)(device_instance, object_type.into(), property_identifier.into(), enabled) };
+        check_stack_call(set, "BACnetStack_SetPropertyByObjectTypeEnabled", StackCallContext { device_instance, object_type: Some(object_type), property_identifier: Some(property_identifier), ..Default::default() })
     }
-}
-/*
-pub fn register_callback_get_property_octet_string(callback: fn(u32, u16, u32, u32, *mut u32, bool, u32) -> bool) -> Result<(), Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn(fn(u32, u16, u32, u32, *mut u32, bool, u32) -> bool)> = lib.get(b"BACnetStack_RegisterCallbackGetPropertyOctetString")?;
-        Ok(func(callback))
+    fn set_property_writable(&self, device_instance: u32, object_type: ObjectType, object_instance: u32, property_identifier: PropertyIdentifier, writable: bool) -> Result<(), AdapterError> {
+        let set = unsafe { (self.set_property_writable)(device_instance, object_type.into(), object_instance, property_identifier.into(), writable) };
+        check_stack_call(set, "BACnetStack_SetPropertyWritable", StackCallContext { device_instance, object_type: Some(object_type), object_instance: Some(object_instance), property_identifier: Some(property_identifier), ..Default::default() })
     }
-}
-*/
-pub fn register_callback_get_property_real(callback: fn(u32, u16, u32, u32, *mut f32, bool, u32) -> bool) -> Result<(), Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn(fn(u32, u16, u32, u32, *mut f32, bool, u32) -> bool)> = lib.get(b"BACnetStack_RegisterCallbackGetPropertyReal")?;
-        Ok(func(callback))
+    // Never a list, and neither subscribable for COV nor required
+    fn set_proprietary_property(&self, device_instance: u32, object_type: ObjectType, object_instance: u32, property_identifier: PropertyIdentifier, is_array: bool, data_type: DataType, writable: bool) -> Result<(), AdapterError> {
+        let set = unsafe { (self.set_proprietary_property)(device_instance, object_type.into(), object_instance, property_identifier.into(), is_array, false, data_type.into(), writable, false, false) };
+        check_stack_call(set, "BACnetStack_SetProprietaryProperty", StackCallContext { device_instance, object_type: Some(object_type), object_instance: Some(object_instance), property_identifier: Some(property_identifier), ..Default::default() })
     }
-}
-pub fn register_callback_get_property_signed_integer(callback: fn(u32, u16, u32, u32, *mut i32, bool, u32) -> bool) -> Result<(), Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn(fn(u32, u16, u32, u32, *mut i32, bool, u32) -> bool)> = lib.get(b"BACnetStack_RegisterCallbackGetPropertySignedInteger")?;
-        Ok(func(callback))
+    fn set_service_enabled(&self, device_instance: u32, service: ServiceSupported, enabled: bool) -> Result<(), AdapterError> {
+        let set = unsafe { (self.set_service_enabled)(device_instance, service.value().into(), enabled) };
+        check_stack_call(set, "BACnetStack_SetServiceEnabled", StackCallContext { device_instance, service: Some(service), ..Default::default() })
     }
-}
-/*
-pub fn register_callback_get_property_time(callback: fn(u32, u16, u32, u32, *mut u8, *mut u8, *mut u8, *mut u8, bool, u32) -> bool) -> Result<(), Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn(fn(u32, u16, u32, u32, *mut u8, *mut u8, *mut u8, *mut u8, bool, u32) -> bool)> = lib.get(b"BACnetStack_RegisterCallbackGetPropertyTime")?;
-        Ok(func(callback))
+
+    // Alarm and Event Setup Functions
+    fn enable_alarms_and_events_for_object(&self, device_instance: u32, object_type: ObjectType, object_instance: u32, notification_class: u32, notify_type: NotifyType, event_enable: EventTransitions, event_detection_enable: bool) -> Result<(), AdapterError> {
+        let enabled = unsafe { (self.enable_alarms_and_events_for_object)(device_instance, object_type.into(), object_instance, notification_class, notify_type.value() as u8, event_enable.to_offnormal, event_enable.to_fault, event_enable.to_normal, event_detection_enable) };
+        check_stack_call(enabled, "BACnetStack_EnableAlarmsAndEventsForObject", StackCallContext { device_instance, object_type: Some(object_type), object_instance: Some(object_instance), ..Default::default() })
     }
-}
-pub fn register_callback_get_property_unsigned_integer(callback: fn(u32, u16, u32, u32, *mut u32, bool, u32) -> bool) -> Result<(), Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn(fn(u32, u16, u32, u32, *mut u32, bool, u32) -> bool)> = lib.get(b"BACnetStack_RegisterCallbackGetPropertyUnsignedInteger")?;
-        Ok(func(callback))
+
+    // Data Notification Functions
+    fn value_updated(&self, device_instance: u32, object_type: ObjectType, object_instance: u32, property_identifier: PropertyIdentifier) {
+        unsafe { (self.value_updated)(device_instance, object_type.into(), object_instance, property_identifier.into()) }
     }
-}
-*/
 
-// Set Data Functions
-/*
-pub fn register_callback_set_property_bitstring(callback: fn(u32, u16, u32, u32, *const bool, u32, bool, u32, u8, *mut u32) -> bool) -> Result<(), Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn(fn(u32, u16, u32, u32, *const bool, u32, bool, u32, u8, *mut u32) -> bool)> = lib.get(b"BACnetStack_RegisterCallbackSetPropertyBitString")?;
-        Ok(func(callback))
+    // Callback Registration Functions
+    // Send and Receive Message Functions
+    fn register_callback_receive_message(&self, callback: ReceiveMessageCallback) {
+        unsafe { (self.register_callback_receive_message)(callback) }
     }
-}
-pub fn register_callback_set_property_bool(callback: fn(u32, u16, u32, u32, bool, bool, u32, u8, *mut u32) -> bool) -> Result<(), Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn(fn(u32, u16, u32, u32, bool, bool, u32, u8, *mut u32) -> bool)> = lib.get(b"BACnetStack_RegisterCallbackSetPropertyBool")?;
-        Ok(func(callback))
+    fn register_callback_send_message(&self, callback: SendMessageCallback) {
+        unsafe { (self.register_callback_send_message)(callback) }
     }
-}
-pub fn register_callback_set_property_character_string(callback: fn(u32, u16, u32, u32, *const c_char, u32, u8, bool, u32, u8, *mut u32) -> bool) -> Result<(), Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn(fn(u32, u16, u32, u32, *const c_char, u32, u8, bool, u32, u8, *mut u32) -> bool)> = lib.get(b"BACnetStack_RegisterCallbackSetPropertyCharacterString")?;
-        Ok(func(callback))
+
+    // System Functions
+    fn register_callback_get_system_time(&self, callback: GetSystemTimeCallback) {
+        unsafe { (self.register_callback_get_system_time)(callback) }
     }
-}
-pub fn register_callback_set_property_date(callback: fn(u32, u16, u32, u32, u8, u8, u8, u8, bool, u32, u8, *mut u32) -> bool) -> Result<(), Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn(fn(u32, u16, u32, u32, u8, u8, u8, u8, bool, u32, u8, *mut u32) -> bool)> = lib.get(b"BACnetStack_RegisterCallbackSetPropertyDate")?;
-        Ok(func(callback))
+    fn register_callback_set_system_time(&self, callback: SetSystemTimeCallback) {
+        unsafe { (self.register_callback_set_system_time)(callback) }
     }
-}
-pub fn register_callback_set_property_double(callback: fn(u32, u16, u32, u32, f64, bool, u32, u8, *mut u32) -> bool) -> Result<(), Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn(fn(u32, u16, u32, u32, f64, bool, u32, u8, *mut u32) -> bool)> = lib.get(b"BACnetStack_RegisterCallbackSetPropertyDouble")?;
-        Ok(func(callback))
+
+    // Get Data Functions
+    fn register_callback_get_property_bitstring(&self, callback: GetPropertyBitStringCallback) {
+        unsafe { (self.register_callback_get_property_bitstring)(callback) }
     }
-}
-pub fn register_callback_set_property_enumerated(callback: fn(u32, u16, u32, u32, u32, bool, u32, u8, *mut u32) -> bool) -> Result<(), Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn(fn(u32, u16, u32, u32, u32, bool, u32, u8, *mut u32) -> bool)> = lib.get(b"BACnetStack_RegisterCallbackSetPropertyEnumerated")?;
-        Ok(func(callback))
+    fn register_callback_get_property_bool(&self, callback: GetPropertyBoolCallback) {
+        unsafe { (self.register_callback_get_property_bool)(callback) }
     }
-}
-pub fn register_callback_set_property_null(callback: fn(u32, u16, u32, u32, bool, u32, u8, *mut u32) -> bool) -> Result<(), Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn(fn(u32, u16, u32, u32, bool, u32, u8, *mut u32) -> bool)> = lib.get(b"BACnetStack_RegisterCallbackSetPropertyNull")?;
-        Ok(func(callback))
+    fn register_callback_get_property_character_string(&self, callback: GetPropertyCharacterStringCallback) {
+        unsafe { (self.register_callback_get_property_character_string)(callback) }
     }
-}
-pub fn register_callback_set_property_octet_string(callback: fn(u32, u16, u32, u32, *const u8, u32, bool, u32, u8, *mut u32) -> bool) -> Result<(), Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn(fn(u32, u16, u32, u32, *const u8, u32, bool, u32, u8, *mut u32) -> bool)> = lib.get(b"BACnetStack_RegisterCallbackSetPropertyOctetString")?;
-        Ok(func(callback))
+    fn register_callback_get_property_date(&self, callback: GetPropertyDateCallback) {
+        unsafe { (self.register_callback_get_property_date)(callback) }
     }
-}
-pub fn register_callback_set_property_real(callback: fn(u32, u16, u32, u32, f32, bool, u32, u8, *mut u32) -> bool) -> Result<(), Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn(fn(u32, u16, u32, u32, f32, bool, u32, u8, *mut u32) -> bool)> = lib.get(b"BACnetStack_RegisterCallbackSetPropertyReal")?;
-        Ok(func(callback))
+    fn register_callback_get_property_double(&self, callback: GetPropertyDoubleCallback) {
+        unsafe { (self.register_callback_get_property_double)(callback) }
     }
-}
-pub fn register_callback_set_property_signed_integer(callback: fn(u32, u16, u32, u32, i32, bool, u32, u8, *mut u32) -> bool) -> Result<(), Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn(fn(u32, u16, u32, u32, i32, bool, u32, u8, *mut u32) -> bool)> = lib.get(b"BACnetStack_RegisterCallbackSetPropertySignedInteger")?;
-        Ok(func(callback))
+    fn register_callback_get_property_enumerated(&self, callback: GetPropertyEnumeratedCallback) {
+        unsafe { (self.register_callback_get_property_enumerated)(callback) }
     }
-}
-pub fn register_callback_set_property_time(callback: fn(u32, u16, u32, u32, u8, u8, u8, u8, bool, u32, u8, *mut u32) -> bool) -> Result<(), Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn(fn(u32, u16, u32, u32, u8, u8, u8, u8, bool, u32, u8, *mut u32) -> bool)> = lib.get(b"BACnetStack_RegisterCallbackSetPropertyTime")?;
-        Ok(func(callback))
+    fn register_callback_get_property_octet_string(&self, callback: GetPropertyOctetStringCallback) {
+        unsafe { (self.register_callback_get_property_octet_string)(callback) }
     }
-}
-pub fn register_callback_set_property_unsigned_integer(callback: fn(u32, u16, u32, u32, u32, bool, u32, u8, *mut u32) -> bool) -> Result<(), Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn(fn(u32, u16, u32, u32, u32, bool, u32, u8, *mut u32) -> bool)> = lib.get(b"BACnetStack_RegisterCallbackSetPropertyUnsignedInteger")?;
-        Ok(func(callback))
+    fn register_callback_get_property_real(&self, callback: GetPropertyRealCallback) {
+        unsafe { (self.register_callback_get_property_real)(callback) }
     }
-}
-*/
-
-// Object Creation Functions
-/*
-pub fn register_callback_create_object(callback: fn(u32, u16, u32) -> bool) -> Result<(), Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn(fn(u32, u16, u32) -> bool)> = lib.get(b"BACnetStack_RegisterCallbackCreateObject")?;
-        Ok(func(callback))
+    fn register_callback_get_property_signed_integer(&self, callback: GetPropertySignedIntegerCallback) {
+        unsafe { (self.register_callback_get_property_signed_integer)(callback) }
     }
-}
-pub fn register_callback_delete_object(callback: fn(u32, u16, u32) -> bool) -> Result<(), Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn(fn(u32, u16, u32) -> bool)> = lib.get(b"BACnetStack_RegisterCallbackDeleteObject")?;
-        Ok(func(callback))
+    fn register_callback_get_property_time(&self, callback: GetPropertyTimeCallback) {
+        unsafe { (self.register_callback_get_property_time)(callback) }
+    }
+    fn register_callback_get_property_unsigned_integer(&self, callback: GetPropertyUnsignedIntegerCallback) {
+        unsafe { (self.register_callback_get_property_unsigned_integer)(callback) }
     }
-}
-*/
 
-// Remote Device Management Functions
-/*
-pub fn register_callback_reinitialize_device(callback: fn(u32, u32, *const c_char, u32, *mut u32) -> bool) -> Result<(), Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn(fn(u32, u32, *const c_char, u32, *mut u32) -> bool)> = lib.get(b"BACnetStack_RegisterCallbackCreateObject")?;
-        Ok(func(callback))
+    // Set Data Functions
+    fn register_callback_set_property_bitstring(&self, callback: SetPropertyBitStringCallback) {
+        unsafe { (self.register_callback_set_property_bitstring)(callback) }
     }
-}
-pub fn register_callback_device_communication_control(callback: fn(u32, u8, *const c_char, u8, bool, u16, *mut u32) -> bool) -> Result<(), Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn(fn(u32, u8, *const c_char, u8, bool, u16, *mut u32) -> bool)> = lib.get(b"BACnetStack_RegisterCallbackDeleteObject")?;
-        Ok(func(callback))
+    fn register_callback_set_property_bool(&self, callback: SetPropertyBoolCallback) {
+        unsafe { (self.register_callback_set_property_bool)(callback) }
     }
-}
-*/
-
-// Debug Message Functions
-/*
-pub fn register_callback_log_debug_message(callback: fn(*const c_char, u16, u8)) -> Result<(), Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn(fn(*const c_char, u16, u8))> = lib.get(b"BACnetStack_RegisterCallbackLogDebugMessage")?;
-        Ok(func(callback))
+    fn register_callback_set_property_character_string(&self, callback: SetPropertyCharacterStringCallback) {
+        unsafe { (self.register_callback_set_property_character_string)(callback) }
     }
-}
-*/
-
-// Client Functions
-// Client Hooks
-/*
-pub fn register_hook_text_message(callback: fn(u32, bool, u32, *const c_char, u32, u8, *const c_char, u32, *const u8, u8, u8, u16, *const u8, u8, *mut u16, *mut u16) -> bool) -> Result<(), Box<dyn std::error::Error>> {
-    unsafe {
-        let func: libloading::Symbol<unsafe extern fn(fn(u32, bool, u32, *const c_char, u32, u8, *const c_char, u32, *const u8, u8, u8, u16, *const u8, u8, *mut u16, *mut u16) -> bool)> = lib.get(b"BACnetStack_RegisterHookTextMessage")?;
-        Ok(func(callback))
+    fn register_callback_set_property_date(&self, callback: SetPropertyDateCallback) {
+        unsafe { (self.register_callback_set_property_date)(callback) }
+    }
+    fn register_callback_set_property_double(&self, callback: SetPropertyDoubleCallback) {
+        unsafe { (self.register_callback_set_property_double)(callback) }
+    }
+    fn register_callback_set_property_enumerated(&self, callback: SetPropertyEnumeratedCallback) {
+        unsafe { (self.register_callback_set_property_enumerated)(callback) }
+    }
+    fn register_callback_set_property_null(&self, callback: SetPropertyNullCallback) {
+        unsafe { (self.register_callback_set_property_null)(callback) }
+    }
+    fn register_callback_set_property_octet_string(&self, callback: SetPropertyOctetStringCallback) {
+        unsafe { (self.register_callback_set_property_octet_string)(callback) }
+    }
+    fn register_callback_set_property_real(&self, callback: SetPropertyRealCallback) {
+        unsafe { (self.register_callback_set_property_real)(callback) }
+    }
+    fn register_callback_set_property_signed_integer(&self, callback: SetPropertySignedIntegerCallback) {
+        unsafe { (self.register_callback_set_property_signed_integer)(callback) }
+    }
+    fn register_callback_set_property_time(&self, callback: SetPropertyTimeCallback) {
+        unsafe { (self.register_callback_set_property_time)(callback) }
+    }
+    fn register_callback_set_property_unsigned_integer(&self, callback: SetPropertyUnsignedIntegerCallback) {
+        unsafe { (self.register_callback_set_property_unsigned_integer)(callback) }
     }
 }
-*/
\ No newline at end of file
@@ -1,3 +1,6 @@
+// Raw values as they cross the C ABI. ObjectType, PropertyIdentifier, ServiceSupported, ErrorCode and
+// Reliability in bacnet_types cover the full ASHRAE 135 tables with names for logs and config.
+
 // CAS BACnet Stack network type
 pub const NETWORK_TYPE_IP: u8 = 0;
 pub const NETWORK_TYPE_MSTP: u8 = 1;
@@ -13,18 +16,18 @@ pub const OBJECT_TYPE_ANALOG_VALUE: u16 = 2;
 pub const OBJECT_TYPE_BINARY_INPUT: u16 = 3;
 pub const OBJECT_TYPE_BINARY_OUTPUT: u16 = 4;
 pub const OBJECT_TYPE_BINARY_VALUE: u16 = 5;
-//pub const OBJECT_TYPE_CALENDAR: u16 = 6;
+pub const OBJECT_TYPE_CALENDAR: u16 = 6;
 //pub const OBJECT_TYPE_COMMAND: u16 = 7;
 pub const OBJECT_TYPE_DEVICE: u16 = 8;
 //pub const OBJECT_TYPE_EVENT_ENROLLMENT: u16 = 9;
 //pub const OBJECT_TYPE_FILE: u16 = 10;
 //pub const OBJECT_TYPE_GROUP: u16 = 11;
-//pub const OBJECT_TYPE_LOOP: u16 = 12;
+pub const OBJECT_TYPE_LOOP: u16 = 12;
 pub const OBJECT_TYPE_MULTI_STATE_INPUT: u16 = 13;
 pub const OBJECT_TYPE_MULTI_STATE_OUTPUT: u16 = 14;
 //pub const OBJECT_TYPE_NOTIFICATION_CLASS: u16 = 15;
 //pub const OBJECT_TYPE_PROGRAM: u16 = 16;
-//pub const OBJECT_TYPE_SCHEDULE: u16 = 17;
+pub const OBJECT_TYPE_SCHEDULE: u16 = 17;
 //pub const OBJECT_TYPE_AVERAGING: u16 = 18;
 pub const OBJECT_TYPE_MULTI_STATE_VALUE: u16 = 19;
 pub const OBJECT_TYPE_TREND_LOG: u16 = 20;
@@ -149,6 +152,9 @@ pub const FD_BBMD_ADDRESS_PORT: u8 = 2;
 pub const NETWORK_TYPE_BACNET_IP: u8 = 0;
 pub const NETWORK_TYPE_IPV4: u8 = 5;
 
+// Character String Encoding
+pub const CHARACTER_UTF8: u8 = 0;
+
 // Protocol Level
 pub const PROTOCOL_LEVEL_BACNET_APPLICATION: u8 = 2;
 
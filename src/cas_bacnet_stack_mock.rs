@@ -0,0 +1,547 @@
+use std::os::raw::c_char;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use crate::cas_bacnet_stack_adapters::*;
+use crate::bacnet_types::{CharacterSet, DataType, ErrorCode, EventTransitions, NotifyType, ObjectType, PropertyIdentifier, ServiceSupported};
+
+// Maximum string length requested from the character string callback
+const MAX_CHARACTER_STRING_LENGTH: u32 = 1024;
+// Maximum number of bits requested from the bitstring callback
+const MAX_BITSTRING_LENGTH: u32 = 256;
+// Maximum number of octets requested from the octet string callback
+const MAX_OCTET_STRING_LENGTH: u32 = 1024;
+
+// A call made on the mock stack
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MockCall {
+    AddDevice { device_instance: u32 },
+    AddObject { device_instance: u32, object_type: ObjectType, object_instance: u32 },
+    AddNotificationClassObject { device_instance: u32, object_instance: u32, priority: [u8; 3], ack_required: EventTransitions },
+    SetPropertyEnabled { device_instance: u32, object_type: ObjectType, object_instance: u32, property_identifier: PropertyIdentifier, enabled: bool },
+    SetPropertyByObjectTypeEnabled { device_instance: u32, object_type: ObjectType, property_identifier: PropertyIdentifier, enabled: bool },
+    SetPropertyWritable { device_instance: u32, object_type: ObjectType, object_instance: u32, property_identifier: PropertyIdentifier, writable: bool },
+    SetProprietaryProperty { device_instance: u32, object_type: ObjectType, object_instance: u32, property_identifier: PropertyIdentifier, is_array: bool, data_type: DataType, writable: bool },
+    SetServiceEnabled { device_instance: u32, service: ServiceSupported, enabled: bool },
+    EnableAlarmsAndEventsForObject { device_instance: u32, object_type: ObjectType, object_instance: u32, notification_class: u32, notify_type: NotifyType, event_enable: EventTransitions, event_detection_enable: bool },
+    ValueUpdated { device_instance: u32, object_type: ObjectType, object_instance: u32, property_identifier: PropertyIdentifier }
+}
+
+#[derive(Default)]
+struct MockState {
+    calls: Vec<MockCall>,
+    devices: Vec<u32>,
+    objects: Vec<(u32, ObjectType, u32)>,
+    writable_properties: Vec<(u32, ObjectType, u32, PropertyIdentifier)>,
+    loop_count: u64,
+
+    receive_message: Option<ReceiveMessageCallback>,
+    send_message: Option<SendMessageCallback>,
+    get_system_time: Option<GetSystemTimeCallback>,
+    set_system_time: Option<SetSystemTimeCallback>,
+    get_property_bitstring: Option<GetPropertyBitStringCallback>,
+    get_property_bool: Option<GetPropertyBoolCallback>,
+    get_property_character_string: Option<GetPropertyCharacterStringCallback>,
+    get_property_date: Option<GetPropertyDateCallback>,
+    get_property_double: Option<GetPropertyDoubleCallback>,
+    get_property_enumerated: Option<GetPropertyEnumeratedCallback>,
+    get_property_octet_string: Option<GetPropertyOctetStringCallback>,
+    get_property_real: Option<GetPropertyRealCallback>,
+    get_property_signed_integer: Option<GetPropertySignedIntegerCallback>,
+    get_property_time: Option<GetPropertyTimeCallback>,
+    get_property_unsigned_integer: Option<GetPropertyUnsignedIntegerCallback>,
+
+    set_property_bitstring: Option<SetPropertyBitStringCallback>,
+    set_property_bool: Option<SetPropertyBoolCallback>,
+    set_property_character_string: Option<SetPropertyCharacterStringCallback>,
+    set_property_date: Option<SetPropertyDateCallback>,
+    set_property_double: Option<SetPropertyDoubleCallback>,
+    set_property_enumerated: Option<SetPropertyEnumeratedCallback>,
+    set_property_null: Option<SetPropertyNullCallback>,
+    set_property_octet_string: Option<SetPropertyOctetStringCallback>,
+    set_property_real: Option<SetPropertyRealCallback>,
+    set_property_signed_integer: Option<SetPropertySignedIntegerCallback>,
+    set_property_time: Option<SetPropertyTimeCallback>,
+    set_property_unsigned_integer: Option<SetPropertyUnsignedIntegerCallback>
+}
+
+impl MockState {
+    // The device object exists once the device is added
+    fn has_object(&self, device_instance: u32, object_type: ObjectType, object_instance: u32) -> bool {
+        self.objects.contains(&(device_instance, object_type, object_instance)) || (object_type == ObjectType::Device && object_instance == device_instance && self.devices.contains(&device_instance))
+    }
+}
+
+// In-process stand-in for the CAS BACnet Stack. Records every setup call and lets the registered
+// callbacks be invoked directly, so the database and callback layer can run without the library.
+#[derive(Default)]
+pub struct MockBacnetStack {
+    state: Mutex<MockState>
+}
+
+impl MockBacnetStack {
+    pub fn new() -> MockBacnetStack {
+        MockBacnetStack::default()
+    }
+
+    fn state(&self) -> MutexGuard<'_, MockState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    // Every call in the order it was made
+    pub fn calls(&self) -> Vec<MockCall> {
+        self.state().calls.clone()
+    }
+
+    // (device_instance, object_type, object_instance) of every object added
+    pub fn objects(&self) -> Vec<(u32, ObjectType, u32)> {
+        self.state().objects.clone()
+    }
+
+    pub fn loop_count(&self) -> u64 {
+        self.state().loop_count
+    }
+
+    fn array_index(property_array_index: Option<u32>) -> (bool, u32) {
+        (property_array_index.is_some(), property_array_index.unwrap_or(0))
+    }
+
+    // Get Data Functions. Return None when no callback is registered or the callback fails.
+    // The callback is copied out of the state first, so the lock is not held while it runs.
+    pub fn get_property_bitstring(&self, device_instance: u32, object_type: ObjectType, object_instance: u32, property_identifier: PropertyIdentifier, property_array_index: Option<u32>) -> Option<Vec<bool>> {
+        let callback = self.state().get_property_bitstring?;
+        let (use_array_index, property_array_index) = MockBacnetStack::array_index(property_array_index);
+        let mut value = vec![false; MAX_BITSTRING_LENGTH as usize];
+        let mut value_element_count = 0;
+        if !callback(device_instance, object_type.into(), object_instance, property_identifier.into(), value.as_mut_ptr(), &mut value_element_count, MAX_BITSTRING_LENGTH, use_array_index, property_array_index) {
+            return None;
+        }
+        value.truncate(value_element_count as usize);
+        Some(value)
+    }
+
+    pub fn get_property_bool(&self, device_instance: u32, object_type: ObjectType, object_instance: u32, property_identifier: PropertyIdentifier, property_array_index: Option<u32>) -> Option<bool> {
+        let callback = self.state().get_property_bool?;
+        let (use_array_index, property_array_index) = MockBacnetStack::array_index(property_array_index);
+        let mut value = false;
+        callback(device_instance, object_type.into(), object_instance, property_identifier.into(), &mut value, use_array_index, property_array_index).then_some(value)
+    }
+
+    pub fn get_property_character_string(&self, device_instance: u32, object_type: ObjectType, object_instance: u32, property_identifier: PropertyIdentifier, property_array_index: Option<u32>) -> Option<String> {
+        let callback = self.state().get_property_character_string?;
+        let (use_array_index, property_array_index) = MockBacnetStack::array_index(property_array_index);
+        let mut value = vec![0 as c_char; MAX_CHARACTER_STRING_LENGTH as usize];
+        let mut value_element_count = 0;
+        let mut encoding_type = 0;
+        if !callback(device_instance, object_type.into(), object_instance, property_identifier.into(), value.as_mut_ptr(), &mut value_element_count, MAX_CHARACTER_STRING_LENGTH, &mut encoding_type, use_array_index, property_array_index) {
+            return None;
+        }
+        let bytes: Vec<u8> = value.iter().take(value_element_count as usize).map(|&character| character as u8).collect();
+        CharacterSet::try_from(encoding_type).ok()?.decode(&bytes)
+    }
+
+    // (year, month, day, weekday), the year counted from 1900
+    pub fn get_property_date(&self, device_instance: u32, object_type: ObjectType, object_instance: u32, property_identifier: PropertyIdentifier, property_array_index: Option<u32>) -> Option<(u8, u8, u8, u8)> {
+        let callback = self.state().get_property_date?;
+        let (use_array_index, property_array_index) = MockBacnetStack::array_index(property_array_index);
+        let (mut year, mut month, mut day, mut weekday) = (0, 0, 0, 0);
+        callback(device_instance, object_type.into(), object_instance, property_identifier.into(), &mut year, &mut month, &mut day, &mut weekday, use_array_index, property_array_index).then_some((year, month, day, weekday))
+    }
+
+    pub fn get_property_double(&self, device_instance: u32, object_type: ObjectType, object_instance: u32, property_identifier: PropertyIdentifier, property_array_index: Option<u32>) -> Option<f64> {
+        let callback = self.state().get_property_double?;
+        let (use_array_index, property_array_index) = MockBacnetStack::array_index(property_array_index);
+        let mut value = 0.0;
+        callback(device_instance, object_type.into(), object_instance, property_identifier.into(), &mut value, use_array_index, property_array_index).then_some(value)
+    }
+
+    pub fn get_property_enumerated(&self, device_instance: u32, object_type: ObjectType, object_instance: u32, property_identifier: PropertyIdentifier, property_array_index: Option<u32>) -> Option<u32> {
+        let callback = self.state().get_property_enumerated?;
+        let (use_array_index, property_array_index) = MockBacnetStack::array_index(property_array_index);
+        let mut value = 0;
+        callback(device_instance, object_type.into(), object_instance, property_identifier.into(), &mut value, use_array_index, property_array_index).then_some(value)
+    }
+
+    pub fn get_property_octet_string(&self, device_instance: u32, object_type: ObjectType, object_instance: u32, property_identifier: PropertyIdentifier, property_array_index: Option<u32>) -> Option<Vec<u8>> {
+        let callback = self.state().get_property_octet_string?;
+        let (use_array_index, property_array_index) = MockBacnetStack::array_index(property_array_index);
+        let mut value = vec![0; MAX_OCTET_STRING_LENGTH as usize];
+        let mut value_element_count = 0;
+        if !callback(device_instance, object_type.into(), object_instance, property_identifier.into(), value.as_mut_ptr(), &mut value_element_count, MAX_OCTET_STRING_LENGTH, use_array_index, property_array_index) {
+            return None;
+        }
+        value.truncate(value_element_count as usize);
+        Some(value)
+    }
+
+    pub fn get_property_real(&self, device_instance: u32, object_type: ObjectType, object_instance: u32, property_identifier: PropertyIdentifier, property_array_index: Option<u32>) -> Option<f32> {
+        let callback = self.state().get_property_real?;
+        let (use_array_index, property_array_index) = MockBacnetStack::array_index(property_array_index);
+        let mut value = 0.0;
+        callback(device_instance, object_type.into(), object_instance, property_identifier.into(), &mut value, use_array_index, property_array_index).then_some(value)
+    }
+
+    pub fn get_property_signed_integer(&self, device_instance: u32, object_type: ObjectType, object_instance: u32, property_identifier: PropertyIdentifier, property_array_index: Option<u32>) -> Option<i32> {
+        let callback = self.state().get_property_signed_integer?;
+        let (use_array_index, property_array_index) = MockBacnetStack::array_index(property_array_index);
+        let mut value = 0;
+        callback(device_instance, object_type.into(), object_instance, property_identifier.into(), &mut value, use_array_index, property_array_index).then_some(value)
+    }
+
+    // (hour, minute, second, hundredth_seconds)
+    pub fn get_property_time(&self, device_instance: u32, object_type: ObjectType, object_instance: u32, property_identifier: PropertyIdentifier, property_array_index: Option<u32>) -> Option<(u8, u8, u8, u8)> {
+        let callback = self.state().get_property_time?;
+        let (use_array_index, property_array_index) = MockBacnetStack::array_index(property_array_index);
+        let (mut hour, mut minute, mut second, mut hundredth_seconds) = (0, 0, 0, 0);
+        callback(device_instance, object_type.into(), object_instance, property_identifier.into(), &mut hour, &mut minute, &mut second, &mut hundredth_seconds, use_array_index, property_array_index).then_some((hour, minute, second, hundredth_seconds))
+    }
+
+    pub fn get_property_unsigned_integer(&self, device_instance: u32, object_type: ObjectType, object_instance: u32, property_identifier: PropertyIdentifier, property_array_index: Option<u32>) -> Option<u32> {
+        let callback = self.state().get_property_unsigned_integer?;
+        let (use_array_index, property_array_index) = MockBacnetStack::array_index(property_array_index);
+        let mut value = 0;
+        callback(device_instance, object_type.into(), object_instance, property_identifier.into(), &mut value, use_array_index, property_array_index).then_some(value)
+    }
+
+    // Set Data Functions. Like the stack handling a WriteProperty request, the callback is only
+    // called for properties set writable. A rejected write returns the callback's error code.
+    fn writable_callback<C>(&self, device_instance: u32, object_type: ObjectType, object_instance: u32, property_identifier: PropertyIdentifier, callback: impl FnOnce(&MockState) -> Option<C>) -> Result<C, ErrorCode> {
+        let state = self.state();
+        if !state.writable_properties.contains(&(device_instance, object_type, object_instance, property_identifier)) {
+            return Err(ErrorCode::WriteAccessDenied);
+        }
+        callback(&state).ok_or(ErrorCode::WriteAccessDenied)
+    }
+
+    fn write_result(written: bool, error_code: u32) -> Result<(), ErrorCode> {
+        if written {
+            Ok(())
+        } else {
+            Err(ErrorCode::try_from(error_code).unwrap_or(ErrorCode::Other))
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn set_property_bitstring(&self, device_instance: u32, object_type: ObjectType, object_instance: u32, property_identifier: PropertyIdentifier, value: &[bool], property_array_index: Option<u32>, priority: u8) -> Result<(), ErrorCode> {
+        let callback = self.writable_callback(device_instance, object_type, object_instance, property_identifier, |state| state.set_property_bitstring)?;
+        let (use_array_index, property_array_index) = MockBacnetStack::array_index(property_array_index);
+        let mut error_code = 0;
+        let written = callback(device_instance, object_type.into(), object_instance, property_identifier.into(), value.as_ptr(), value.len() as u32, use_array_index, property_array_index, priority, &mut error_code);
+        MockBacnetStack::write_result(written, error_code)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn set_property_bool(&self, device_instance: u32, object_type: ObjectType, object_instance: u32, property_identifier: PropertyIdentifier, value: bool, property_array_index: Option<u32>, priority: u8) -> Result<(), ErrorCode> {
+        let callback = self.writable_callback(device_instance, object_type, object_instance, property_identifier, |state| state.set_property_bool)?;
+        let (use_array_index, property_array_index) = MockBacnetStack::array_index(property_array_index);
+        let mut error_code = 0;
+        let written = callback(device_instance, object_type.into(), object_instance, property_identifier.into(), value, use_array_index, property_array_index, priority, &mut error_code);
+        MockBacnetStack::write_result(written, error_code)
+    }
+
+    // The value is encoded in the character set, as a client would send it
+    #[allow(clippy::too_many_arguments)]
+    pub fn set_property_character_string(&self, device_instance: u32, object_type: ObjectType, object_instance: u32, property_identifier: PropertyIdentifier, value: &[u8], character_set: CharacterSet, property_array_index: Option<u32>, priority: u8) -> Result<(), ErrorCode> {
+        let callback = self.writable_callback(device_instance, object_type, object_instance, property_identifier, |state| state.set_property_character_string)?;
+        let (use_array_index, property_array_index) = MockBacnetStack::array_index(property_array_index);
+        let mut error_code = 0;
+        let written = callback(device_instance, object_type.into(), object_instance, property_identifier.into(), value.as_ptr() as *const c_char, value.len() as u32, character_set.into(), use_array_index, property_array_index, priority, &mut error_code);
+        MockBacnetStack::write_result(written, error_code)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn set_property_date(&self, device_instance: u32, object_type: ObjectType, object_instance: u32, property_identifier: PropertyIdentifier, year: u8, month: u8, day: u8, weekday: u8, property_array_index: Option<u32>, priority: u8) -> Result<(), ErrorCode> {
+        let callback = self.writable_callback(device_instance, object_type, object_instance, property_identifier, |state| state.set_property_date)?;
+        let (use_array_index, property_array_index) = MockBacnetStack::array_index(property_array_index);
+        let mut error_code = 0;
+        let written = callback(device_instance, object_type.into(), object_instance, property_identifier.into(), year, month, day, weekday, use_array_index, property_array_index, priority, &mut error_code);
+        MockBacnetStack::write_result(written, error_code)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn set_property_double(&self, device_instance: u32, object_type: ObjectType, object_instance: u32, property_identifier: PropertyIdentifier, value: f64, property_array_index: Option<u32>, priority: u8) -> Result<(), ErrorCode> {
+        let callback = self.writable_callback(device_instance, object_type, object_instance, property_identifier, |state| state.set_property_double)?;
+        let (use_array_index, property_array_index) = MockBacnetStack::array_index(property_array_index);
+        let mut error_code = 0;
+        let written = callback(device_instance, object_type.into(), object_instance, property_identifier.into(), value, use_array_index, property_array_index, priority, &mut error_code);
+        MockBacnetStack::write_result(written, error_code)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn set_property_enumerated(&self, device_instance: u32, object_type: ObjectType, object_instance: u32, property_identifier: PropertyIdentifier, value: u32, property_array_index: Option<u32>, priority: u8) -> Result<(), ErrorCode> {
+        let callback = self.writable_callback(device_instance, object_type, object_instance, property_identifier, |state| state.set_property_enumerated)?;
+        let (use_array_index, property_array_index) = MockBacnetStack::array_index(property_array_index);
+        let mut error_code = 0;
+        let written = callback(device_instance, object_type.into(), object_instance, property_identifier.into(), value, use_array_index, property_array_index, priority, &mut error_code);
+        MockBacnetStack::write_result(written, error_code)
+    }
+
+    pub fn set_property_null(&self, device_instance: u32, object_type: ObjectType, object_instance: u32, property_identifier: PropertyIdentifier, property_array_index: Option<u32>, priority: u8) -> Result<(), ErrorCode> {
+        let callback = self.writable_callback(device_instance, object_type, object_instance, property_identifier, |state| state.set_property_null)?;
+        let (use_array_index, property_array_index) = MockBacnetStack::array_index(property_array_index);
+        let mut error_code = 0;
+        let written = callback(device_instance, object_type.into(), object_instance, property_identifier.into(), use_array_index, property_array_index, priority, &mut error_code);
+        MockBacnetStack::write_result(written, error_code)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn set_property_octet_string(&self, device_instance: u32, object_type: ObjectType, object_instance: u32, property_identifier: PropertyIdentifier, value: &[u8], property_array_index: Option<u32>, priority: u8) -> Result<(), ErrorCode> {
+        let callback = self.writable_callback(device_instance, object_type, object_instance, property_identifier, |state| state.set_property_octet_string)?;
+        let (use_array_index, property_array_index) = MockBacnetStack::array_index(property_array_index);
+        let mut error_code = 0;
+        let written = callback(device_instance, object_type.into(), object_instance, property_identifier.into(), value.as_ptr(), value.len() as u32, use_array_index, property_array_index, priority, &mut error_code);
+        MockBacnetStack::write_result(written, error_code)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn set_property_real(&self, device_instance: u32, object_type: ObjectType, object_instance: u32, property_identifier: PropertyIdentifier, value: f32, property_array_index: Option<u32>, priority: u8) -> Result<(), ErrorCode> {
+        let callback = self.writable_callback(device_instance, object_type, object_instance, property_identifier, |state| state.set_property_real)?;
+        let (use_array_index, property_array_index) = MockBacnetStack::array_index(property_array_index);
+        let mut error_code = 0;
+        let written = callback(device_instance, object_type.into(), object_instance, property_identifier.into(), value, use_array_index, property_array_index, priority, &mut error_code);
+        MockBacnetStack::write_result(written, error_code)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn set_property_signed_integer(&self, device_instance: u32, object_type: ObjectType, object_instance: u32, property_identifier: PropertyIdentifier, value: i32, property_array_index: Option<u32>, priority: u8) -> Result<(), ErrorCode> {
+        let callback = self.writable_callback(device_instance, object_type, object_instance, property_identifier, |state| state.set_property_signed_integer)?;
+        let (use_array_index, property_array_index) = MockBacnetStack::array_index(property_array_index);
+        let mut error_code = 0;
+        let written = callback(device_instance, object_type.into(), object_instance, property_identifier.into(), value, use_array_index, property_array_index, priority, &mut error_code);
+        MockBacnetStack::write_result(written, error_code)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn set_property_time(&self, device_instance: u32, object_type: ObjectType, object_instance: u32, property_identifier: PropertyIdentifier, hour: u8, minute: u8, second: u8, hundredth_seconds: u8, property_array_index: Option<u32>, priority: u8) -> Result<(), ErrorCode> {
+        let callback = self.writable_callback(device_instance, object_type, object_instance, property_identifier, |state| state.set_property_time)?;
+        let (use_array_index, property_array_index) = MockBacnetStack::array_index(property_array_index);
+        let mut error_code = 0;
+        let written = callback(device_instance, object_type.into(), object_instance, property_identifier.into(), hour, minute, second, hundredth_seconds, use_array_index, property_array_index, priority, &mut error_code);
+        MockBacnetStack::write_result(written, error_code)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn set_property_unsigned_integer(&self, device_instance: u32, object_type: ObjectType, object_instance: u32, property_identifier: PropertyIdentifier, value: u32, property_array_index: Option<u32>, priority: u8) -> Result<(), ErrorCode> {
+        let callback = self.writable_callback(device_instance, object_type, object_instance, property_identifier, |state| state.set_property_unsigned_integer)?;
+        let (use_array_index, property_array_index) = MockBacnetStack::array_index(property_array_index);
+        let mut error_code = 0;
+        let written = callback(device_instance, object_type.into(), object_instance, property_identifier.into(), value, use_array_index, property_array_index, priority, &mut error_code);
+        MockBacnetStack::write_result(written, error_code)
+    }
+
+    // Send and Receive Message Functions
+    pub fn receive_message(&self, max_message_length: u16) -> Option<(Vec<u8>, Vec<u8>, u8)> {
+        let callback = self.state().receive_message?;
+        let mut message = vec![0; max_message_length as usize];
+        let mut connection_string = [0; 6];
+        let mut connection_string_length = 0;
+        let mut network_type = 0;
+        let length = callback(message.as_mut_ptr(), max_message_length, connection_string.as_mut_ptr(), connection_string.len() as u8, &mut connection_string_length, &mut network_type);
+        message.truncate(length as usize);
+        Some((message, connection_string[..connection_string_length as usize].to_vec(), network_type))
+    }
+
+    pub fn send_message(&self, message: &[u8], connection_string: &[u8], network_type: u8, broadcast: bool) -> Option<u16> {
+        let callback = self.state().send_message?;
+        Some(callback(message.as_ptr(), message.len() as u16, connection_string.as_ptr(), connection_string.len() as u8, network_type, broadcast))
+    }
+
+    // System Functions
+    pub fn get_system_time(&self) -> Option<u64> {
+        let callback = self.state().get_system_time?;
+        Some(callback())
+    }
+
+    // A TimeSynchronization request, with the device's local date and time
+    pub fn set_system_time(&self, device_instance: u32, (year, month, day, weekday): (u8, u8, u8, u8), (hour, minute, second, hundredth_seconds): (u8, u8, u8, u8)) -> Option<bool> {
+        let callback = self.state().set_system_time?;
+        Some(callback(device_instance, year, month, day, weekday, hour, minute, second, hundredth_seconds))
+    }
+}
+
+impl BacnetStack for MockBacnetStack {
+    // Versioning
+    fn get_api_major_version(&self) -> u32 {
+        0
+    }
+    fn get_api_minor_version(&self) -> u32 {
+        0
+    }
+    fn get_api_patch_version(&self) -> u32 {
+        0
+    }
+    fn get_api_build_version(&self) -> u32 {
+        0
+    }
+
+    // Main Loop
+    fn bacnet_loop(&self) {
+        self.state().loop_count += 1;
+    }
+
+    // Device Setup Functions
+    fn add_device(&self, device_instance: u32) -> Result<(), AdapterError> {
+        let mut state = self.state();
+        state.calls.push(MockCall::AddDevice { device_instance });
+        let added = !state.devices.contains(&device_instance);
+        if added {
+            state.devices.push(device_instance);
+        }
+        check_stack_call(added, "BACnetStack_AddDevice", StackCallContext { device_instance, ..Default::default() })
+    }
+    fn add_object(&self, device_instance: u32, object_type: ObjectType, object_instance: u32) -> Result<(), AdapterError> {
+        let mut state = self.state();
+        state.calls.push(MockCall::AddObject { device_instance, object_type, object_instance });
+        let added = state.devices.contains(&device_instance) && !state.objects.contains(&(device_instance, object_type, object_instance));
+        if added {
+            state.objects.push((device_instance, object_type, object_instance));
+        }
+        check_stack_call(added, "BACnetStack_AddObject", StackCallContext { device_instance, object_type: Some(object_type), object_instance: Some(object_instance), ..Default::default() })
+    }
+    fn add_notification_class_object(&self, device_instance: u32, object_instance: u32, priority: [u8; 3], ack_required: EventTransitions) -> Result<(), AdapterError> {
+        let mut state = self.state();
+        state.calls.push(MockCall::AddNotificationClassObject { device_instance, object_instance, priority, ack_required });
+        let added = state.devices.contains(&device_instance) && !state.objects.contains(&(device_instance, ObjectType::NotificationClass, object_instance));
+        if added {
+            state.objects.push((device_instance, ObjectType::NotificationClass, object_instance));
+        }
+        check_stack_call(added, "BACnetStack_AddNotificationClassObject", StackCallContext { device_instance, object_type: Some(ObjectType::NotificationClass), object_instance: Some(object_instance), ..Default::default() })
+    }
+
+    // Property Setup Functions
+    fn set_property_enabled(&self, device_instance: u32, object_type: ObjectType, object_instance: u32, property_identifier: PropertyIdentifier, enabled: bool) -> Result<(), AdapterError> {
+        let mut state = self.state();
+        state.calls.push(MockCall::SetPropertyEnabled { device_instance, object_type, object_instance, property_identifier, enabled });
+        let set = state.has_object(device_instance, object_type, object_instance);
+        check_stack_call(set, "BACnetStack_SetPropertyEnabled", StackCallContext { device_instance, object_type: Some(object_type), object_instance: Some(object_instance), property_identifier: Some(property_identifier), ..Default::default() })
+    }
+    fn set_property_by_object_type_enabled(&self, device_instance: u32, object_type: ObjectType, property_identifier: PropertyIdentifier, enabled: bool) -> Result<(), AdapterError> {
+        let mut state = self.state();
+        state.calls.push(MockCall::SetPropertyByObjectTypeEnabled { device_instance, object_type, property_identifier, enabled });
+        let set = state.devices.contains(&device_instance);
+        check_stack_call(set, "BACnetStack_SetPropertyByObjectTypeEnabled", StackCallContext { device_instance, object_type: Some(object_type), property_identifier: Some(property_identifier), ..Default::default() })
+    }
+    fn set_property_writable(&self, device_instance: u32, object_type: ObjectType, object_instance: u32, property_identifier: PropertyIdentifier, writable: bool) -> Result<(), AdapterError> {
+        let mut state = self.state();
+        state.calls.push(MockCall::SetPropertyWritable { device_instance, object_type, object_instance, property_identifier, writable });
+        let set = state.has_object(device_instance, object_type, object_instance);
+        let key = (device_instance, object_type, object_instance, property_identifier);
+        state.writable_properties.retain(|writable_property| *writable_property != key);
+        if set && writable {
+            state.writable_properties.push(key);
+        }
+        check_stack_call(set, "BACnetStack_SetPropertyWritable", StackCallContext { device_instance, object_type: Some(object_type), object_instance: Some(object_instance), property_identifier: Some(property_identifier), ..Default::default() })
+    }
+    fn set_proprietary_property(&self, device_instance: u32, object_type: ObjectType, object_instance: u32, property_identifier: PropertyIdentifier, is_array: bool, data_type: DataType, writable: bool) -> Result<(), AdapterError> {
+        let mut state = self.state();
+        state.calls.push(MockCall::SetProprietaryProperty { device_instance, object_type, object_instance, property_identifier, is_array, data_type, writable });
+        let set = state.has_object(device_instance, object_type, object_instance) && property_identifier.is_proprietary();
+        let key = (device_instance, object_type, object_instance, property_identifier);
+        state.writable_properties.retain(|writable_property| *writable_property != key);
+        if set && writable {
+            state.writable_properties.push(key);
+        }
+        check_stack_call(set, "BACnetStack_SetProprietaryProperty", StackCallContext { device_instance, object_type: Some(object_type), object_instance: Some(object_instance), property_identifier: Some(property_identifier), ..Default::default() })
+    }
+    fn set_service_enabled(&self, device_instance: u32, service: ServiceSupported, enabled: bool) -> Result<(), AdapterError> {
+        let mut state = self.state();
+        state.calls.push(MockCall::SetServiceEnabled { device_instance, service, enabled });
+        let set = state.devices.contains(&device_instance);
+        check_stack_call(set, "BACnetStack_SetServiceEnabled", StackCallContext { device_instance, service: Some(service), ..Default::default() })
+    }
+
+    // Alarm and Event Setup Functions
+    fn enable_alarms_and_events_for_object(&self, device_instance: u32, object_type: ObjectType, object_instance: u32, notification_class: u32, notify_type: NotifyType, event_enable: EventTransitions, event_detection_enable: bool) -> Result<(), AdapterError> {
+        let mut state = self.state();
+        state.calls.push(MockCall::EnableAlarmsAndEventsForObject { device_instance, object_type, object_instance, notification_class, notify_type, event_enable, event_detection_enable });
+        let enabled = state.has_object(device_instance, object_type, object_instance) && state.has_object(device_instance, ObjectType::NotificationClass, notification_class);
+        check_stack_call(enabled, "BACnetStack_EnableAlarmsAndEventsForObject", StackCallContext { device_instance, object_type: Some(object_type), object_instance: Some(object_instance), ..Default::default() })
+    }
+
+    // Data Notification Functions
+    fn value_updated(&self, device_instance: u32, object_type: ObjectType, object_instance: u32, property_identifier: PropertyIdentifier) {
+        self.state().calls.push(MockCall::ValueUpdated { device_instance, object_type, object_instance, property_identifier });
+    }
+
+    // Callback Registration Functions
+    fn register_callback_receive_message(&self, callback: ReceiveMessageCallback) {
+        self.state().receive_message = Some(callback);
+    }
+    fn register_callback_send_message(&self, callback: SendMessageCallback) {
+        self.state().send_message = Some(callback);
+    }
+    fn register_callback_get_system_time(&self, callback: GetSystemTimeCallback) {
+        self.state().get_system_time = Some(callback);
+    }
+    fn register_callback_set_system_time(&self, callback: SetSystemTimeCallback) {
+        self.state().set_system_time = Some(callback);
+    }
+    fn register_callback_get_property_bitstring(&self, callback: GetPropertyBitStringCallback) {
+        self.state().get_property_bitstring = Some(callback);
+    }
+    fn register_callback_get_property_bool(&self, callback: GetPropertyBoolCallback) {
+        self.state().get_property_bool = Some(callback);
+    }
+    fn register_callback_get_property_character_string(&self, callback: GetPropertyCharacterStringCallback) {
+        self.state().get_property_character_string = Some(callback);
+    }
+    fn register_callback_get_property_date(&self, callback: GetPropertyDateCallback) {
+        self.state().get_property_date = Some(callback);
+    }
+    fn register_callback_get_property_double(&self, callback: GetPropertyDoubleCallback) {
+        self.state().get_property_double = Some(callback);
+    }
+    fn register_callback_get_property_enumerated(&self, callback: GetPropertyEnumeratedCallback) {
+        self.state().get_property_enumerated = Some(callback);
+    }
+    fn register_callback_get_property_octet_string(&self, callback: GetPropertyOctetStringCallback) {
+        self.state().get_property_octet_string = Some(callback);
+    }
+    fn register_callback_get_property_real(&self, callback: GetPropertyRealCallback) {
+        self.state().get_property_real = Some(callback);
+    }
+    fn register_callback_get_property_signed_integer(&self, callback: GetPropertySignedIntegerCallback) {
+        self.state().get_property_signed_integer = Some(callback);
+    }
+    fn register_callback_get_property_time(&self, callback: GetPropertyTimeCallback) {
+        self.state().get_property_time = Some(callback);
+    }
+    fn register_callback_get_property_unsigned_integer(&self, callback: GetPropertyUnsignedIntegerCallback) {
+        self.state().get_property_unsigned_integer = Some(callback);
+    }
+    fn register_callback_set_property_bitstring(&self, callback: SetPropertyBitStringCallback) {
+        self.state().set_property_bitstring = Some(callback);
+    }
+    fn register_callback_set_property_bool(&self, callback: SetPropertyBoolCallback) {
+        self.state().set_property_bool = Some(callback);
+    }
+    fn register_callback_set_property_character_string(&self, callback: SetPropertyCharacterStringCallback) {
+        self.state().set_property_character_string = Some(callback);
+    }
+    fn register_callback_set_property_date(&self, callback: SetPropertyDateCallback) {
+        self.state().set_property_date = Some(callback);
+    }
+    fn register_callback_set_property_double(&self, callback: SetPropertyDoubleCallback) {
+        self.state().set_property_double = Some(callback);
+    }
+    fn register_callback_set_property_enumerated(&self, callback: SetPropertyEnumeratedCallback) {
+        self.state().set_property_enumerated = Some(callback);
+    }
+    fn register_callback_set_property_null(&self, callback: SetPropertyNullCallback) {
+        self.state().set_property_null = Some(callback);
+    }
+    fn register_callback_set_property_octet_string(&self, callback: SetPropertyOctetStringCallback) {
+        self.state().set_property_octet_string = Some(callback);
+    }
+    fn register_callback_set_property_real(&self, callback: SetPropertyRealCallback) {
+        self.state().set_property_real = Some(callback);
+    }
+    fn register_callback_set_property_signed_integer(&self, callback: SetPropertySignedIntegerCallback) {
+        self.state().set_property_signed_integer = Some(callback);
+    }
+    fn register_callback_set_property_time(&self, callback: SetPropertyTimeCallback) {
+        self.state().set_property_time = Some(callback);
+    }
+    fn register_callback_set_property_unsigned_integer(&self, callback: SetPropertyUnsignedIntegerCallback) {
+        self.state().set_property_unsigned_integer = Some(callback);
+    }
+}
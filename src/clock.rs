@@ -0,0 +1,119 @@
+// The device clock. It runs at the host clock plus an offset set by time synchronization, so the
+// host clock itself is never changed, and reports local time in an IANA time zone or at a fixed
+// UTC offset.
+
+use std::time::SystemTime;
+
+use tz::TimeZone;
+
+use crate::bacnet_types::{ErrorCode, PropertyIdentifier};
+use crate::database::{date_time_to_millis, millis_to_date_time, BacnetDate, BacnetTime, PropertyValue};
+
+pub struct DeviceClock {
+	// Minutes from local standard time to UTC, positive west of Greenwich. Only used without a time
+	// zone, which has its own.
+	pub utc_offset: i32,
+	pub time_zone: Option<TimeZone>,
+	// Milliseconds from the host clock to the device clock
+	pub time_offset: i64
+}
+
+impl DeviceClock {
+	pub fn new(utc_offset: i32, time_zone: Option<TimeZone>) -> DeviceClock {
+		DeviceClock { utc_offset, time_zone, time_offset: 0 }
+	}
+
+	// Milliseconds since the Unix epoch on the host clock
+	pub fn host_time() -> i64 {
+		SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).map_or(0, |duration| duration.as_millis() as i64)
+	}
+
+	// Milliseconds since the Unix epoch on the device clock
+	pub fn now(&self) -> u64 {
+		(DeviceClock::host_time() + self.time_offset).max(0) as u64
+	}
+
+	// Milliseconds from UTC to local time at time, in milliseconds since the Unix epoch, and whether
+	// daylight saving time is in effect
+	fn local_offset(&self, time: i64) -> (i64, bool) {
+		let local_time_type = self.time_zone.as_ref().and_then(|time_zone| time_zone.find_local_time_type(time.div_euclid(1000)).ok());
+		match local_time_type {
+			Some(local_time_type) => (i64::from(local_time_type.ut_offset()) * 1000, local_time_type.is_dst()),
+			None => (-i64::from(self.utc_offset) * 60_000, false)
+		}
+	}
+
+	// UTC_Offset at time. In daylight saving time the standard offset is taken from the last time
+	// outside it, looking back a month at a time over the past year.
+	pub fn current_utc_offset(&self, time: u64) -> i32 {
+		if self.time_zone.is_none() {
+			return self.utc_offset;
+		}
+		(0..=12).map(|months| self.local_offset(time as i64 - months * 30 * 86_400_000))
+			.find(|(_, is_dst)| !is_dst)
+			.map_or(self.utc_offset, |(offset, _)| -(offset / 60_000) as i32)
+	}
+
+	// Local date and time at time, in milliseconds since the Unix epoch
+	pub fn local_date_time(&self, time: u64) -> (BacnetDate, BacnetTime) {
+		let (offset, _) = self.local_offset(time as i64);
+		millis_to_date_time((time as i64 + offset).max(0) as u64)
+	}
+
+	// Milliseconds since the Unix epoch at a local date and time. In a time zone the offset is looked
+	// up at the UTC time of the local time, first guessed with the offset at the local time itself, so
+	// a time just after a daylight saving change is not off by it. None for a date or time with
+	// unspecified fields.
+	pub fn local_to_utc(&self, date: &BacnetDate, time: &BacnetTime) -> Option<i64> {
+		let local = date_time_to_millis(date, time)? as i64;
+		let (guess, _) = self.local_offset(local);
+		let (offset, _) = self.local_offset(local - guess);
+		Some(local - offset)
+	}
+
+	// Sets the clock to a local date and time. Fails for a date or time with unspecified fields.
+	pub fn set_local_date_time(&mut self, date: &BacnetDate, time: &BacnetTime) -> Result<(), ErrorCode> {
+		let utc = self.local_to_utc(date, time).ok_or(ErrorCode::ValueOutOfRange)?;
+		self.time_offset = utc - DeviceClock::host_time();
+		Ok(())
+	}
+
+	// Sets the clock to a UTC date and time. Fails for a date or time with unspecified fields.
+	pub fn set_utc_date_time(&mut self, date: &BacnetDate, time: &BacnetTime) -> Result<(), ErrorCode> {
+		let utc = date_time_to_millis(date, time).ok_or(ErrorCode::ValueOutOfRange)?;
+		self.time_offset = utc as i64 - DeviceClock::host_time();
+		Ok(())
+	}
+
+	// Reads the clock properties, None for any other property
+	pub fn get_property(&self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>) -> Option<Result<PropertyValue, ErrorCode>> {
+		let now = self.now();
+		let value = match property_identifier {
+			PropertyIdentifier::LocalDate => PropertyValue::Date(self.local_date_time(now).0),
+			PropertyIdentifier::LocalTime => PropertyValue::Time(self.local_date_time(now).1),
+			PropertyIdentifier::UtcOffset => PropertyValue::Signed(self.current_utc_offset(now)),
+			PropertyIdentifier::DaylightSavingsStatus => PropertyValue::Boolean(self.local_offset(now as i64).1),
+			_ => return None
+		};
+		match property_array_index {
+			Some(_) => Some(Err(ErrorCode::PropertyIsNotAnArray)),
+			None => Some(Ok(value))
+		}
+	}
+
+	// Writes the clock properties, None for any other property. Only a fixed UTC offset can be
+	// written, the local date and time are set by time synchronization.
+	pub fn set_property(&mut self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>, value: &PropertyValue) -> Option<Result<(), ErrorCode>> {
+		let current = self.get_property(property_identifier, property_array_index)?;
+		Some(current.and_then(|_| match (property_identifier, value) {
+			(PropertyIdentifier::UtcOffset, _) if self.time_zone.is_some() => Err(ErrorCode::WriteAccessDenied),
+			(PropertyIdentifier::UtcOffset, PropertyValue::Signed(utc_offset)) if !(-1440..=1440).contains(utc_offset) => Err(ErrorCode::ValueOutOfRange),
+			(PropertyIdentifier::UtcOffset, PropertyValue::Signed(utc_offset)) => {
+				self.utc_offset = *utc_offset;
+				Ok(())
+			},
+			(PropertyIdentifier::UtcOffset, _) => Err(ErrorCode::InvalidDataType),
+			_ => Err(ErrorCode::WriteAccessDenied)
+		}))
+	}
+}
@@ -0,0 +1,284 @@
+use std::collections::HashMap;
+use std::env;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::bacnet_types::{CharacterSet, DataType, ObjectType, PropertyIdentifier};
+use crate::cas_bacnet_stack_adapters::LibraryOptions;
+
+// Used when no --config argument is given and the file exists in the working directory
+pub const DEFAULT_CONFIG_FILE: &str = "config.toml";
+
+// Journal of written values used when the [persistence] section does not name one
+pub const DEFAULT_JOURNAL_FILE: &str = "bacnet_values.jsonl";
+
+// Device and objects used when the config file has no [device] section
+const EXAMPLE_CONFIG: &str = include_str!("../config.example.toml");
+
+// Contents of the TOML configuration file
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerConfig {
+	pub stack: StackConfig,
+	pub persistence: PersistenceConfig,
+	pub device: Option<DeviceConfig>,
+	pub objects: Vec<ObjectConfig>
+}
+
+// [stack] section: where to find the CAS BACnet Stack library
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StackConfig {
+	pub library: Option<PathBuf>,
+	pub variant: Option<String>,
+	pub architecture: Option<String>,
+	pub search_paths: Vec<PathBuf>
+}
+
+impl StackConfig {
+	pub fn library_options(&self) -> Result<LibraryOptions, ConfigError> {
+		Ok(LibraryOptions {
+			path: self.library.clone(),
+			variant: self.variant.as_deref().map(str::parse).transpose().map_err(ConfigError::Invalid)?,
+			architecture: self.architecture.as_deref().map(str::parse).transpose().map_err(ConfigError::Invalid)?,
+			search_paths: self.search_paths.clone()
+		})
+	}
+}
+
+// [persistence] section: where written property values are journaled
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PersistenceConfig {
+	pub enabled: bool,
+	pub journal: PathBuf
+}
+
+impl Default for PersistenceConfig {
+	fn default() -> Self {
+		PersistenceConfig { enabled: true, journal: PathBuf::from(DEFAULT_JOURNAL_FILE) }
+	}
+}
+
+// The device and objects to serve, from the config file or the example config
+#[derive(Debug)]
+pub struct DatabaseConfig {
+	pub device: DeviceConfig,
+	pub objects: Vec<ObjectConfig>,
+	// None when persistence is disabled
+	pub journal: Option<PathBuf>
+}
+
+// [device] section: the BACnet device hosted by this server
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DeviceConfig {
+	pub instance: u32,
+	pub name: String,
+	#[serde(default)]
+	pub description: String,
+	// Minutes from local standard time to UTC, positive west of Greenwich, when there is no time zone
+	#[serde(default)]
+	pub utc_offset: i32,
+	// IANA time zone, such as "America/Vancouver", for the local time and daylight saving time
+	#[serde(default)]
+	pub time_zone: Option<String>,
+	#[serde(default)]
+	pub enabled_properties: Vec<PropertyIdentifier>,
+	#[serde(default)]
+	pub writable_properties: Vec<PropertyIdentifier>,
+	#[serde(default)]
+	pub character_sets: HashMap<PropertyIdentifier, CharacterSet>
+}
+
+// [[objects]] entry: one object in the database
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ObjectConfig {
+	#[serde(rename = "type")]
+	pub object_type: ObjectType,
+	pub instance: u32,
+	pub name: String,
+	#[serde(default)]
+	pub values: HashMap<PropertyIdentifier, toml::Value>,
+	#[serde(default)]
+	pub enabled_properties: Vec<PropertyIdentifier>,
+	#[serde(default)]
+	pub writable_properties: Vec<PropertyIdentifier>,
+	#[serde(default)]
+	pub proprietary_properties: Vec<ProprietaryPropertyConfig>,
+	#[serde(default)]
+	pub character_sets: HashMap<PropertyIdentifier, CharacterSet>,
+	#[serde(default = "default_serve")]
+	pub serve: bool
+}
+
+// [[objects.proprietary_properties]] entry: a vendor specific property of the object, holding a
+// value of its type or, when array is set, an array of them
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProprietaryPropertyConfig {
+	pub identifier: PropertyIdentifier,
+	#[serde(rename = "type")]
+	pub data_type: DataType,
+	#[serde(default)]
+	pub array: bool,
+	#[serde(default)]
+	pub writable: bool,
+	pub value: toml::Value
+}
+
+fn default_serve() -> bool {
+	true
+}
+
+impl ObjectConfig {
+	// Initial values to be taken one property at a time by the database
+	pub fn values(&self) -> ObjectValues {
+		ObjectValues { object_type: self.object_type, object_instance: self.instance, values: self.values.clone() }
+	}
+
+	// Listed in writable_properties, or a proprietary property declared writable
+	pub fn is_writable(&self, property_identifier: PropertyIdentifier) -> bool {
+		self.writable_properties.contains(&property_identifier)
+			|| self.proprietary_properties.iter().any(|property| property.identifier == property_identifier && property.writable)
+	}
+}
+
+// Initial property values of one configured object. Each value is taken once, anything left over
+// is not used by that object type and is reported as an error.
+pub struct ObjectValues {
+	object_type: ObjectType,
+	object_instance: u32,
+	values: HashMap<PropertyIdentifier, toml::Value>
+}
+
+impl ObjectValues {
+	// Takes the value of a property, or the default when it is not configured
+	pub fn take<T: for<'de> Deserialize<'de>>(&mut self, property_identifier: PropertyIdentifier, default: T) -> Result<T, ConfigError> {
+		match self.values.remove(&property_identifier) {
+			Some(value) => value.try_into().map_err(|err| ConfigError::Invalid(format!("{} {} {}: {}", self.object_type, self.object_instance, property_identifier, err))),
+			None => Ok(default)
+		}
+	}
+
+	// Takes the raw TOML value of a property, for values that need more than deserializing
+	pub fn take_raw(&mut self, property_identifier: PropertyIdentifier) -> Option<toml::Value> {
+		self.values.remove(&property_identifier)
+	}
+
+	// Fails if any configured value was not taken
+	pub fn finish(self) -> Result<(), ConfigError> {
+		let mut unused: Vec<String> = self.values.keys().map(PropertyIdentifier::to_string).collect();
+		if unused.is_empty() {
+			return Ok(());
+		}
+		unused.sort();
+		Err(ConfigError::Invalid(format!("{} {} has no configurable value for {}", self.object_type, self.object_instance, unused.join(", "))))
+	}
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+	Read(PathBuf, std::io::Error),
+	Parse(PathBuf, toml::de::Error),
+	Invalid(String),
+	Usage(String)
+}
+
+impl fmt::Display for ConfigError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			ConfigError::Read(path, err) => write!(f, "Unable to read config file {}: {}", path.display(), err),
+			ConfigError::Parse(path, err) => write!(f, "Unable to parse config file {}: {}", path.display(), err),
+			ConfigError::Invalid(message) => write!(f, "Invalid configuration: {message}"),
+			ConfigError::Usage(message) => write!(f, "{message}\n\n{USAGE}")
+		}
+	}
+}
+
+impl std::error::Error for ConfigError {}
+
+pub fn load_config_file(path: &Path) -> Result<ServerConfig, ConfigError> {
+	let contents = fs::read_to_string(path).map_err(|err| ConfigError::Read(path.to_path_buf(), err))?;
+	toml::from_str(&contents).map_err(|err| ConfigError::Parse(path.to_path_buf(), err))
+}
+
+pub const USAGE: &str = "Usage: BACnetServerExampleRust [OPTIONS]
+
+Options:
+  --config <FILE>             TOML configuration file (default: ./config.toml if present)
+  --stack-library <FILE>      Path to the CAS BACnet Stack library
+  --stack-variant <VARIANT>   Debug or Release
+  --stack-arch <ARCH>         x86 or x64
+  --stack-search-path <DIR>   Directory to search for the library, may be repeated
+  --mock-stack                Run against an in-process mock stack instead of the library
+  -h, --help                  Print this message";
+
+// Options given on the command line
+#[derive(Debug, Default)]
+pub struct CommandLine {
+	pub config_file: Option<PathBuf>,
+	pub library: LibraryOptions,
+	pub mock_stack: bool,
+	pub help: bool
+}
+
+impl CommandLine {
+	pub fn parse<I: Iterator<Item = String>>(mut args: I) -> Result<CommandLine, ConfigError> {
+		let mut command_line = CommandLine::default();
+		while let Some(arg) = args.next() {
+			let mut value = |name: &str| args.next().ok_or_else(|| ConfigError::Usage(format!("Missing value for {name}")));
+			match arg.as_str() {
+				"--config" => command_line.config_file = Some(PathBuf::from(value(&arg)?)),
+				"--stack-library" => command_line.library.path = Some(PathBuf::from(value(&arg)?)),
+				"--stack-variant" => command_line.library.variant = Some(value(&arg)?.parse().map_err(ConfigError::Usage)?),
+				"--stack-arch" => command_line.library.architecture = Some(value(&arg)?.parse().map_err(ConfigError::Usage)?),
+				"--stack-search-path" => command_line.library.search_paths.push(PathBuf::from(value(&arg)?)),
+				"--mock-stack" => command_line.mock_stack = true,
+				"-h" | "--help" => command_line.help = true,
+				_ => return Err(ConfigError::Usage(format!("Unknown argument: {arg}")))
+			}
+		}
+		Ok(command_line)
+	}
+}
+
+// Loads the config file and resolves the library options.
+// Precedence: command line, then environment variables, then config file.
+pub fn load(command_line: &CommandLine) -> Result<(DatabaseConfig, LibraryOptions), ConfigError> {
+	let mut config = match &command_line.config_file {
+		Some(path) => load_config_file(path)?,
+		None if Path::new(DEFAULT_CONFIG_FILE).is_file() => load_config_file(Path::new(DEFAULT_CONFIG_FILE))?,
+		None => ServerConfig::default()
+	};
+	let journal = config.persistence.enabled.then_some(config.persistence.journal);
+	let mut database = match config.device.take() {
+		Some(device) => DatabaseConfig { device, objects: config.objects, journal },
+		None if config.objects.is_empty() => {
+			let example: ServerConfig = toml::from_str(EXAMPLE_CONFIG).map_err(|err| ConfigError::Parse(PathBuf::from("config.example.toml"), err))?;
+			DatabaseConfig { device: example.device.ok_or_else(|| ConfigError::Invalid("the example config has no [device] section".to_string()))?, objects: example.objects, journal }
+		},
+		None => return Err(ConfigError::Invalid("objects are configured without a [device] section".to_string()))
+	};
+
+	// The present value of an input, schedule or loop is written while it is out of service, the database rejects it otherwise
+	for object in &mut database.objects {
+		let is_overridable = matches!(object.object_type, ObjectType::AnalogInput | ObjectType::BinaryInput | ObjectType::MultiStateInput | ObjectType::Schedule | ObjectType::Loop);
+		if is_overridable && object.writable_properties.contains(&PropertyIdentifier::OutOfService) && !object.writable_properties.contains(&PropertyIdentifier::PresentValue) {
+			object.writable_properties.push(PropertyIdentifier::PresentValue);
+		}
+	}
+
+	let library_options = config.stack.library_options()?
+		.overridden_by(LibraryOptions::from_env().map_err(ConfigError::Invalid)?)
+		.overridden_by(command_line.library.clone());
+	Ok((database, library_options))
+}
+
+pub fn parse_command_line() -> Result<CommandLine, ConfigError> {
+	CommandLine::parse(env::args().skip(1))
+}
@@ -1,14 +1,107 @@
 use std::collections::HashMap;
+use std::fmt;
+use std::net::{Ipv4Addr, SocketAddrV4};
+
+use serde::{Deserialize, Serialize};
+use tz::TimeZone;
+
+use crate::bacnet_types::{CharacterSet, DataType, EngineeringUnits, ErrorCode, EventState, EventTransitions, LoggingType, NotifyType, ObjectType, Polarity, PropertyIdentifier, Reliability};
+use crate::cas_bacnet_stack_example_constants as bacnet_const;
+use crate::clock::DeviceClock;
+use crate::config::{ConfigError, DatabaseConfig, DeviceConfig, ObjectConfig, ObjectValues, ProprietaryPropertyConfig};
+use crate::intrinsic_reporting::{EventAlgorithm, IntrinsicReporting};
+use crate::pid::Pid;
+use crate::schedule::{CalendarEntry, DateRange, SpecialEvent, SpecialEventPeriod, TimeValue, Timetable};
+use crate::trend_log::{LogBuffer, ReadRange, ReadRangeResult};
 
 const PRIORITY_ARRAY_LENGTH: usize = 16;
+// Largest size a client may give a resizable array
+const MAX_ARRAY_LENGTH: usize = 1024;
+
+// A date or time field that is 0xFF is unspecified, and matches any value
+pub const UNSPECIFIED: u8 = 0xFF;
+
+// Identifies an object in the example database
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ObjectKey {
+	pub device_instance: u32,
+	pub object_type: ObjectType,
+	pub object_instance: u32
+}
+
+impl ObjectKey {
+	pub fn new(device_instance: u32, object_type: ObjectType, object_instance: u32) -> ObjectKey {
+		ObjectKey { device_instance, object_type, object_instance }
+	}
+
+	// The device object of a device
+	pub fn device(device_instance: u32) -> ObjectKey {
+		ObjectKey::new(device_instance, ObjectType::Device, device_instance)
+	}
+
+	// From the raw values passed to the stack callbacks. None for an unknown object type.
+	pub fn from_raw(device_instance: u32, object_type: u16, object_instance: u32) -> Option<ObjectKey> {
+		ObjectType::try_from(object_type).ok().map(|object_type| ObjectKey::new(device_instance, object_type, object_instance))
+	}
+}
+
+impl fmt::Display for ObjectKey {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "device {} {} {}", self.device_instance, self.object_type, self.object_instance)
+	}
+}
+
+// Every object of the example database
+pub type Database = HashMap<ObjectKey, ExampleDatabaseObject>;
+
+// Declares ExampleDatabaseObject with a variant for each object type, and the methods that forward
+// to the object of any variant
+macro_rules! example_database_objects {
+	($($variant:ident($object:ty)),* $(,)?) => {
+		pub enum ExampleDatabaseObject {
+			$($variant($object),)*
+		}
+
+		impl ExampleDatabaseObject {
+			pub fn as_object(&self) -> &dyn BacnetObject {
+				match self {
+					$(ExampleDatabaseObject::$variant(object) => object,)*
+				}
+			}
 
-pub enum ExampleDatabaseObject {
+			pub fn as_object_mut(&mut self) -> &mut dyn BacnetObject {
+				match self {
+					$(ExampleDatabaseObject::$variant(object) => object,)*
+				}
+			}
+
+			pub fn proprietary_properties(&self) -> &ProprietaryProperties {
+				match self {
+					$(ExampleDatabaseObject::$variant(object) => &object.proprietary_properties,)*
+				}
+			}
+
+			pub fn proprietary_properties_mut(&mut self) -> &mut ProprietaryProperties {
+				match self {
+					$(ExampleDatabaseObject::$variant(object) => &mut object.proprietary_properties,)*
+				}
+			}
+		}
+	};
+}
+
+example_database_objects! {
 	AnalogInput(ExampleDatabaseAnalogInput),
 	AnalogOutput(ExampleDatabaseAnalogOutput),
 	AnalogValue(ExampleDatabaseAnalogValue),
 	BinaryInput(ExampleDatabaseBinaryInput),
+	BinaryOutput(ExampleDatabaseBinaryOutput),
+	BinaryValue(ExampleDatabaseBinaryValue),
+	DateValue(ExampleDatabaseDateValue),
 	Device(ExampleDatabaseDevice),
 	MultiStateInput(ExampleDatabaseMultiStateInput),
+	MultiStateOutput(ExampleDatabaseMultiStateOutput),
+	MultiStateValue(ExampleDatabaseMultiStateValue),
 	BitstringValue(ExampleDatabaseBitstringValue),
 	CharacterStringValue(ExampleDatabaseCharacterStringValue),
 	IntegerValue(ExampleDatabaseIntegerValue),
@@ -16,110 +109,1367 @@ pub enum ExampleDatabaseObject {
 	OctetStringValue(ExampleDatabaseOctetStringValue),
 	PositiveIntegerValue(ExampleDatabasePositiveIntegerValue),
 	NetworkPort(ExampleDatabaseNetworkPort),
-	DateTimeValue(ExampleDatabaseDateTimeValue)
+	DateTimeValue(ExampleDatabaseDateTimeValue),
+	TimeValue(ExampleDatabaseTimeValue),
+	TrendLog(ExampleDatabaseTrendLog),
+	NotificationClass(ExampleDatabaseNotificationClass),
+	Schedule(ExampleDatabaseSchedule),
+	Calendar(ExampleDatabaseCalendar),
+	Loop(ExampleDatabaseLoop)
+}
+
+// A property value as written by a client
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PropertyValue {
+	Null,
+	Boolean(bool),
+	Unsigned(u32),
+	Signed(i32),
+	Real(f32),
+	Double(f64),
+	OctetString(Vec<u8>),
+	CharacterString(String),
+	BitString(Vec<bool>),
+	Enumerated(u32),
+	Date(BacnetDate),
+	Time(BacnetTime),
+	DateTime(BacnetDate, BacnetTime),
+	Array(Vec<PropertyValue>)
+}
+
+// A BACnet date, the year is counted from 1900 and the weekday runs from 1 (Monday) to 7 (Sunday)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BacnetDate {
+	pub year: u8,
+	pub month: u8,
+	pub day: u8,
+	pub weekday: u8
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BacnetTime {
+	pub hour: u8,
+	pub minute: u8,
+	pub second: u8,
+	pub hundredth_seconds: u8
+}
+
+impl BacnetDate {
+	pub const UNSPECIFIED: BacnetDate = BacnetDate { year: UNSPECIFIED, month: UNSPECIFIED, day: UNSPECIFIED, weekday: UNSPECIFIED };
+
+	// Any field may be unspecified. The month may also be odd (13) or even (14), and the day the last
+	// of the month (32), odd (33) or even (34). A specific day must exist in its month, and a specific
+	// weekday must fall on a fully specified date.
+	pub fn is_valid(&self) -> bool {
+		if !matches!(self.month, 1..=14 | UNSPECIFIED) || !matches!(self.day, 1..=34 | UNSPECIFIED) || !matches!(self.weekday, 1..=7 | UNSPECIFIED) {
+			return false;
+		}
+		if !matches!((self.month, self.day), (1..=12, 1..=31)) {
+			return true;
+		}
+		let year = (self.year != UNSPECIFIED).then(|| 1900 + u16::from(self.year));
+		if self.day > days_in_month(year, self.month) {
+			return false;
+		}
+		match year {
+			Some(year) => self.weekday == UNSPECIFIED || self.weekday == day_of_week(year, self.month, self.day),
+			None => true
+		}
+	}
+
+	// Whether a fully specified date matches this date pattern
+	pub fn matches(&self, date: &BacnetDate) -> bool {
+		let month_matches = match self.month {
+			13 => !date.month.is_multiple_of(2),
+			14 => date.month.is_multiple_of(2),
+			month => month == UNSPECIFIED || month == date.month
+		};
+		let day_matches = match self.day {
+			32 => date.day == days_in_month(Some(1900 + u16::from(date.year)), date.month),
+			33 => !date.day.is_multiple_of(2),
+			34 => date.day.is_multiple_of(2),
+			day => day == UNSPECIFIED || day == date.day
+		};
+		(self.year == UNSPECIFIED || self.year == date.year) && month_matches && day_matches && (self.weekday == UNSPECIFIED || self.weekday == date.weekday)
+	}
+}
+
+impl BacnetTime {
+	pub const UNSPECIFIED: BacnetTime = BacnetTime { hour: UNSPECIFIED, minute: UNSPECIFIED, second: UNSPECIFIED, hundredth_seconds: UNSPECIFIED };
+
+	// Any field may be unspecified
+	pub fn is_valid(&self) -> bool {
+		matches!(self.hour, 0..=23 | UNSPECIFIED) && matches!(self.minute, 0..=59 | UNSPECIFIED) && matches!(self.second, 0..=59 | UNSPECIFIED) && matches!(self.hundredth_seconds, 0..=99 | UNSPECIFIED)
+	}
+}
+
+// An object in the example database. Each object type reads and writes the properties it stores,
+// properties it does not have are left to the stack.
+pub trait BacnetObject {
+	fn object_name(&self) -> &str;
+
+	fn get_property(&self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>) -> Result<PropertyValue, ErrorCode>;
+
+	fn set_property(&mut self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>, value: &PropertyValue) -> Result<(), ErrorCode>;
+
+	// Whether writes to the property are commands at the priority of the request
+	fn is_commandable(&self, _property_identifier: PropertyIdentifier) -> bool {
+		false
+	}
+
+	// Commands the property at a priority from 1 (highest) to 16. Null relinquishes the command.
+	fn command(&mut self, _property_identifier: PropertyIdentifier, _priority: u8, _value: &PropertyValue) -> Result<(), ErrorCode> {
+		Err(ErrorCode::WriteAccessDenied)
+	}
+}
+
+impl ExampleDatabaseObject {
+	// Every object type but the device, notification classes and calendars has Status_Flags and Event_State
+	fn has_status_flags(&self) -> bool {
+		!matches!(self, ExampleDatabaseObject::Device(_) | ExampleDatabaseObject::NotificationClass(_) | ExampleDatabaseObject::Calendar(_))
+	}
+
+	fn is_fault(&self) -> bool {
+		matches!(self.as_object().get_property(PropertyIdentifier::Reliability, None), Ok(PropertyValue::Enumerated(reliability)) if reliability != u32::from(Reliability::NoFaultDetected))
+	}
+
+	// From the intrinsic reporting when the object has it, otherwise fault when its reliability reports one
+	pub fn event_state(&self) -> EventState {
+		match self.intrinsic_reporting() {
+			Some(intrinsic_reporting) => intrinsic_reporting.event_state,
+			None if self.is_fault() => EventState::Fault,
+			None => EventState::Normal
+		}
+	}
+
+	// In-alarm, fault, overridden and out-of-service. Nothing overrides the objects of this server.
+	pub fn status_flags(&self) -> Vec<bool> {
+		let out_of_service = matches!(self.as_object().get_property(PropertyIdentifier::OutOfService, None), Ok(PropertyValue::Boolean(true)));
+		vec![self.event_state() != EventState::Normal, self.is_fault(), false, out_of_service]
+	}
+
+	// The intrinsic reporting of objects that report events, None for any other object
+	pub fn intrinsic_reporting(&self) -> Option<&IntrinsicReporting> {
+		match self {
+			ExampleDatabaseObject::AnalogInput(object) => object.intrinsic_reporting.as_ref(),
+			ExampleDatabaseObject::BinaryInput(object) => object.intrinsic_reporting.as_ref(),
+			_ => None
+		}
+	}
+}
+
+impl BacnetObject for ExampleDatabaseObject {
+	fn object_name(&self) -> &str {
+		self.as_object().object_name()
+	}
+
+	fn get_property(&self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>) -> Result<PropertyValue, ErrorCode> {
+		if let Some(value) = self.proprietary_properties().get_property(property_identifier, property_array_index) {
+			return value;
+		}
+		match property_identifier {
+			PropertyIdentifier::StatusFlags if self.has_status_flags() => check_not_array(property_array_index).map(|_| PropertyValue::BitString(self.status_flags())),
+			PropertyIdentifier::EventState if self.has_status_flags() => check_not_array(property_array_index).map(|_| PropertyValue::Enumerated(self.event_state().into())),
+			_ => self.as_object().get_property(property_identifier, property_array_index)
+		}
+	}
+
+	// Status_Flags and Event_State are computed from the object
+	fn set_property(&mut self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>, value: &PropertyValue) -> Result<(), ErrorCode> {
+		if let Some(result) = self.proprietary_properties_mut().set_property(property_identifier, property_array_index, value) {
+			return result;
+		}
+		match property_identifier {
+			PropertyIdentifier::StatusFlags | PropertyIdentifier::EventState if self.has_status_flags() => Err(ErrorCode::WriteAccessDenied),
+			_ => self.as_object_mut().set_property(property_identifier, property_array_index, value)
+		}
+	}
+
+	fn is_commandable(&self, property_identifier: PropertyIdentifier) -> bool {
+		self.as_object().is_commandable(property_identifier)
+	}
+
+	fn command(&mut self, property_identifier: PropertyIdentifier, priority: u8, value: &PropertyValue) -> Result<(), ErrorCode> {
+		self.as_object_mut().command(property_identifier, priority, value)
+	}
+}
+
+// For properties that are not arrays
+fn check_not_array(property_array_index: Option<u32>) -> Result<(), ErrorCode> {
+	match property_array_index {
+		Some(_) => Err(ErrorCode::PropertyIsNotAnArray),
+		None => Ok(())
+	}
+}
+
+// Reads an array property: every element without an index, the number of elements at index 0, or
+// the element at an index from 1
+fn array_property<T>(elements: &[T], property_array_index: Option<u32>, to_value: impl Fn(&T) -> PropertyValue) -> Result<PropertyValue, ErrorCode> {
+	match property_array_index {
+		None => Ok(PropertyValue::Array(elements.iter().map(to_value).collect())),
+		Some(0) => Ok(PropertyValue::Unsigned(elements.len() as u32)),
+		Some(index) => elements.get(index as usize - 1).map(to_value).ok_or(ErrorCode::InvalidArrayIndex)
+	}
+}
+
+// Writes an array of fixed size: the whole array, or the element at an index from 1. Index 0 holds
+// the size, which cannot be written.
+fn write_array_property<T>(elements: &mut [T], property_array_index: Option<u32>, value: &PropertyValue, from_value: impl Fn(&PropertyValue) -> Result<T, ErrorCode>) -> Result<(), ErrorCode> {
+	match (property_array_index, value) {
+		(None, PropertyValue::Array(values)) if values.len() == elements.len() => {
+			let values = values.iter().map(from_value).collect::<Result<Vec<T>, ErrorCode>>()?;
+			for (element, value) in elements.iter_mut().zip(values) {
+				*element = value;
+			}
+		},
+		(None, PropertyValue::Array(_)) => return Err(ErrorCode::ValueOutOfRange),
+		(None, _) => return Err(ErrorCode::InvalidDataType),
+		(Some(0), _) => return Err(ErrorCode::WriteAccessDenied),
+		(Some(index), value) => *elements.get_mut(index as usize - 1).ok_or(ErrorCode::InvalidArrayIndex)? = from_value(value)?
+	}
+	Ok(())
+}
+
+// Writes an array clients may resize: a size at index 0 truncates it or pads it with new elements,
+// and a whole array replaces it at its own size. Single elements are written as in a fixed array.
+fn write_resizable_array_property<T: Clone>(elements: &mut Vec<T>, property_array_index: Option<u32>, value: &PropertyValue, new_element: T, from_value: impl Fn(&PropertyValue) -> Result<T, ErrorCode>) -> Result<(), ErrorCode> {
+	match (property_array_index, value) {
+		(Some(0), PropertyValue::Unsigned(length)) if *length as usize > MAX_ARRAY_LENGTH => return Err(ErrorCode::ValueOutOfRange),
+		(Some(0), PropertyValue::Unsigned(length)) => elements.resize(*length as usize, new_element),
+		(Some(0), _) => return Err(ErrorCode::InvalidDataType),
+		(None, PropertyValue::Array(values)) if values.len() > MAX_ARRAY_LENGTH => return Err(ErrorCode::ValueOutOfRange),
+		(None, PropertyValue::Array(values)) => *elements = values.iter().map(from_value).collect::<Result<Vec<T>, ErrorCode>>()?,
+		_ => return write_array_property(elements, property_array_index, value, from_value)
+	}
+	Ok(())
+}
+
+fn character_string_value(value: &PropertyValue) -> Result<String, ErrorCode> {
+	match value {
+		PropertyValue::CharacterString(text) => Ok(text.clone()),
+		_ => Err(ErrorCode::InvalidDataType)
+	}
+}
+
+// Error for a write the object cannot store: the property is read only, or the object does not have it
+fn write_error(object: &dyn BacnetObject, property_identifier: PropertyIdentifier) -> ErrorCode {
+	match object.get_property(property_identifier, None) {
+		Ok(_) => ErrorCode::WriteAccessDenied,
+		Err(_) => ErrorCode::UnknownProperty
+	}
+}
+
+// A present value type that can be commanded through a priority array
+pub trait CommandValue: Copy {
+	// A priority that cannot be commanded, reserved by the object type
+	const RESERVED_PRIORITY: Option<u8> = None;
+
+	fn to_property_value(self) -> PropertyValue;
+
+	fn from_property_value(value: &PropertyValue) -> Result<Self, ErrorCode>;
+}
+
+impl CommandValue for f32 {
+	fn to_property_value(self) -> PropertyValue {
+		PropertyValue::Real(self)
+	}
+
+	fn from_property_value(value: &PropertyValue) -> Result<f32, ErrorCode> {
+		match value {
+			PropertyValue::Real(value) => Ok(*value),
+			_ => Err(ErrorCode::InvalidDataType)
+		}
+	}
+}
+
+// BACnetBinaryPV, inactive (0) or active (1). Binary objects reserve priority 6 for minimum on and
+// off times.
+impl CommandValue for bool {
+	const RESERVED_PRIORITY: Option<u8> = Some(6);
+
+	fn to_property_value(self) -> PropertyValue {
+		PropertyValue::Enumerated(u32::from(self))
+	}
+
+	fn from_property_value(value: &PropertyValue) -> Result<bool, ErrorCode> {
+		match value {
+			PropertyValue::Enumerated(value @ (0 | 1)) => Ok(*value == 1),
+			PropertyValue::Enumerated(_) => Err(ErrorCode::ValueOutOfRange),
+			_ => Err(ErrorCode::InvalidDataType)
+		}
+	}
+}
+
+impl CommandValue for u32 {
+	fn to_property_value(self) -> PropertyValue {
+		PropertyValue::Unsigned(self)
+	}
+
+	fn from_property_value(value: &PropertyValue) -> Result<u32, ErrorCode> {
+		match value {
+			PropertyValue::Unsigned(value) => Ok(*value),
+			_ => Err(ErrorCode::InvalidDataType)
+		}
+	}
+}
+
+// Priority array and relinquish default of a commandable object. The present value is the command at
+// the highest active priority, or the relinquish default when every priority is relinquished.
+pub struct Commandable<T: CommandValue> {
+	pub priority_array: [Option<T>; PRIORITY_ARRAY_LENGTH],
+	pub relinquish_default: T
+}
+
+impl<T: CommandValue> Commandable<T> {
+	pub fn new(relinquish_default: T) -> Commandable<T> {
+		Commandable { priority_array: [None; PRIORITY_ARRAY_LENGTH], relinquish_default }
+	}
+
+	pub fn present_value(&self) -> T {
+		self.priority_array.iter().flatten().next().copied().unwrap_or(self.relinquish_default)
+	}
+
+	pub fn command(&mut self, priority: u8, value: &PropertyValue) -> Result<(), ErrorCode> {
+		if !(1..=bacnet_const::MAX_BACNET_PRIORITY).contains(&priority) {
+			return Err(ErrorCode::ParameterOutOfRange);
+		}
+		if T::RESERVED_PRIORITY == Some(priority) {
+			return Err(ErrorCode::WriteAccessDenied);
+		}
+		self.priority_array[usize::from(priority - 1)] = match value {
+			PropertyValue::Null => None,
+			value => Some(T::from_property_value(value)?)
+		};
+		Ok(())
+	}
+
+	// Reads the commandable properties, None for any other property
+	pub fn get_property(&self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>) -> Option<Result<PropertyValue, ErrorCode>> {
+		let value = match (property_identifier, property_array_index) {
+			(PropertyIdentifier::PriorityArray, _) => array_property(&self.priority_array, property_array_index, |command| command.map_or(PropertyValue::Null, T::to_property_value)),
+			(PropertyIdentifier::PresentValue | PropertyIdentifier::RelinquishDefault, Some(_)) => Err(ErrorCode::PropertyIsNotAnArray),
+			(PropertyIdentifier::PresentValue, None) => Ok(self.present_value().to_property_value()),
+			(PropertyIdentifier::RelinquishDefault, None) => Ok(self.relinquish_default.to_property_value()),
+			_ => return None
+		};
+		Some(value)
+	}
+
+	// Writes the relinquish default, None for any other property. The priority array is only
+	// changed by commanding the present value.
+	pub fn set_property(&mut self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>, value: &PropertyValue) -> Option<Result<(), ErrorCode>> {
+		match property_identifier {
+			PropertyIdentifier::RelinquishDefault => Some(check_not_array(property_array_index).and_then(|_| {
+				self.relinquish_default = T::from_property_value(value)?;
+				Ok(())
+			})),
+			PropertyIdentifier::PriorityArray => Some(Err(ErrorCode::WriteAccessDenied)),
+			_ => None
+		}
+	}
+}
+
+// Number of states and state text of a multi-state object. States are numbered from 1.
+pub struct StateSet {
+	pub number_of_states: u32,
+	pub state_text: Vec<String>
+}
+
+impl StateSet {
+	pub fn check_state(&self, state: u32) -> Result<(), ErrorCode> {
+		if state == 0 || state > self.number_of_states {
+			return Err(ErrorCode::ValueOutOfRange);
+		}
+		Ok(())
+	}
+
+	// Reads the number of states, and the state text when there is any. None for any other property.
+	pub fn get_property(&self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>) -> Option<Result<PropertyValue, ErrorCode>> {
+		match property_identifier {
+			PropertyIdentifier::NumberOfStates => Some(check_not_array(property_array_index).map(|_| PropertyValue::Unsigned(self.number_of_states))),
+			PropertyIdentifier::StateText if !self.state_text.is_empty() => Some(array_property(&self.state_text, property_array_index, |text| PropertyValue::CharacterString(text.clone()))),
+			_ => None
+		}
+	}
+
+	// Writes the state text, one state or all of them. The number of states is fixed by the configuration.
+	pub fn set_property(&mut self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>, value: &PropertyValue) -> Option<Result<(), ErrorCode>> {
+		let current = self.get_property(property_identifier, property_array_index)?;
+		Some(current.and_then(|_| match property_identifier {
+			PropertyIdentifier::StateText => write_array_property(&mut self.state_text, property_array_index, value, character_string_value),
+			_ => Err(ErrorCode::WriteAccessDenied)
+		}))
+	}
+}
+
+// Units, resolution, present value range and COV increment of an analog object
+pub struct AnalogProperties {
+	pub units: EngineeringUnits,
+	pub resolution: f32,
+	pub min_pres_value: f32,
+	pub max_pres_value: f32,
+	pub cov_increment: f32
+}
+
+impl AnalogProperties {
+	pub fn check_present_value(&self, present_value: f32) -> Result<(), ErrorCode> {
+		if !(self.min_pres_value..=self.max_pres_value).contains(&present_value) {
+			return Err(ErrorCode::ValueOutOfRange);
+		}
+		Ok(())
+	}
+
+	// Reads the analog properties, None for any other property
+	pub fn get_property(&self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>) -> Option<Result<PropertyValue, ErrorCode>> {
+		let value = match property_identifier {
+			PropertyIdentifier::Units => PropertyValue::Enumerated(self.units.into()),
+			PropertyIdentifier::Resolution => PropertyValue::Real(self.resolution),
+			PropertyIdentifier::MinPresValue => PropertyValue::Real(self.min_pres_value),
+			PropertyIdentifier::MaxPresValue => PropertyValue::Real(self.max_pres_value),
+			PropertyIdentifier::CovIncrement => PropertyValue::Real(self.cov_increment),
+			_ => return None
+		};
+		Some(check_not_array(property_array_index).map(|_| value))
+	}
+
+	// Writes the analog properties, None for any other property. The range must not exclude the
+	// present value, given as the object's current one.
+	pub fn set_property(&mut self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>, value: &PropertyValue, present_value: f32) -> Option<Result<(), ErrorCode>> {
+		let current = self.get_property(property_identifier, property_array_index)?;
+		Some(current.and_then(|_| self.write(property_identifier, value, present_value)))
+	}
+
+	fn write(&mut self, property_identifier: PropertyIdentifier, value: &PropertyValue, present_value: f32) -> Result<(), ErrorCode> {
+		match (property_identifier, value) {
+			(PropertyIdentifier::Units, PropertyValue::Enumerated(units)) => self.units = EngineeringUnits::try_from(*units).map_err(|_| ErrorCode::ValueOutOfRange)?,
+			(PropertyIdentifier::Resolution | PropertyIdentifier::CovIncrement, PropertyValue::Real(value)) if *value < 0.0 => return Err(ErrorCode::ValueOutOfRange),
+			(PropertyIdentifier::Resolution, PropertyValue::Real(resolution)) => self.resolution = *resolution,
+			(PropertyIdentifier::CovIncrement, PropertyValue::Real(cov_increment)) => self.cov_increment = *cov_increment,
+			(PropertyIdentifier::MinPresValue, PropertyValue::Real(min_pres_value)) if *min_pres_value > present_value.min(self.max_pres_value) => return Err(ErrorCode::ValueOutOfRange),
+			(PropertyIdentifier::MinPresValue, PropertyValue::Real(min_pres_value)) => self.min_pres_value = *min_pres_value,
+			(PropertyIdentifier::MaxPresValue, PropertyValue::Real(max_pres_value)) if *max_pres_value < present_value.max(self.min_pres_value) => return Err(ErrorCode::ValueOutOfRange),
+			(PropertyIdentifier::MaxPresValue, PropertyValue::Real(max_pres_value)) => self.max_pres_value = *max_pres_value,
+			_ => return Err(ErrorCode::InvalidDataType)
+		}
+		Ok(())
+	}
+}
+
+// A vendor specific property declared in the configuration. The value of an array is a
+// PropertyValue::Array of its elements.
+pub struct ProprietaryProperty {
+	pub property_identifier: PropertyIdentifier,
+	pub data_type: DataType,
+	pub value: PropertyValue
+}
+
+// The proprietary properties of an object, served for every object type
+#[derive(Default)]
+pub struct ProprietaryProperties {
+	pub properties: Vec<ProprietaryProperty>
+}
+
+impl ProprietaryProperties {
+	// Reads a declared property, None for any other property
+	pub fn get_property(&self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>) -> Option<Result<PropertyValue, ErrorCode>> {
+		let property = self.properties.iter().find(|property| property.property_identifier == property_identifier)?;
+		Some(match &property.value {
+			PropertyValue::Array(elements) => array_property(elements, property_array_index, PropertyValue::clone),
+			value => check_not_array(property_array_index).map(|_| value.clone())
+		})
+	}
+
+	// Writes a value of its data type to a declared property, None for any other property. Arrays
+	// are resized by writing their size at index 0, new elements take the data type's initial value.
+	pub fn set_property(&mut self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>, value: &PropertyValue) -> Option<Result<(), ErrorCode>> {
+		let property = self.properties.iter_mut().find(|property| property.property_identifier == property_identifier)?;
+		let data_type = property.data_type;
+		Some(match (&mut property.value, property_array_index) {
+			(PropertyValue::Array(elements), Some(index)) if index > 0 => match elements.get_mut(index as usize - 1) {
+				Some(element) => write_proprietary_value(data_type, element, value),
+				None => Err(ErrorCode::InvalidArrayIndex)
+			},
+			(PropertyValue::Array(elements), _) => match initial_value(data_type) {
+				Some(new_element) => write_resizable_array_property(elements, property_array_index, value, new_element, |value| has_data_type(value, data_type).then(|| value.clone()).ok_or(ErrorCode::InvalidDataType)),
+				None => Err(ErrorCode::WriteAccessDenied)
+			},
+			(current, None) => write_proprietary_value(data_type, current, value),
+			(_, Some(_)) => Err(ErrorCode::PropertyIsNotAnArray)
+		})
+	}
+}
+
+// A date and time is written as its date and its time
+fn write_proprietary_value(data_type: DataType, current: &mut PropertyValue, value: &PropertyValue) -> Result<(), ErrorCode> {
+	match (current, value) {
+		(_, PropertyValue::Date(date) | PropertyValue::DateTime(date, _)) if !date.is_valid() => return Err(ErrorCode::ValueOutOfRange),
+		(_, PropertyValue::Time(time) | PropertyValue::DateTime(_, time)) if !time.is_valid() => return Err(ErrorCode::ValueOutOfRange),
+		(PropertyValue::DateTime(date, _), PropertyValue::Date(value)) => *date = *value,
+		(PropertyValue::DateTime(_, time), PropertyValue::Time(value)) => *time = *value,
+		(current, value) if has_data_type(value, data_type) => *current = value.clone(),
+		_ => return Err(ErrorCode::InvalidDataType)
+	}
+	Ok(())
+}
+
+// The value of a new element of a proprietary array
+fn initial_value(data_type: DataType) -> Option<PropertyValue> {
+	match data_type {
+		DataType::Boolean => Some(PropertyValue::Boolean(false)),
+		DataType::UnsignedInteger => Some(PropertyValue::Unsigned(0)),
+		DataType::SignedInteger => Some(PropertyValue::Signed(0)),
+		DataType::Real => Some(PropertyValue::Real(0.0)),
+		DataType::Double => Some(PropertyValue::Double(0.0)),
+		DataType::OctetString => Some(PropertyValue::OctetString(Vec::new())),
+		DataType::CharacterString => Some(PropertyValue::CharacterString(String::new())),
+		DataType::BitString => Some(PropertyValue::BitString(Vec::new())),
+		DataType::Enumerated => Some(PropertyValue::Enumerated(0)),
+		DataType::Date => Some(PropertyValue::Date(BacnetDate::UNSPECIFIED)),
+		DataType::Time => Some(PropertyValue::Time(BacnetTime::UNSPECIFIED)),
+		DataType::DateTime => Some(PropertyValue::DateTime(BacnetDate::UNSPECIFIED, BacnetTime::UNSPECIFIED)),
+		DataType::Null | DataType::BacnetObjectIdentifier => None
+	}
+}
+
+fn has_data_type(value: &PropertyValue, data_type: DataType) -> bool {
+	matches!((data_type, value),
+		(DataType::Boolean, PropertyValue::Boolean(_))
+		| (DataType::UnsignedInteger, PropertyValue::Unsigned(_))
+		| (DataType::SignedInteger, PropertyValue::Signed(_))
+		| (DataType::Real, PropertyValue::Real(_))
+		| (DataType::Double, PropertyValue::Double(_))
+		| (DataType::OctetString, PropertyValue::OctetString(_))
+		| (DataType::CharacterString, PropertyValue::CharacterString(_))
+		| (DataType::BitString, PropertyValue::BitString(_))
+		| (DataType::Enumerated, PropertyValue::Enumerated(_))
+		| (DataType::Date, PropertyValue::Date(_))
+		| (DataType::Time, PropertyValue::Time(_))
+		| (DataType::DateTime, PropertyValue::DateTime(_, _)))
 }
 
 pub struct ExampleDatabaseAnalogInput {
 	pub object_name: String,
 	pub instance: u32,
+	pub out_of_service: bool,
 	pub present_value: f32,
-
-	pub cov_incurment: f32,
-	pub reliability: u32,
+	pub analog: AnalogProperties,
+	pub reliability: Reliability,
 	pub description: String,
+	pub intrinsic_reporting: Option<IntrinsicReporting>,
+	pub proprietary_properties: ProprietaryProperties
+}
+
+impl ExampleDatabaseAnalogInput {
+	// Returns the new event state when it changes
+	fn update_event_state(&mut self, now: u64) -> Option<EventState> {
+		self.intrinsic_reporting.as_mut()?.update(now, &PropertyValue::Real(self.present_value), self.reliability)
+	}
 
-	pub proprietary_year: u8,
-	pub proprietary_month: u8,
-	pub proprietary_day: u8,
-	pub proprietary_weekday: u8,
-	pub proprietary_hour: u8,
-	pub proprietary_minute: u8,
-	pub proprietary_second: u8,
-	pub proprietary_hundredth_seconds: u8,
+	// One step of the example's simulated sensor. The value climbs by 1.001 until it is past the high
+	// limit and deadband, then starts over from the low limit, so it keeps crossing the limits. Without
+	// limits it wraps from 100 to 0. Returns whether it started over.
+	pub fn simulate_step(&mut self) -> bool {
+		let (low, high) = match self.intrinsic_reporting.as_ref().map(|intrinsic_reporting| intrinsic_reporting.algorithm) {
+			Some(EventAlgorithm::OutOfRange { high_limit, low_limit, deadband, .. }) => (low_limit, high_limit + deadband),
+			_ => (0.0, 100.0)
+		};
+		if self.present_value > high {
+			self.present_value = low;
+			return true;
+		}
+		self.present_value += 1.001;
+		false
+	}
+}
+
+impl BacnetObject for ExampleDatabaseAnalogInput {
+	fn object_name(&self) -> &str {
+		&self.object_name
+	}
 
-	pub proprietary_real: f32,
+	fn get_property(&self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>) -> Result<PropertyValue, ErrorCode> {
+		if let Some(value) = self.intrinsic_reporting.as_ref().and_then(|intrinsic_reporting| intrinsic_reporting.get_property(property_identifier, property_array_index)) {
+			return value;
+		}
+		if let Some(value) = self.analog.get_property(property_identifier, property_array_index) {
+			return value;
+		}
+		check_not_array(property_array_index)?;
+		match property_identifier {
+			PropertyIdentifier::ObjectName => Ok(PropertyValue::CharacterString(self.object_name.clone())),
+			PropertyIdentifier::OutOfService => Ok(PropertyValue::Boolean(self.out_of_service)),
+			PropertyIdentifier::PresentValue => Ok(PropertyValue::Real(self.present_value)),
+			PropertyIdentifier::Reliability => Ok(PropertyValue::Enumerated(self.reliability.into())),
+			PropertyIdentifier::Description => Ok(PropertyValue::CharacterString(self.description.clone())),
+			_ => Err(ErrorCode::UnknownProperty)
+		}
+	}
 
-	pub proprietary_array_of_real: Vec<f32>
+	fn set_property(&mut self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>, value: &PropertyValue) -> Result<(), ErrorCode> {
+		if let Some(result) = self.intrinsic_reporting.as_mut().and_then(|intrinsic_reporting| intrinsic_reporting.set_property(property_identifier, property_array_index, value)) {
+			return result;
+		}
+		if let Some(result) = self.analog.set_property(property_identifier, property_array_index, value, self.present_value) {
+			return result;
+		}
+		check_not_array(property_array_index)?;
+		match (property_identifier, value) {
+			(PropertyIdentifier::ObjectName, PropertyValue::CharacterString(object_name)) => self.object_name = object_name.clone(),
+			(PropertyIdentifier::OutOfService, PropertyValue::Boolean(out_of_service)) => self.out_of_service = *out_of_service,
+			// The present value and reliability of an input follow its data source while it is in service
+			(PropertyIdentifier::PresentValue | PropertyIdentifier::Reliability, _) if !self.out_of_service => return Err(ErrorCode::WriteAccessDenied),
+			(PropertyIdentifier::PresentValue, PropertyValue::Real(present_value)) => {
+				self.analog.check_present_value(*present_value)?;
+				self.present_value = *present_value;
+			},
+			(PropertyIdentifier::Reliability, PropertyValue::Enumerated(reliability)) => self.reliability = Reliability::try_from(*reliability).map_err(|_| ErrorCode::ValueOutOfRange)?,
+			(PropertyIdentifier::Description, PropertyValue::CharacterString(description)) => self.description = description.clone(),
+			(PropertyIdentifier::ObjectName | PropertyIdentifier::OutOfService | PropertyIdentifier::PresentValue | PropertyIdentifier::Reliability | PropertyIdentifier::Description, _) => return Err(ErrorCode::InvalidDataType),
+			_ => return Err(write_error(self, property_identifier))
+		}
+		Ok(())
+	}
 }
 
 pub struct ExampleDatabaseAnalogOutput {
 	pub object_name: String,
 	pub instance: u32,
-	pub priority_array_nulls: [bool; PRIORITY_ARRAY_LENGTH],
-	pub priority_array_values: [bool; PRIORITY_ARRAY_LENGTH]	
+	pub out_of_service: bool,
+	pub commandable: Commandable<f32>,
+	pub analog: AnalogProperties,
+	pub proprietary_properties: ProprietaryProperties
+}
+
+impl BacnetObject for ExampleDatabaseAnalogOutput {
+	fn object_name(&self) -> &str {
+		&self.object_name
+	}
+
+	fn get_property(&self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>) -> Result<PropertyValue, ErrorCode> {
+		if let Some(value) = self.commandable.get_property(property_identifier, property_array_index) {
+			return value;
+		}
+		if let Some(value) = self.analog.get_property(property_identifier, property_array_index) {
+			return value;
+		}
+		check_not_array(property_array_index)?;
+		match property_identifier {
+			PropertyIdentifier::ObjectName => Ok(PropertyValue::CharacterString(self.object_name.clone())),
+			PropertyIdentifier::OutOfService => Ok(PropertyValue::Boolean(self.out_of_service)),
+			_ => Err(ErrorCode::UnknownProperty)
+		}
+	}
+
+	fn set_property(&mut self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>, value: &PropertyValue) -> Result<(), ErrorCode> {
+		if let (PropertyIdentifier::RelinquishDefault, PropertyValue::Real(relinquish_default)) = (property_identifier, value) {
+			self.analog.check_present_value(*relinquish_default)?;
+		}
+		if let Some(result) = self.commandable.set_property(property_identifier, property_array_index, value) {
+			return result;
+		}
+		if let Some(result) = self.analog.set_property(property_identifier, property_array_index, value, self.commandable.present_value()) {
+			return result;
+		}
+		check_not_array(property_array_index)?;
+		match (property_identifier, value) {
+			(PropertyIdentifier::ObjectName, PropertyValue::CharacterString(object_name)) => self.object_name = object_name.clone(),
+			(PropertyIdentifier::OutOfService, PropertyValue::Boolean(out_of_service)) => self.out_of_service = *out_of_service,
+			(PropertyIdentifier::ObjectName | PropertyIdentifier::OutOfService, _) => return Err(ErrorCode::InvalidDataType),
+			_ => return Err(write_error(self, property_identifier))
+		}
+		Ok(())
+	}
+
+	fn is_commandable(&self, property_identifier: PropertyIdentifier) -> bool {
+		property_identifier == PropertyIdentifier::PresentValue
+	}
+
+	fn command(&mut self, property_identifier: PropertyIdentifier, priority: u8, value: &PropertyValue) -> Result<(), ErrorCode> {
+		if property_identifier != PropertyIdentifier::PresentValue {
+			return Err(ErrorCode::WriteAccessDenied);
+		}
+		if let PropertyValue::Real(present_value) = value {
+			self.analog.check_present_value(*present_value)?;
+		}
+		self.commandable.command(priority, value)
+	}
 }
 
 pub struct ExampleDatabaseAnalogValue {
 	pub object_name: String,
 	pub instance: u32,
+	pub out_of_service: bool,
 	pub present_value: f32,
-	pub max_pres_value: f32,
-	pub min_pres_value: f32
+	pub analog: AnalogProperties,
+	pub proprietary_properties: ProprietaryProperties
+}
+
+impl BacnetObject for ExampleDatabaseAnalogValue {
+	fn object_name(&self) -> &str {
+		&self.object_name
+	}
+
+	fn get_property(&self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>) -> Result<PropertyValue, ErrorCode> {
+		if let Some(value) = self.analog.get_property(property_identifier, property_array_index) {
+			return value;
+		}
+		check_not_array(property_array_index)?;
+		match property_identifier {
+			PropertyIdentifier::ObjectName => Ok(PropertyValue::CharacterString(self.object_name.clone())),
+			PropertyIdentifier::OutOfService => Ok(PropertyValue::Boolean(self.out_of_service)),
+			PropertyIdentifier::PresentValue => Ok(PropertyValue::Real(self.present_value)),
+			_ => Err(ErrorCode::UnknownProperty)
+		}
+	}
+
+	fn set_property(&mut self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>, value: &PropertyValue) -> Result<(), ErrorCode> {
+		if let Some(result) = self.analog.set_property(property_identifier, property_array_index, value, self.present_value) {
+			return result;
+		}
+		check_not_array(property_array_index)?;
+		match (property_identifier, value) {
+			(PropertyIdentifier::ObjectName, PropertyValue::CharacterString(object_name)) => self.object_name = object_name.clone(),
+			(PropertyIdentifier::OutOfService, PropertyValue::Boolean(out_of_service)) => self.out_of_service = *out_of_service,
+			(PropertyIdentifier::PresentValue, PropertyValue::Real(present_value)) => {
+				self.analog.check_present_value(*present_value)?;
+				self.present_value = *present_value;
+			},
+			(PropertyIdentifier::ObjectName | PropertyIdentifier::OutOfService | PropertyIdentifier::PresentValue, _) => return Err(ErrorCode::InvalidDataType),
+			_ => return Err(write_error(self, property_identifier))
+		}
+		Ok(())
+	}
 }
 
 pub struct ExampleDatabaseBinaryInput {
 	pub object_name: String,
 	pub instance: u32,
+	pub out_of_service: bool,
 	pub present_value: bool,
-	pub description: String
+	pub polarity: Polarity,
+	pub active_text: String,
+	pub inactive_text: String,
+	pub description: String,
+	pub intrinsic_reporting: Option<IntrinsicReporting>,
+	pub proprietary_properties: ProprietaryProperties
+}
+
+impl ExampleDatabaseBinaryInput {
+	// Returns the new event state when it changes
+	fn update_event_state(&mut self, now: u64) -> Option<EventState> {
+		self.intrinsic_reporting.as_mut()?.update(now, &PropertyValue::Enumerated(u32::from(self.present_value)), Reliability::NoFaultDetected)
+	}
+}
+
+impl BacnetObject for ExampleDatabaseBinaryInput {
+	fn object_name(&self) -> &str {
+		&self.object_name
+	}
+
+	fn get_property(&self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>) -> Result<PropertyValue, ErrorCode> {
+		if let Some(value) = self.intrinsic_reporting.as_ref().and_then(|intrinsic_reporting| intrinsic_reporting.get_property(property_identifier, property_array_index)) {
+			return value;
+		}
+		check_not_array(property_array_index)?;
+		match property_identifier {
+			PropertyIdentifier::ObjectName => Ok(PropertyValue::CharacterString(self.object_name.clone())),
+			PropertyIdentifier::OutOfService => Ok(PropertyValue::Boolean(self.out_of_service)),
+			PropertyIdentifier::PresentValue => Ok(PropertyValue::Enumerated(u32::from(self.present_value))),
+			PropertyIdentifier::Polarity => Ok(PropertyValue::Enumerated(self.polarity.into())),
+			PropertyIdentifier::ActiveText => Ok(PropertyValue::CharacterString(self.active_text.clone())),
+			PropertyIdentifier::InactiveText => Ok(PropertyValue::CharacterString(self.inactive_text.clone())),
+			PropertyIdentifier::Description => Ok(PropertyValue::CharacterString(self.description.clone())),
+			_ => Err(ErrorCode::UnknownProperty)
+		}
+	}
+
+	fn set_property(&mut self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>, value: &PropertyValue) -> Result<(), ErrorCode> {
+		if let Some(result) = self.intrinsic_reporting.as_mut().and_then(|intrinsic_reporting| intrinsic_reporting.set_property(property_identifier, property_array_index, value)) {
+			return result;
+		}
+		check_not_array(property_array_index)?;
+		match (property_identifier, value) {
+			(PropertyIdentifier::ObjectName, PropertyValue::CharacterString(object_name)) => self.object_name = object_name.clone(),
+			(PropertyIdentifier::OutOfService, PropertyValue::Boolean(out_of_service)) => self.out_of_service = *out_of_service,
+			// The present value of an input follows its data source while it is in service
+			(PropertyIdentifier::PresentValue, _) if !self.out_of_service => return Err(ErrorCode::WriteAccessDenied),
+			(PropertyIdentifier::PresentValue, PropertyValue::Enumerated(present_value @ (0 | 1))) => self.present_value = *present_value == 1,
+			(PropertyIdentifier::PresentValue, PropertyValue::Enumerated(_)) => return Err(ErrorCode::ValueOutOfRange),
+			(PropertyIdentifier::Polarity, PropertyValue::Enumerated(polarity)) => self.polarity = Polarity::try_from(*polarity).map_err(|_| ErrorCode::ValueOutOfRange)?,
+			(PropertyIdentifier::ActiveText, PropertyValue::CharacterString(active_text)) => self.active_text = active_text.clone(),
+			(PropertyIdentifier::InactiveText, PropertyValue::CharacterString(inactive_text)) => self.inactive_text = inactive_text.clone(),
+			(PropertyIdentifier::Description, PropertyValue::CharacterString(description)) => self.description = description.clone(),
+			(PropertyIdentifier::ObjectName | PropertyIdentifier::OutOfService | PropertyIdentifier::PresentValue | PropertyIdentifier::Polarity | PropertyIdentifier::ActiveText | PropertyIdentifier::InactiveText | PropertyIdentifier::Description, _) => return Err(ErrorCode::InvalidDataType),
+			_ => return Err(write_error(self, property_identifier))
+		}
+		Ok(())
+	}
+}
+
+pub struct ExampleDatabaseBinaryOutput {
+	pub object_name: String,
+	pub instance: u32,
+	pub out_of_service: bool,
+	pub commandable: Commandable<bool>,
+	pub polarity: Polarity,
+	pub active_text: String,
+	pub inactive_text: String,
+	pub proprietary_properties: ProprietaryProperties
+}
+
+impl BacnetObject for ExampleDatabaseBinaryOutput {
+	fn object_name(&self) -> &str {
+		&self.object_name
+	}
+
+	fn get_property(&self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>) -> Result<PropertyValue, ErrorCode> {
+		if let Some(value) = self.commandable.get_property(property_identifier, property_array_index) {
+			return value;
+		}
+		check_not_array(property_array_index)?;
+		match property_identifier {
+			PropertyIdentifier::ObjectName => Ok(PropertyValue::CharacterString(self.object_name.clone())),
+			PropertyIdentifier::OutOfService => Ok(PropertyValue::Boolean(self.out_of_service)),
+			PropertyIdentifier::Polarity => Ok(PropertyValue::Enumerated(self.polarity.into())),
+			PropertyIdentifier::ActiveText => Ok(PropertyValue::CharacterString(self.active_text.clone())),
+			PropertyIdentifier::InactiveText => Ok(PropertyValue::CharacterString(self.inactive_text.clone())),
+			_ => Err(ErrorCode::UnknownProperty)
+		}
+	}
+
+	fn set_property(&mut self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>, value: &PropertyValue) -> Result<(), ErrorCode> {
+		if let Some(result) = self.commandable.set_property(property_identifier, property_array_index, value) {
+			return result;
+		}
+		check_not_array(property_array_index)?;
+		match (property_identifier, value) {
+			(PropertyIdentifier::ObjectName, PropertyValue::CharacterString(object_name)) => self.object_name = object_name.clone(),
+			(PropertyIdentifier::OutOfService, PropertyValue::Boolean(out_of_service)) => self.out_of_service = *out_of_service,
+			(PropertyIdentifier::Polarity, PropertyValue::Enumerated(polarity)) => self.polarity = Polarity::try_from(*polarity).map_err(|_| ErrorCode::ValueOutOfRange)?,
+			(PropertyIdentifier::ActiveText, PropertyValue::CharacterString(active_text)) => self.active_text = active_text.clone(),
+			(PropertyIdentifier::InactiveText, PropertyValue::CharacterString(inactive_text)) => self.inactive_text = inactive_text.clone(),
+			(PropertyIdentifier::ObjectName | PropertyIdentifier::OutOfService | PropertyIdentifier::Polarity | PropertyIdentifier::ActiveText | PropertyIdentifier::InactiveText, _) => return Err(ErrorCode::InvalidDataType),
+			_ => return Err(write_error(self, property_identifier))
+		}
+		Ok(())
+	}
+
+	fn is_commandable(&self, property_identifier: PropertyIdentifier) -> bool {
+		property_identifier == PropertyIdentifier::PresentValue
+	}
+
+	fn command(&mut self, property_identifier: PropertyIdentifier, priority: u8, value: &PropertyValue) -> Result<(), ErrorCode> {
+		if property_identifier != PropertyIdentifier::PresentValue {
+			return Err(ErrorCode::WriteAccessDenied);
+		}
+		self.commandable.command(priority, value)
+	}
+}
+
+pub struct ExampleDatabaseBinaryValue {
+	pub object_name: String,
+	pub instance: u32,
+	pub out_of_service: bool,
+	pub commandable: Commandable<bool>,
+	pub active_text: String,
+	pub inactive_text: String,
+	pub proprietary_properties: ProprietaryProperties
+}
+
+impl BacnetObject for ExampleDatabaseBinaryValue {
+	fn object_name(&self) -> &str {
+		&self.object_name
+	}
+
+	fn get_property(&self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>) -> Result<PropertyValue, ErrorCode> {
+		if let Some(value) = self.commandable.get_property(property_identifier, property_array_index) {
+			return value;
+		}
+		check_not_array(property_array_index)?;
+		match property_identifier {
+			PropertyIdentifier::ObjectName => Ok(PropertyValue::CharacterString(self.object_name.clone())),
+			PropertyIdentifier::OutOfService => Ok(PropertyValue::Boolean(self.out_of_service)),
+			PropertyIdentifier::ActiveText => Ok(PropertyValue::CharacterString(self.active_text.clone())),
+			PropertyIdentifier::InactiveText => Ok(PropertyValue::CharacterString(self.inactive_text.clone())),
+			_ => Err(ErrorCode::UnknownProperty)
+		}
+	}
+
+	fn set_property(&mut self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>, value: &PropertyValue) -> Result<(), ErrorCode> {
+		if let Some(result) = self.commandable.set_property(property_identifier, property_array_index, value) {
+			return result;
+		}
+		check_not_array(property_array_index)?;
+		match (property_identifier, value) {
+			(PropertyIdentifier::ObjectName, PropertyValue::CharacterString(object_name)) => self.object_name = object_name.clone(),
+			(PropertyIdentifier::OutOfService, PropertyValue::Boolean(out_of_service)) => self.out_of_service = *out_of_service,
+			(PropertyIdentifier::ActiveText, PropertyValue::CharacterString(active_text)) => self.active_text = active_text.clone(),
+			(PropertyIdentifier::InactiveText, PropertyValue::CharacterString(inactive_text)) => self.inactive_text = inactive_text.clone(),
+			(PropertyIdentifier::ObjectName | PropertyIdentifier::OutOfService | PropertyIdentifier::ActiveText | PropertyIdentifier::InactiveText, _) => return Err(ErrorCode::InvalidDataType),
+			_ => return Err(write_error(self, property_identifier))
+		}
+		Ok(())
+	}
+
+	fn is_commandable(&self, property_identifier: PropertyIdentifier) -> bool {
+		property_identifier == PropertyIdentifier::PresentValue
+	}
+
+	fn command(&mut self, property_identifier: PropertyIdentifier, priority: u8, value: &PropertyValue) -> Result<(), ErrorCode> {
+		if property_identifier != PropertyIdentifier::PresentValue {
+			return Err(ErrorCode::WriteAccessDenied);
+		}
+		self.commandable.command(priority, value)
+	}
 }
 
 pub struct ExampleDatabaseDevice {
 	pub object_name: String,
 	pub instance: u32,
-	pub utc_offset: i32,
-	pub current_time_offset: i32,
+	pub clock: DeviceClock,
 	pub description: String,
-	pub system_status: u32
+	pub system_status: u32,
+	pub proprietary_properties: ProprietaryProperties
+}
+
+impl BacnetObject for ExampleDatabaseDevice {
+	fn object_name(&self) -> &str {
+		&self.object_name
+	}
+
+	fn get_property(&self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>) -> Result<PropertyValue, ErrorCode> {
+		if let Some(value) = self.clock.get_property(property_identifier, property_array_index) {
+			return value;
+		}
+		check_not_array(property_array_index)?;
+		match property_identifier {
+			PropertyIdentifier::ObjectName => Ok(PropertyValue::CharacterString(self.object_name.clone())),
+			PropertyIdentifier::Description => Ok(PropertyValue::CharacterString(self.description.clone())),
+			PropertyIdentifier::SystemStatus => Ok(PropertyValue::Enumerated(self.system_status)),
+			_ => Err(ErrorCode::UnknownProperty)
+		}
+	}
+
+	fn set_property(&mut self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>, value: &PropertyValue) -> Result<(), ErrorCode> {
+		if let Some(result) = self.clock.set_property(property_identifier, property_array_index, value) {
+			return result;
+		}
+		check_not_array(property_array_index)?;
+		match (property_identifier, value) {
+			(PropertyIdentifier::ObjectName, PropertyValue::CharacterString(object_name)) => self.object_name = object_name.clone(),
+			(PropertyIdentifier::Description, PropertyValue::CharacterString(description)) => self.description = description.clone(),
+			(PropertyIdentifier::ObjectName | PropertyIdentifier::Description, _) => return Err(ErrorCode::InvalidDataType),
+			_ => return Err(write_error(self, property_identifier))
+		}
+		Ok(())
+	}
 }
 
 pub struct ExampleDatabaseMultiStateInput {
 	pub object_name: String,
 	pub instance: u32,
+	pub out_of_service: bool,
 	pub present_value: u32,
-	pub state_text: Vec<String>
+	pub states: StateSet,
+	pub proprietary_properties: ProprietaryProperties
+}
+
+impl BacnetObject for ExampleDatabaseMultiStateInput {
+	fn object_name(&self) -> &str {
+		&self.object_name
+	}
+
+	fn get_property(&self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>) -> Result<PropertyValue, ErrorCode> {
+		if let Some(value) = self.states.get_property(property_identifier, property_array_index) {
+			return value;
+		}
+		check_not_array(property_array_index)?;
+		match property_identifier {
+			PropertyIdentifier::ObjectName => Ok(PropertyValue::CharacterString(self.object_name.clone())),
+			PropertyIdentifier::OutOfService => Ok(PropertyValue::Boolean(self.out_of_service)),
+			PropertyIdentifier::PresentValue => Ok(PropertyValue::Unsigned(self.present_value)),
+			_ => Err(ErrorCode::UnknownProperty)
+		}
+	}
+
+	fn set_property(&mut self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>, value: &PropertyValue) -> Result<(), ErrorCode> {
+		if let Some(result) = self.states.set_property(property_identifier, property_array_index, value) {
+			return result;
+		}
+		check_not_array(property_array_index)?;
+		match (property_identifier, value) {
+			(PropertyIdentifier::ObjectName, PropertyValue::CharacterString(object_name)) => self.object_name = object_name.clone(),
+			(PropertyIdentifier::OutOfService, PropertyValue::Boolean(out_of_service)) => self.out_of_service = *out_of_service,
+			// The present value of an input follows its data source while it is in service
+			(PropertyIdentifier::PresentValue, _) if !self.out_of_service => return Err(ErrorCode::WriteAccessDenied),
+			(PropertyIdentifier::PresentValue, PropertyValue::Unsigned(present_value)) => {
+				self.states.check_state(*present_value)?;
+				self.present_value = *present_value;
+			},
+			(PropertyIdentifier::ObjectName | PropertyIdentifier::OutOfService | PropertyIdentifier::PresentValue, _) => return Err(ErrorCode::InvalidDataType),
+			_ => return Err(write_error(self, property_identifier))
+		}
+		Ok(())
+	}
+}
+
+pub struct ExampleDatabaseMultiStateOutput {
+	pub object_name: String,
+	pub instance: u32,
+	pub out_of_service: bool,
+	pub commandable: Commandable<u32>,
+	pub states: StateSet,
+	pub proprietary_properties: ProprietaryProperties
+}
+
+impl BacnetObject for ExampleDatabaseMultiStateOutput {
+	fn object_name(&self) -> &str {
+		&self.object_name
+	}
+
+	fn get_property(&self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>) -> Result<PropertyValue, ErrorCode> {
+		if let Some(value) = self.commandable.get_property(property_identifier, property_array_index) {
+			return value;
+		}
+		if let Some(value) = self.states.get_property(property_identifier, property_array_index) {
+			return value;
+		}
+		check_not_array(property_array_index)?;
+		match property_identifier {
+			PropertyIdentifier::ObjectName => Ok(PropertyValue::CharacterString(self.object_name.clone())),
+			PropertyIdentifier::OutOfService => Ok(PropertyValue::Boolean(self.out_of_service)),
+			_ => Err(ErrorCode::UnknownProperty)
+		}
+	}
+
+	fn set_property(&mut self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>, value: &PropertyValue) -> Result<(), ErrorCode> {
+		if let (PropertyIdentifier::RelinquishDefault, PropertyValue::Unsigned(state)) = (property_identifier, value) {
+			self.states.check_state(*state)?;
+		}
+		if let Some(result) = self.commandable.set_property(property_identifier, property_array_index, value) {
+			return result;
+		}
+		if let Some(result) = self.states.set_property(property_identifier, property_array_index, value) {
+			return result;
+		}
+		check_not_array(property_array_index)?;
+		match (property_identifier, value) {
+			(PropertyIdentifier::ObjectName, PropertyValue::CharacterString(object_name)) => self.object_name = object_name.clone(),
+			(PropertyIdentifier::OutOfService, PropertyValue::Boolean(out_of_service)) => self.out_of_service = *out_of_service,
+			(PropertyIdentifier::ObjectName | PropertyIdentifier::OutOfService, _) => return Err(ErrorCode::InvalidDataType),
+			_ => return Err(write_error(self, property_identifier))
+		}
+		Ok(())
+	}
+
+	fn is_commandable(&self, property_identifier: PropertyIdentifier) -> bool {
+		property_identifier == PropertyIdentifier::PresentValue
+	}
+
+	fn command(&mut self, property_identifier: PropertyIdentifier, priority: u8, value: &PropertyValue) -> Result<(), ErrorCode> {
+		if property_identifier != PropertyIdentifier::PresentValue {
+			return Err(ErrorCode::WriteAccessDenied);
+		}
+		if let PropertyValue::Unsigned(state) = value {
+			self.states.check_state(*state)?;
+		}
+		self.commandable.command(priority, value)
+	}
+}
+
+pub struct ExampleDatabaseMultiStateValue {
+	pub object_name: String,
+	pub instance: u32,
+	pub out_of_service: bool,
+	pub commandable: Commandable<u32>,
+	pub states: StateSet,
+	pub proprietary_properties: ProprietaryProperties
+}
+
+impl BacnetObject for ExampleDatabaseMultiStateValue {
+	fn object_name(&self) -> &str {
+		&self.object_name
+	}
+
+	fn get_property(&self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>) -> Result<PropertyValue, ErrorCode> {
+		if let Some(value) = self.commandable.get_property(property_identifier, property_array_index) {
+			return value;
+		}
+		if let Some(value) = self.states.get_property(property_identifier, property_array_index) {
+			return value;
+		}
+		check_not_array(property_array_index)?;
+		match property_identifier {
+			PropertyIdentifier::ObjectName => Ok(PropertyValue::CharacterString(self.object_name.clone())),
+			PropertyIdentifier::OutOfService => Ok(PropertyValue::Boolean(self.out_of_service)),
+			_ => Err(ErrorCode::UnknownProperty)
+		}
+	}
+
+	fn set_property(&mut self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>, value: &PropertyValue) -> Result<(), ErrorCode> {
+		if let (PropertyIdentifier::RelinquishDefault, PropertyValue::Unsigned(state)) = (property_identifier, value) {
+			self.states.check_state(*state)?;
+		}
+		if let Some(result) = self.commandable.set_property(property_identifier, property_array_index, value) {
+			return result;
+		}
+		if let Some(result) = self.states.set_property(property_identifier, property_array_index, value) {
+			return result;
+		}
+		check_not_array(property_array_index)?;
+		match (property_identifier, value) {
+			(PropertyIdentifier::ObjectName, PropertyValue::CharacterString(object_name)) => self.object_name = object_name.clone(),
+			(PropertyIdentifier::OutOfService, PropertyValue::Boolean(out_of_service)) => self.out_of_service = *out_of_service,
+			(PropertyIdentifier::ObjectName | PropertyIdentifier::OutOfService, _) => return Err(ErrorCode::InvalidDataType),
+			_ => return Err(write_error(self, property_identifier))
+		}
+		Ok(())
+	}
+
+	fn is_commandable(&self, property_identifier: PropertyIdentifier) -> bool {
+		property_identifier == PropertyIdentifier::PresentValue
+	}
+
+	fn command(&mut self, property_identifier: PropertyIdentifier, priority: u8, value: &PropertyValue) -> Result<(), ErrorCode> {
+		if property_identifier != PropertyIdentifier::PresentValue {
+			return Err(ErrorCode::WriteAccessDenied);
+		}
+		if let PropertyValue::Unsigned(state) = value {
+			self.states.check_state(*state)?;
+		}
+		self.commandable.command(priority, value)
+	}
 }
 
 pub struct ExampleDatabaseBitstringValue {
 	pub object_name: String,
 	pub instance: u32,
+	pub out_of_service: bool,
 	pub present_value: Vec<bool>,
-	pub bit_text: Vec<String>
+	// A description of each bit. The number of bits is fixed by the configuration.
+	pub bit_text: Vec<String>,
+	pub proprietary_properties: ProprietaryProperties
+}
+
+impl BacnetObject for ExampleDatabaseBitstringValue {
+	fn object_name(&self) -> &str {
+		&self.object_name
+	}
+
+	fn get_property(&self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>) -> Result<PropertyValue, ErrorCode> {
+		if property_identifier == PropertyIdentifier::BitText && !self.bit_text.is_empty() {
+			return array_property(&self.bit_text, property_array_index, |text| PropertyValue::CharacterString(text.clone()));
+		}
+		check_not_array(property_array_index)?;
+		match property_identifier {
+			PropertyIdentifier::ObjectName => Ok(PropertyValue::CharacterString(self.object_name.clone())),
+			PropertyIdentifier::OutOfService => Ok(PropertyValue::Boolean(self.out_of_service)),
+			PropertyIdentifier::PresentValue => Ok(PropertyValue::BitString(self.present_value.clone())),
+			_ => Err(ErrorCode::UnknownProperty)
+		}
+	}
+
+	fn set_property(&mut self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>, value: &PropertyValue) -> Result<(), ErrorCode> {
+		if property_identifier == PropertyIdentifier::BitText && !self.bit_text.is_empty() {
+			return write_array_property(&mut self.bit_text, property_array_index, value, character_string_value);
+		}
+		check_not_array(property_array_index)?;
+		match (property_identifier, value) {
+			(PropertyIdentifier::ObjectName, PropertyValue::CharacterString(object_name)) => self.object_name = object_name.clone(),
+			(PropertyIdentifier::OutOfService, PropertyValue::Boolean(out_of_service)) => self.out_of_service = *out_of_service,
+			(PropertyIdentifier::PresentValue, PropertyValue::BitString(present_value)) => self.present_value = present_value.clone(),
+			(PropertyIdentifier::ObjectName | PropertyIdentifier::OutOfService | PropertyIdentifier::PresentValue, _) => return Err(ErrorCode::InvalidDataType),
+			_ => return Err(write_error(self, property_identifier))
+		}
+		Ok(())
+	}
 }
 
 pub struct ExampleDatabaseCharacterStringValue {
 	pub object_name: String,
 	pub instance: u32,
-	pub present_value: String		
+	pub out_of_service: bool,
+	pub present_value: String,
+	pub proprietary_properties: ProprietaryProperties
+}
+
+impl BacnetObject for ExampleDatabaseCharacterStringValue {
+	fn object_name(&self) -> &str {
+		&self.object_name
+	}
+
+	fn get_property(&self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>) -> Result<PropertyValue, ErrorCode> {
+		check_not_array(property_array_index)?;
+		match property_identifier {
+			PropertyIdentifier::ObjectName => Ok(PropertyValue::CharacterString(self.object_name.clone())),
+			PropertyIdentifier::OutOfService => Ok(PropertyValue::Boolean(self.out_of_service)),
+			PropertyIdentifier::PresentValue => Ok(PropertyValue::CharacterString(self.present_value.clone())),
+			_ => Err(ErrorCode::UnknownProperty)
+		}
+	}
+
+	fn set_property(&mut self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>, value: &PropertyValue) -> Result<(), ErrorCode> {
+		check_not_array(property_array_index)?;
+		match (property_identifier, value) {
+			(PropertyIdentifier::ObjectName, PropertyValue::CharacterString(object_name)) => self.object_name = object_name.clone(),
+			(PropertyIdentifier::OutOfService, PropertyValue::Boolean(out_of_service)) => self.out_of_service = *out_of_service,
+			(PropertyIdentifier::PresentValue, PropertyValue::CharacterString(present_value)) => self.present_value = present_value.clone(),
+			(PropertyIdentifier::ObjectName | PropertyIdentifier::OutOfService | PropertyIdentifier::PresentValue, _) => return Err(ErrorCode::InvalidDataType),
+			_ => return Err(write_error(self, property_identifier))
+		}
+		Ok(())
+	}
 }
 
 pub struct ExampleDatabaseIntegerValue {
 	pub object_name: String,
 	pub instance: u32,
-	pub present_value: i32
+	pub out_of_service: bool,
+	pub present_value: i32,
+	pub proprietary_properties: ProprietaryProperties
+}
+
+impl BacnetObject for ExampleDatabaseIntegerValue {
+	fn object_name(&self) -> &str {
+		&self.object_name
+	}
+
+	fn get_property(&self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>) -> Result<PropertyValue, ErrorCode> {
+		check_not_array(property_array_index)?;
+		match property_identifier {
+			PropertyIdentifier::ObjectName => Ok(PropertyValue::CharacterString(self.object_name.clone())),
+			PropertyIdentifier::OutOfService => Ok(PropertyValue::Boolean(self.out_of_service)),
+			PropertyIdentifier::PresentValue => Ok(PropertyValue::Signed(self.present_value)),
+			_ => Err(ErrorCode::UnknownProperty)
+		}
+	}
+
+	fn set_property(&mut self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>, value: &PropertyValue) -> Result<(), ErrorCode> {
+		check_not_array(property_array_index)?;
+		match (property_identifier, value) {
+			(PropertyIdentifier::ObjectName, PropertyValue::CharacterString(object_name)) => self.object_name = object_name.clone(),
+			(PropertyIdentifier::OutOfService, PropertyValue::Boolean(out_of_service)) => self.out_of_service = *out_of_service,
+			(PropertyIdentifier::PresentValue, PropertyValue::Signed(present_value)) => self.present_value = *present_value,
+			(PropertyIdentifier::ObjectName | PropertyIdentifier::OutOfService | PropertyIdentifier::PresentValue, _) => return Err(ErrorCode::InvalidDataType),
+			_ => return Err(write_error(self, property_identifier))
+		}
+		Ok(())
+	}
 }
 
 pub struct ExampleDatabaseLargeAnalogValue {
 	pub object_name: String,
 	pub instance: u32,
-	pub present_value: f64
+	pub out_of_service: bool,
+	pub present_value: f64,
+	pub proprietary_properties: ProprietaryProperties
+}
+
+impl BacnetObject for ExampleDatabaseLargeAnalogValue {
+	fn object_name(&self) -> &str {
+		&self.object_name
+	}
+
+	fn get_property(&self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>) -> Result<PropertyValue, ErrorCode> {
+		check_not_array(property_array_index)?;
+		match property_identifier {
+			PropertyIdentifier::ObjectName => Ok(PropertyValue::CharacterString(self.object_name.clone())),
+			PropertyIdentifier::OutOfService => Ok(PropertyValue::Boolean(self.out_of_service)),
+			PropertyIdentifier::PresentValue => Ok(PropertyValue::Double(self.present_value)),
+			_ => Err(ErrorCode::UnknownProperty)
+		}
+	}
+
+	fn set_property(&mut self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>, value: &PropertyValue) -> Result<(), ErrorCode> {
+		check_not_array(property_array_index)?;
+		match (property_identifier, value) {
+			(PropertyIdentifier::ObjectName, PropertyValue::CharacterString(object_name)) => self.object_name = object_name.clone(),
+			(PropertyIdentifier::OutOfService, PropertyValue::Boolean(out_of_service)) => self.out_of_service = *out_of_service,
+			(PropertyIdentifier::PresentValue, PropertyValue::Double(present_value)) => self.present_value = *present_value,
+			(PropertyIdentifier::ObjectName | PropertyIdentifier::OutOfService | PropertyIdentifier::PresentValue, _) => return Err(ErrorCode::InvalidDataType),
+			_ => return Err(write_error(self, property_identifier))
+		}
+		Ok(())
+	}
 }
 
 pub struct ExampleDatabaseOctetStringValue {
 	pub object_name: String,
 	pub instance: u32,
-	pub present_value: Vec<u8>
+	pub out_of_service: bool,
+	pub present_value: Vec<u8>,
+	pub proprietary_properties: ProprietaryProperties
+}
+
+impl BacnetObject for ExampleDatabaseOctetStringValue {
+	fn object_name(&self) -> &str {
+		&self.object_name
+	}
+
+	fn get_property(&self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>) -> Result<PropertyValue, ErrorCode> {
+		check_not_array(property_array_index)?;
+		match property_identifier {
+			PropertyIdentifier::ObjectName => Ok(PropertyValue::CharacterString(self.object_name.clone())),
+			PropertyIdentifier::OutOfService => Ok(PropertyValue::Boolean(self.out_of_service)),
+			PropertyIdentifier::PresentValue => Ok(PropertyValue::OctetString(self.present_value.clone())),
+			_ => Err(ErrorCode::UnknownProperty)
+		}
+	}
+
+	fn set_property(&mut self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>, value: &PropertyValue) -> Result<(), ErrorCode> {
+		check_not_array(property_array_index)?;
+		match (property_identifier, value) {
+			(PropertyIdentifier::ObjectName, PropertyValue::CharacterString(object_name)) => self.object_name = object_name.clone(),
+			(PropertyIdentifier::OutOfService, PropertyValue::Boolean(out_of_service)) => self.out_of_service = *out_of_service,
+			(PropertyIdentifier::PresentValue, PropertyValue::OctetString(present_value)) => self.present_value = present_value.clone(),
+			(PropertyIdentifier::ObjectName | PropertyIdentifier::OutOfService | PropertyIdentifier::PresentValue, _) => return Err(ErrorCode::InvalidDataType),
+			_ => return Err(write_error(self, property_identifier))
+		}
+		Ok(())
+	}
 }
 
 pub struct ExampleDatabasePositiveIntegerValue {
 	pub object_name: String,
 	pub instance: u32,
-	pub present_value: u32
+	pub out_of_service: bool,
+	pub present_value: u32,
+	pub proprietary_properties: ProprietaryProperties
+}
+
+impl BacnetObject for ExampleDatabasePositiveIntegerValue {
+	fn object_name(&self) -> &str {
+		&self.object_name
+	}
+
+	fn get_property(&self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>) -> Result<PropertyValue, ErrorCode> {
+		check_not_array(property_array_index)?;
+		match property_identifier {
+			PropertyIdentifier::ObjectName => Ok(PropertyValue::CharacterString(self.object_name.clone())),
+			PropertyIdentifier::OutOfService => Ok(PropertyValue::Boolean(self.out_of_service)),
+			PropertyIdentifier::PresentValue => Ok(PropertyValue::Unsigned(self.present_value)),
+			_ => Err(ErrorCode::UnknownProperty)
+		}
+	}
+
+	fn set_property(&mut self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>, value: &PropertyValue) -> Result<(), ErrorCode> {
+		check_not_array(property_array_index)?;
+		match (property_identifier, value) {
+			(PropertyIdentifier::ObjectName, PropertyValue::CharacterString(object_name)) => self.object_name = object_name.clone(),
+			(PropertyIdentifier::OutOfService, PropertyValue::Boolean(out_of_service)) => self.out_of_service = *out_of_service,
+			(PropertyIdentifier::PresentValue, PropertyValue::Unsigned(present_value)) => self.present_value = *present_value,
+			(PropertyIdentifier::ObjectName | PropertyIdentifier::OutOfService | PropertyIdentifier::PresentValue, _) => return Err(ErrorCode::InvalidDataType),
+			_ => return Err(write_error(self, property_identifier))
+		}
+		Ok(())
+	}
 }
 
 pub struct ExampleDatabaseNetworkPort {
 	pub object_name: String,
 	pub instance: u32,
+	pub out_of_service: bool,
 	
 	pub bacnet_ip_udp_port: u16,
 	pub ip_address: [u8; 4],
@@ -137,12 +1487,46 @@ pub struct ExampleDatabaseNetworkPort {
 	pub fd_bbmd_address_host_type: u8,
 	pub fd_bbmd_address_host_ip: [u8; 4],
 	pub fd_bbmd_address_port: u16,
-	pub fd_subscription_lifetime: u16
+	pub fd_subscription_lifetime: u16,
+	pub proprietary_properties: ProprietaryProperties
+}
+
+impl BacnetObject for ExampleDatabaseNetworkPort {
+	fn object_name(&self) -> &str {
+		&self.object_name
+	}
+
+	fn get_property(&self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>) -> Result<PropertyValue, ErrorCode> {
+		check_not_array(property_array_index)?;
+		match property_identifier {
+			PropertyIdentifier::ObjectName => Ok(PropertyValue::CharacterString(self.object_name.clone())),
+			PropertyIdentifier::OutOfService => Ok(PropertyValue::Boolean(self.out_of_service)),
+			PropertyIdentifier::BacnetIpUdpPort => Ok(PropertyValue::Unsigned(u32::from(self.bacnet_ip_udp_port))),
+			PropertyIdentifier::IpAddress => Ok(PropertyValue::OctetString(self.ip_address.to_vec())),
+			PropertyIdentifier::IpDefaultGateway => Ok(PropertyValue::OctetString(self.ip_default_gateway.to_vec())),
+			PropertyIdentifier::IpSubnetMask => Ok(PropertyValue::OctetString(self.ip_subnet_mask.to_vec())),
+			PropertyIdentifier::ChangesPending => Ok(PropertyValue::Boolean(self.changes_pending)),
+			PropertyIdentifier::FdSubscriptionLifetime => Ok(PropertyValue::Unsigned(u32::from(self.fd_subscription_lifetime))),
+			_ => Err(ErrorCode::UnknownProperty)
+		}
+	}
+
+	fn set_property(&mut self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>, value: &PropertyValue) -> Result<(), ErrorCode> {
+		check_not_array(property_array_index)?;
+		match (property_identifier, value) {
+			(PropertyIdentifier::ObjectName, PropertyValue::CharacterString(object_name)) => self.object_name = object_name.clone(),
+			(PropertyIdentifier::OutOfService, PropertyValue::Boolean(out_of_service)) => self.out_of_service = *out_of_service,
+			(PropertyIdentifier::ObjectName | PropertyIdentifier::OutOfService, _) => return Err(ErrorCode::InvalidDataType),
+			_ => return Err(write_error(self, property_identifier))
+		}
+		Ok(())
+	}
 }
 
 pub struct ExampleDatabaseDateTimeValue {
 	pub object_name: String,
 	pub instance: u32,
+	pub out_of_service: bool,
 	pub present_value_year: u8,
 	pub present_value_month: u8,
 	pub present_value_day: u8,
@@ -150,204 +1534,1680 @@ pub struct ExampleDatabaseDateTimeValue {
 	pub present_value_hour: u8,
 	pub present_value_minute: u8,
 	pub present_value_second: u8,
-	pub present_value_hundredth_seconds: u8
-}
-
-// Add objects into example database
-pub fn setup_database() -> HashMap::<String, ExampleDatabaseObject> {
-	let mut database = HashMap::<String, ExampleDatabaseObject>::new();
-    database.insert("device-389001".to_string(), ExampleDatabaseObject::Device(setup_device()));
-	database.insert("analog_input-0".to_string(), ExampleDatabaseObject::AnalogInput(setup_analog_input()));
-	database.insert("analog_output-1".to_string(), ExampleDatabaseObject::AnalogOutput(setup_analog_output()));
-	database.insert("analog_value-2".to_string(), ExampleDatabaseObject::AnalogValue(setup_analog_value()));
-	database.insert("binary_input-3".to_string(), ExampleDatabaseObject::BinaryInput(setup_binary_input()));
-	database.insert("multistate_input-13".to_string(), ExampleDatabaseObject::MultiStateInput(setup_multistate_input()));
-	database.insert("bitstring_value-39".to_string(), ExampleDatabaseObject::BitstringValue(setup_bitstring_value()));
-	database.insert("character_string_value-40".to_string(), ExampleDatabaseObject::CharacterStringValue(setup_character_string_value()));
-	database.insert("integer_value-45".to_string(), ExampleDatabaseObject::IntegerValue(setup_integer_value()));
-	database.insert("large_analog_value-46".to_string(), ExampleDatabaseObject::LargeAnalogValue(setup_large_analog_value()));
-	database.insert("octet_string_value-47".to_string(), ExampleDatabaseObject::OctetStringValue(setup_octet_string_value()));
-	database.insert("positive_integer_value-48".to_string(), ExampleDatabaseObject::PositiveIntegerValue(setup_positive_integer_value()));
-	database.insert("network_port-56".to_string(), ExampleDatabaseObject::NetworkPort(setup_network_port()));
-	database.insert("date_time_value-44".to_string(), ExampleDatabaseObject::DateTimeValue(setup_date_time_value()));
-    database
-}
-
-
-
-// Set up object with example properties
-
-fn setup_device() -> ExampleDatabaseDevice {
-	let mut device = ExampleDatabaseDevice {	
-		object_name: "Red King Device".to_string(),
-		instance: 389001,
-		utc_offset: 0,
-		current_time_offset: 0,
-		description: "CAS BACnet Rust Server Device".to_string(),
-		system_status: 0
+	pub present_value_hundredth_seconds: u8,
+	pub proprietary_properties: ProprietaryProperties
+}
+
+impl ExampleDatabaseDateTimeValue {
+	fn date(&self) -> BacnetDate {
+		BacnetDate { year: self.present_value_year, month: self.present_value_month, day: self.present_value_day, weekday: self.present_value_weekday }
+	}
+
+	fn time(&self) -> BacnetTime {
+		BacnetTime { hour: self.present_value_hour, minute: self.present_value_minute, second: self.present_value_second, hundredth_seconds: self.present_value_hundredth_seconds }
+	}
+
+	fn set_date(&mut self, date: &BacnetDate) {
+		self.present_value_year = date.year;
+		self.present_value_month = date.month;
+		self.present_value_day = date.day;
+		self.present_value_weekday = date.weekday;
+	}
+
+	fn set_time(&mut self, time: &BacnetTime) {
+		self.present_value_hour = time.hour;
+		self.present_value_minute = time.minute;
+		self.present_value_second = time.second;
+		self.present_value_hundredth_seconds = time.hundredth_seconds;
+	}
+}
+
+impl BacnetObject for ExampleDatabaseDateTimeValue {
+	fn object_name(&self) -> &str {
+		&self.object_name
+	}
+
+	fn get_property(&self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>) -> Result<PropertyValue, ErrorCode> {
+		check_not_array(property_array_index)?;
+		match property_identifier {
+			PropertyIdentifier::ObjectName => Ok(PropertyValue::CharacterString(self.object_name.clone())),
+			PropertyIdentifier::OutOfService => Ok(PropertyValue::Boolean(self.out_of_service)),
+			PropertyIdentifier::PresentValue => Ok(PropertyValue::DateTime(self.date(), self.time())),
+			_ => Err(ErrorCode::UnknownProperty)
+		}
+	}
+
+	// The stack writes a date and time present value as a date followed by a time
+	fn set_property(&mut self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>, value: &PropertyValue) -> Result<(), ErrorCode> {
+		check_not_array(property_array_index)?;
+		match (property_identifier, value) {
+			(PropertyIdentifier::ObjectName, PropertyValue::CharacterString(object_name)) => self.object_name = object_name.clone(),
+			(PropertyIdentifier::OutOfService, PropertyValue::Boolean(out_of_service)) => self.out_of_service = *out_of_service,
+			(PropertyIdentifier::PresentValue, PropertyValue::Date(date) | PropertyValue::DateTime(date, _)) if !date.is_valid() => return Err(ErrorCode::ValueOutOfRange),
+			(PropertyIdentifier::PresentValue, PropertyValue::Time(time) | PropertyValue::DateTime(_, time)) if !time.is_valid() => return Err(ErrorCode::ValueOutOfRange),
+			(PropertyIdentifier::PresentValue, PropertyValue::Date(date)) => self.set_date(date),
+			(PropertyIdentifier::PresentValue, PropertyValue::Time(time)) => self.set_time(time),
+			(PropertyIdentifier::PresentValue, PropertyValue::DateTime(date, time)) => {
+				self.set_date(date);
+				self.set_time(time);
+			},
+			(PropertyIdentifier::ObjectName | PropertyIdentifier::OutOfService | PropertyIdentifier::PresentValue, _) => return Err(ErrorCode::InvalidDataType),
+			_ => return Err(write_error(self, property_identifier))
+		}
+		Ok(())
+	}
+}
+
+pub struct ExampleDatabaseDateValue {
+	pub object_name: String,
+	pub instance: u32,
+	pub out_of_service: bool,
+	pub present_value: BacnetDate,
+	pub proprietary_properties: ProprietaryProperties
+}
+
+impl BacnetObject for ExampleDatabaseDateValue {
+	fn object_name(&self) -> &str {
+		&self.object_name
+	}
+
+	fn get_property(&self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>) -> Result<PropertyValue, ErrorCode> {
+		check_not_array(property_array_index)?;
+		match property_identifier {
+			PropertyIdentifier::ObjectName => Ok(PropertyValue::CharacterString(self.object_name.clone())),
+			PropertyIdentifier::OutOfService => Ok(PropertyValue::Boolean(self.out_of_service)),
+			PropertyIdentifier::PresentValue => Ok(PropertyValue::Date(self.present_value)),
+			_ => Err(ErrorCode::UnknownProperty)
+		}
+	}
+
+	fn set_property(&mut self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>, value: &PropertyValue) -> Result<(), ErrorCode> {
+		check_not_array(property_array_index)?;
+		match (property_identifier, value) {
+			(PropertyIdentifier::ObjectName, PropertyValue::CharacterString(object_name)) => self.object_name = object_name.clone(),
+			(PropertyIdentifier::OutOfService, PropertyValue::Boolean(out_of_service)) => self.out_of_service = *out_of_service,
+			(PropertyIdentifier::PresentValue, PropertyValue::Date(present_value)) if !present_value.is_valid() => return Err(ErrorCode::ValueOutOfRange),
+			(PropertyIdentifier::PresentValue, PropertyValue::Date(present_value)) => self.present_value = *present_value,
+			(PropertyIdentifier::ObjectName | PropertyIdentifier::OutOfService | PropertyIdentifier::PresentValue, _) => return Err(ErrorCode::InvalidDataType),
+			_ => return Err(write_error(self, property_identifier))
+		}
+		Ok(())
+	}
+}
+
+pub struct ExampleDatabaseTimeValue {
+	pub object_name: String,
+	pub instance: u32,
+	pub out_of_service: bool,
+	pub present_value: BacnetTime,
+	pub proprietary_properties: ProprietaryProperties
+}
+
+impl BacnetObject for ExampleDatabaseTimeValue {
+	fn object_name(&self) -> &str {
+		&self.object_name
+	}
+
+	fn get_property(&self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>) -> Result<PropertyValue, ErrorCode> {
+		check_not_array(property_array_index)?;
+		match property_identifier {
+			PropertyIdentifier::ObjectName => Ok(PropertyValue::CharacterString(self.object_name.clone())),
+			PropertyIdentifier::OutOfService => Ok(PropertyValue::Boolean(self.out_of_service)),
+			PropertyIdentifier::PresentValue => Ok(PropertyValue::Time(self.present_value)),
+			_ => Err(ErrorCode::UnknownProperty)
+		}
+	}
+
+	fn set_property(&mut self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>, value: &PropertyValue) -> Result<(), ErrorCode> {
+		check_not_array(property_array_index)?;
+		match (property_identifier, value) {
+			(PropertyIdentifier::ObjectName, PropertyValue::CharacterString(object_name)) => self.object_name = object_name.clone(),
+			(PropertyIdentifier::OutOfService, PropertyValue::Boolean(out_of_service)) => self.out_of_service = *out_of_service,
+			(PropertyIdentifier::PresentValue, PropertyValue::Time(present_value)) if !present_value.is_valid() => return Err(ErrorCode::ValueOutOfRange),
+			(PropertyIdentifier::PresentValue, PropertyValue::Time(present_value)) => self.present_value = *present_value,
+			(PropertyIdentifier::ObjectName | PropertyIdentifier::OutOfService | PropertyIdentifier::PresentValue, _) => return Err(ErrorCode::InvalidDataType),
+			_ => return Err(write_error(self, property_identifier))
+		}
+		Ok(())
+	}
+}
+
+// A property of an object on the same device, recorded by a trend log, or written or read by a schedule
+// or loop, e.g.
+// { type = "analog-input", instance = 0, property = "present-value" }
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+pub struct DeviceObjectPropertyReference {
+	#[serde(rename = "type")]
+	pub object_type: ObjectType,
+	pub instance: u32,
+	pub property: PropertyIdentifier,
+	#[serde(default)]
+	pub array_index: Option<u32>
+}
+
+pub struct ExampleDatabaseTrendLog {
+	pub object_name: String,
+	pub instance: u32,
+	pub log_device_object_property: DeviceObjectPropertyReference,
+	pub logging_type: LoggingType,
+	pub log_enable: bool,
+	// Hundredths of a second between polled samples
+	pub log_interval: u32,
+	// Logging is limited to the start and stop times when they are fully specified
+	pub start_time: (BacnetDate, BacnetTime),
+	pub stop_time: (BacnetDate, BacnetTime),
+	pub stop_when_full: bool,
+	// Smallest change of a real value logged on COV, any change is logged when not set
+	pub client_cov_increment: Option<f32>,
+	pub log_buffer: LogBuffer,
+	pub last_sample_time: Option<u64>,
+	pub last_logged_value: Option<PropertyValue>,
+	pub proprietary_properties: ProprietaryProperties
+}
+
+impl ExampleDatabaseTrendLog {
+	// Whether the log is enabled, and now (in milliseconds since the Unix epoch) is between its start and stop times
+	fn is_logging(&self, now: u64) -> bool {
+		self.log_enable
+			&& date_time_to_millis(&self.start_time.0, &self.start_time.1).is_none_or(|start| now >= start)
+			&& date_time_to_millis(&self.stop_time.0, &self.stop_time.1).is_none_or(|stop| now < stop)
+	}
+
+	fn is_changed(&self, value: &PropertyValue) -> bool {
+		match (&self.last_logged_value, value, self.client_cov_increment) {
+			(Some(PropertyValue::Real(last)), PropertyValue::Real(value), Some(client_cov_increment)) => (value - last).abs() >= client_cov_increment,
+			(Some(last), value, _) => last != value,
+			(None, _, _) => true
+		}
+	}
+
+	// Records the monitored value when a polled sample is due, or when it has changed for COV logging.
+	// None when the monitored property could not be read.
+	pub fn update(&mut self, now: u64, value: Option<PropertyValue>) {
+		let Some(value) = value else {
+			return;
+		};
+		if !self.is_logging(now) {
+			return;
+		}
+		let due = match self.logging_type {
+			LoggingType::Polled => self.last_sample_time.is_none_or(|last| now.saturating_sub(last) >= u64::from(self.log_interval) * 10),
+			LoggingType::Cov => self.is_changed(&value),
+			LoggingType::Triggered => false
+		};
+		if !due {
+			return;
+		}
+		if self.stop_when_full && self.log_buffer.is_full() {
+			self.log_enable = false;
+			return;
+		}
+		self.log_buffer.push(now, value.clone());
+		self.last_sample_time = Some(now);
+		self.last_logged_value = Some(value);
+	}
+
+	pub fn read_range(&self, range: ReadRange) -> Result<ReadRangeResult, ErrorCode> {
+		self.log_buffer.read_range(range)
+	}
+}
+
+impl BacnetObject for ExampleDatabaseTrendLog {
+	fn object_name(&self) -> &str {
+		&self.object_name
+	}
+
+	fn get_property(&self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>) -> Result<PropertyValue, ErrorCode> {
+		check_not_array(property_array_index)?;
+		match property_identifier {
+			PropertyIdentifier::ObjectName => Ok(PropertyValue::CharacterString(self.object_name.clone())),
+			PropertyIdentifier::LoggingType => Ok(PropertyValue::Enumerated(self.logging_type.into())),
+			PropertyIdentifier::Enable => Ok(PropertyValue::Boolean(self.log_enable)),
+			PropertyIdentifier::LogInterval => Ok(PropertyValue::Unsigned(self.log_interval)),
+			PropertyIdentifier::StartTime => Ok(PropertyValue::DateTime(self.start_time.0, self.start_time.1)),
+			PropertyIdentifier::StopTime => Ok(PropertyValue::DateTime(self.stop_time.0, self.stop_time.1)),
+			PropertyIdentifier::StopWhenFull => Ok(PropertyValue::Boolean(self.stop_when_full)),
+			PropertyIdentifier::ClientCovIncrement => self.client_cov_increment.map(PropertyValue::Real).ok_or(ErrorCode::UnknownProperty),
+			PropertyIdentifier::BufferSize => Ok(PropertyValue::Unsigned(self.log_buffer.buffer_size())),
+			PropertyIdentifier::RecordCount => Ok(PropertyValue::Unsigned(self.log_buffer.record_count())),
+			PropertyIdentifier::TotalRecordCount => Ok(PropertyValue::Unsigned(self.log_buffer.total_record_count())),
+			// The log buffer is only read with ReadRange
+			PropertyIdentifier::LogBuffer => Err(ErrorCode::ReadAccessDenied),
+			_ => Err(ErrorCode::UnknownProperty)
+		}
+	}
+
+	// Start and stop times are written by the stack as a date followed by a time
+	fn set_property(&mut self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>, value: &PropertyValue) -> Result<(), ErrorCode> {
+		check_not_array(property_array_index)?;
+		match (property_identifier, value) {
+			(PropertyIdentifier::ObjectName, PropertyValue::CharacterString(object_name)) => self.object_name = object_name.clone(),
+			(PropertyIdentifier::Enable, PropertyValue::Boolean(log_enable)) => self.log_enable = *log_enable,
+			(PropertyIdentifier::LogInterval, PropertyValue::Unsigned(0)) if self.logging_type == LoggingType::Polled => return Err(ErrorCode::ValueOutOfRange),
+			(PropertyIdentifier::LogInterval, PropertyValue::Unsigned(log_interval)) => self.log_interval = *log_interval,
+			(PropertyIdentifier::StartTime | PropertyIdentifier::StopTime, PropertyValue::Date(date) | PropertyValue::DateTime(date, _)) if !date.is_valid() => return Err(ErrorCode::ValueOutOfRange),
+			(PropertyIdentifier::StartTime | PropertyIdentifier::StopTime, PropertyValue::Time(time) | PropertyValue::DateTime(_, time)) if !time.is_valid() => return Err(ErrorCode::ValueOutOfRange),
+			(PropertyIdentifier::StartTime, PropertyValue::Date(date)) => self.start_time.0 = *date,
+			(PropertyIdentifier::StartTime, PropertyValue::Time(time)) => self.start_time.1 = *time,
+			(PropertyIdentifier::StartTime, PropertyValue::DateTime(date, time)) => self.start_time = (*date, *time),
+			(PropertyIdentifier::StopTime, PropertyValue::Date(date)) => self.stop_time.0 = *date,
+			(PropertyIdentifier::StopTime, PropertyValue::Time(time)) => self.stop_time.1 = *time,
+			(PropertyIdentifier::StopTime, PropertyValue::DateTime(date, time)) => self.stop_time = (*date, *time),
+			(PropertyIdentifier::StopWhenFull, PropertyValue::Boolean(stop_when_full)) => self.stop_when_full = *stop_when_full,
+			(PropertyIdentifier::BufferSize, PropertyValue::Unsigned(buffer_size)) if !(1..=bacnet_const::MAX_TREND_LOG_MAX_BUFFER_SIZE).contains(buffer_size) => return Err(ErrorCode::ValueOutOfRange),
+			(PropertyIdentifier::BufferSize, PropertyValue::Unsigned(buffer_size)) => self.log_buffer.set_buffer_size(*buffer_size),
+			// Writing zero deletes every record
+			(PropertyIdentifier::RecordCount, PropertyValue::Unsigned(0)) => self.log_buffer.clear(),
+			(PropertyIdentifier::RecordCount, PropertyValue::Unsigned(_)) => return Err(ErrorCode::ValueOutOfRange),
+			(PropertyIdentifier::ObjectName | PropertyIdentifier::Enable | PropertyIdentifier::LogInterval | PropertyIdentifier::StartTime | PropertyIdentifier::StopTime | PropertyIdentifier::StopWhenFull | PropertyIdentifier::BufferSize | PropertyIdentifier::RecordCount, _) => return Err(ErrorCode::InvalidDataType),
+			_ => return Err(write_error(self, property_identifier))
+		}
+		Ok(())
+	}
+}
+
+// Priorities and acknowledgement of the event notifications from the objects that report to it. Both
+// are given to the stack when the object is added, so they are fixed by the configuration.
+pub struct ExampleDatabaseNotificationClass {
+	pub object_name: String,
+	pub instance: u32,
+	// To-offnormal, to-fault and to-normal, 0 is the highest priority
+	pub priority: [u8; 3],
+	pub ack_required: EventTransitions,
+	pub proprietary_properties: ProprietaryProperties
+}
+
+impl BacnetObject for ExampleDatabaseNotificationClass {
+	fn object_name(&self) -> &str {
+		&self.object_name
+	}
+
+	fn get_property(&self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>) -> Result<PropertyValue, ErrorCode> {
+		if property_identifier == PropertyIdentifier::Priority {
+			return array_property(&self.priority, property_array_index, |priority| PropertyValue::Unsigned(u32::from(*priority)));
+		}
+		check_not_array(property_array_index)?;
+		match property_identifier {
+			PropertyIdentifier::ObjectName => Ok(PropertyValue::CharacterString(self.object_name.clone())),
+			PropertyIdentifier::NotificationClass => Ok(PropertyValue::Unsigned(self.instance)),
+			PropertyIdentifier::AckRequired => Ok(PropertyValue::BitString(self.ack_required.to_bits())),
+			_ => Err(ErrorCode::UnknownProperty)
+		}
+	}
+
+	fn set_property(&mut self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>, value: &PropertyValue) -> Result<(), ErrorCode> {
+		check_not_array(property_array_index)?;
+		match (property_identifier, value) {
+			(PropertyIdentifier::ObjectName, PropertyValue::CharacterString(object_name)) => self.object_name = object_name.clone(),
+			(PropertyIdentifier::ObjectName, _) => return Err(ErrorCode::InvalidDataType),
+			_ => return Err(write_error(self, property_identifier))
+		}
+		Ok(())
+	}
+}
+
+pub struct ExampleDatabaseSchedule {
+	pub object_name: String,
+	pub instance: u32,
+	pub out_of_service: bool,
+	pub present_value: PropertyValue,
+	pub timetable: Timetable,
+	// The properties the present value is written to, on the schedule's own device
+	pub list_of_object_property_references: Vec<DeviceObjectPropertyReference>,
+	pub priority_for_writing: u8,
+	// Whether the present value has been evaluated and written since startup
+	pub evaluated: bool,
+	pub proprietary_properties: ProprietaryProperties
+}
+
+impl BacnetObject for ExampleDatabaseSchedule {
+	fn object_name(&self) -> &str {
+		&self.object_name
+	}
+
+	// The weekly and exception schedules, effective period and references are constructed values
+	// the callbacks cannot carry, they are only set by the configuration
+	fn get_property(&self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>) -> Result<PropertyValue, ErrorCode> {
+		check_not_array(property_array_index)?;
+		match property_identifier {
+			PropertyIdentifier::ObjectName => Ok(PropertyValue::CharacterString(self.object_name.clone())),
+			PropertyIdentifier::OutOfService => Ok(PropertyValue::Boolean(self.out_of_service)),
+			PropertyIdentifier::PresentValue => Ok(self.present_value.clone()),
+			PropertyIdentifier::ScheduleDefault => Ok(self.timetable.schedule_default.clone()),
+			PropertyIdentifier::PriorityForWriting => Ok(PropertyValue::Unsigned(u32::from(self.priority_for_writing))),
+			_ => Err(ErrorCode::UnknownProperty)
+		}
+	}
+
+	fn set_property(&mut self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>, value: &PropertyValue) -> Result<(), ErrorCode> {
+		check_not_array(property_array_index)?;
+		match (property_identifier, value) {
+			(PropertyIdentifier::ObjectName, PropertyValue::CharacterString(object_name)) => self.object_name = object_name.clone(),
+			(PropertyIdentifier::OutOfService, PropertyValue::Boolean(out_of_service)) => self.out_of_service = *out_of_service,
+			// The present value is evaluated from the timetable while the schedule is in service
+			(PropertyIdentifier::PresentValue, _) if !self.out_of_service => return Err(ErrorCode::WriteAccessDenied),
+			(PropertyIdentifier::PresentValue, value) => {
+				self.timetable.check_value_type(value)?;
+				self.present_value = value.clone();
+			},
+			(PropertyIdentifier::ScheduleDefault, value) => {
+				self.timetable.check_value_type(value)?;
+				self.timetable.schedule_default = value.clone();
+			},
+			(PropertyIdentifier::PriorityForWriting, PropertyValue::Unsigned(priority @ 1..=16)) => self.priority_for_writing = *priority as u8,
+			(PropertyIdentifier::PriorityForWriting, PropertyValue::Unsigned(_)) => return Err(ErrorCode::ValueOutOfRange),
+			(PropertyIdentifier::ObjectName | PropertyIdentifier::OutOfService | PropertyIdentifier::PriorityForWriting, _) => return Err(ErrorCode::InvalidDataType),
+			_ => return Err(write_error(self, property_identifier))
+		}
+		Ok(())
+	}
+}
+
+pub struct ExampleDatabaseCalendar {
+	pub object_name: String,
+	pub instance: u32,
+	// Whether the date list includes the current date
+	pub present_value: bool,
+	pub date_list: Vec<CalendarEntry>,
+	pub proprietary_properties: ProprietaryProperties
+}
+
+impl ExampleDatabaseCalendar {
+	pub fn lists(&self, date: &BacnetDate) -> bool {
+		self.date_list.iter().any(|entry| entry.matches(date))
+	}
+}
+
+impl BacnetObject for ExampleDatabaseCalendar {
+	fn object_name(&self) -> &str {
+		&self.object_name
+	}
+
+	// The date list is a list of constructed values the callbacks cannot carry, it is only set by the configuration
+	fn get_property(&self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>) -> Result<PropertyValue, ErrorCode> {
+		check_not_array(property_array_index)?;
+		match property_identifier {
+			PropertyIdentifier::ObjectName => Ok(PropertyValue::CharacterString(self.object_name.clone())),
+			PropertyIdentifier::PresentValue => Ok(PropertyValue::Boolean(self.present_value)),
+			_ => Err(ErrorCode::UnknownProperty)
+		}
+	}
+
+	fn set_property(&mut self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>, value: &PropertyValue) -> Result<(), ErrorCode> {
+		check_not_array(property_array_index)?;
+		match (property_identifier, value) {
+			(PropertyIdentifier::ObjectName, PropertyValue::CharacterString(object_name)) => self.object_name = object_name.clone(),
+			(PropertyIdentifier::ObjectName, _) => return Err(ErrorCode::InvalidDataType),
+			_ => return Err(write_error(self, property_identifier))
+		}
+		Ok(())
+	}
+}
+
+pub struct ExampleDatabaseLoop {
+	pub object_name: String,
+	pub instance: u32,
+	pub out_of_service: bool,
+	// The output of the PID control
+	pub present_value: f32,
+	pub output_units: EngineeringUnits,
+	// Unreliable while the controlled variable or the setpoint cannot be read as a number
+	pub reliability: Reliability,
+	// Milliseconds between updates of the output
+	pub update_interval: u32,
+	// The property the output is written to
+	pub manipulated_variable_reference: DeviceObjectPropertyReference,
+	// The property the loop controls, read on every update
+	pub controlled_variable_reference: DeviceObjectPropertyReference,
+	pub controlled_variable_value: f32,
+	pub controlled_variable_units: EngineeringUnits,
+	// The setpoint is read from this property when given, and written by clients otherwise
+	pub setpoint_reference: Option<DeviceObjectPropertyReference>,
+	pub setpoint: f32,
+	pub priority_for_writing: u8,
+	pub pid: Pid,
+	// When the output was last updated, in milliseconds since the Unix epoch. None until the first
+	// update after startup or after the loop is back in service.
+	pub last_update: Option<u64>,
+	pub proprietary_properties: ProprietaryProperties
+}
+
+impl BacnetObject for ExampleDatabaseLoop {
+	fn object_name(&self) -> &str {
+		&self.object_name
+	}
+
+	// The references are constructed values the callbacks cannot carry, they are only set by the configuration
+	fn get_property(&self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>) -> Result<PropertyValue, ErrorCode> {
+		if let Some(value) = self.pid.get_property(property_identifier, property_array_index) {
+			return value;
+		}
+		check_not_array(property_array_index)?;
+		match property_identifier {
+			PropertyIdentifier::ObjectName => Ok(PropertyValue::CharacterString(self.object_name.clone())),
+			PropertyIdentifier::OutOfService => Ok(PropertyValue::Boolean(self.out_of_service)),
+			PropertyIdentifier::PresentValue => Ok(PropertyValue::Real(self.present_value)),
+			PropertyIdentifier::OutputUnits => Ok(PropertyValue::Enumerated(self.output_units.into())),
+			PropertyIdentifier::Reliability => Ok(PropertyValue::Enumerated(self.reliability.into())),
+			PropertyIdentifier::UpdateInterval => Ok(PropertyValue::Unsigned(self.update_interval)),
+			PropertyIdentifier::ControlledVariableValue => Ok(PropertyValue::Real(self.controlled_variable_value)),
+			PropertyIdentifier::ControlledVariableUnits => Ok(PropertyValue::Enumerated(self.controlled_variable_units.into())),
+			PropertyIdentifier::Setpoint => Ok(PropertyValue::Real(self.setpoint)),
+			PropertyIdentifier::PriorityForWriting => Ok(PropertyValue::Unsigned(u32::from(self.priority_for_writing))),
+			_ => Err(ErrorCode::UnknownProperty)
+		}
+	}
+
+	fn set_property(&mut self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>, value: &PropertyValue) -> Result<(), ErrorCode> {
+		if let Some(result) = self.pid.set_property(property_identifier, property_array_index, value) {
+			return result;
+		}
+		check_not_array(property_array_index)?;
+		match (property_identifier, value) {
+			(PropertyIdentifier::ObjectName, PropertyValue::CharacterString(object_name)) => self.object_name = object_name.clone(),
+			(PropertyIdentifier::OutOfService, PropertyValue::Boolean(out_of_service)) => {
+				// The control starts over when the loop is back in service
+				if self.out_of_service && !*out_of_service {
+					self.pid.reset();
+					self.last_update = None;
+				}
+				self.out_of_service = *out_of_service;
+			},
+			// The present value is the output of the control while the loop is in service
+			(PropertyIdentifier::PresentValue, _) if !self.out_of_service => return Err(ErrorCode::WriteAccessDenied),
+			(PropertyIdentifier::PresentValue, PropertyValue::Real(present_value)) => self.present_value = *present_value,
+			(PropertyIdentifier::OutputUnits | PropertyIdentifier::ControlledVariableUnits, PropertyValue::Enumerated(units)) => {
+				let units = EngineeringUnits::try_from(*units).map_err(|_| ErrorCode::ValueOutOfRange)?;
+				match property_identifier {
+					PropertyIdentifier::OutputUnits => self.output_units = units,
+					_ => self.controlled_variable_units = units
+				}
+			},
+			(PropertyIdentifier::UpdateInterval, PropertyValue::Unsigned(update_interval)) => self.update_interval = *update_interval,
+			(PropertyIdentifier::Setpoint, _) if self.setpoint_reference.is_some() => return Err(ErrorCode::WriteAccessDenied),
+			(PropertyIdentifier::Setpoint, PropertyValue::Real(setpoint)) if !setpoint.is_finite() => return Err(ErrorCode::ValueOutOfRange),
+			(PropertyIdentifier::Setpoint, PropertyValue::Real(setpoint)) => self.setpoint = *setpoint,
+			(PropertyIdentifier::PriorityForWriting, PropertyValue::Unsigned(priority @ 1..=16)) => self.priority_for_writing = *priority as u8,
+			(PropertyIdentifier::PriorityForWriting, PropertyValue::Unsigned(_)) => return Err(ErrorCode::ValueOutOfRange),
+			(PropertyIdentifier::ObjectName | PropertyIdentifier::OutOfService | PropertyIdentifier::PresentValue | PropertyIdentifier::OutputUnits | PropertyIdentifier::ControlledVariableUnits | PropertyIdentifier::UpdateInterval | PropertyIdentifier::Setpoint | PropertyIdentifier::PriorityForWriting, _) => return Err(ErrorCode::InvalidDataType),
+			_ => return Err(write_error(self, property_identifier))
+		}
+		Ok(())
+	}
+}
+
+// Build the example database from the configured device and objects
+pub fn setup_database(config: &DatabaseConfig) -> Result<Database, ConfigError> {
+	let mut database = Database::new();
+	database.insert(ObjectKey::device(config.device.instance), ExampleDatabaseObject::Device(setup_device(&config.device)?));
+	for object in &config.objects {
+		let mut values = object.values();
+		let database_object = match object.object_type {
+			ObjectType::AnalogInput => ExampleDatabaseObject::AnalogInput(setup_analog_input(object, &mut values)?),
+			ObjectType::AnalogOutput => ExampleDatabaseObject::AnalogOutput(setup_analog_output(object, &mut values)?),
+			ObjectType::AnalogValue => ExampleDatabaseObject::AnalogValue(setup_analog_value(object, &mut values)?),
+			ObjectType::BinaryInput => ExampleDatabaseObject::BinaryInput(setup_binary_input(object, &mut values)?),
+			ObjectType::BinaryOutput => ExampleDatabaseObject::BinaryOutput(setup_binary_output(object, &mut values)?),
+			ObjectType::BinaryValue => ExampleDatabaseObject::BinaryValue(setup_binary_value(object, &mut values)?),
+			ObjectType::MultiStateInput => ExampleDatabaseObject::MultiStateInput(setup_multistate_input(object, &mut values)?),
+			ObjectType::MultiStateOutput => ExampleDatabaseObject::MultiStateOutput(setup_multistate_output(object, &mut values)?),
+			ObjectType::MultiStateValue => ExampleDatabaseObject::MultiStateValue(setup_multistate_value(object, &mut values)?),
+			ObjectType::BitstringValue => ExampleDatabaseObject::BitstringValue(setup_bitstring_value(object, &mut values)?),
+			ObjectType::CharacterstringValue => ExampleDatabaseObject::CharacterStringValue(setup_character_string_value(object, &mut values)?),
+			ObjectType::IntegerValue => ExampleDatabaseObject::IntegerValue(setup_integer_value(object, &mut values)?),
+			ObjectType::LargeAnalogValue => ExampleDatabaseObject::LargeAnalogValue(setup_large_analog_value(object, &mut values)?),
+			ObjectType::OctetstringValue => ExampleDatabaseObject::OctetStringValue(setup_octet_string_value(object, &mut values)?),
+			ObjectType::PositiveIntegerValue => ExampleDatabaseObject::PositiveIntegerValue(setup_positive_integer_value(object, &mut values)?),
+			ObjectType::NetworkPort => ExampleDatabaseObject::NetworkPort(setup_network_port(object, &mut values)?),
+			ObjectType::DateValue => ExampleDatabaseObject::DateValue(setup_date_value(object, &mut values)?),
+			ObjectType::DatetimeValue => ExampleDatabaseObject::DateTimeValue(setup_date_time_value(object, &mut values)?),
+			ObjectType::TimeValue => ExampleDatabaseObject::TimeValue(setup_time_value(object, &mut values)?),
+			ObjectType::TrendLog => ExampleDatabaseObject::TrendLog(setup_trend_log(object, &mut values)?),
+			ObjectType::NotificationClass => ExampleDatabaseObject::NotificationClass(setup_notification_class(object, &mut values)?),
+			ObjectType::Schedule => ExampleDatabaseObject::Schedule(setup_schedule(object, &mut values)?),
+			ObjectType::Calendar => ExampleDatabaseObject::Calendar(setup_calendar(object, &mut values)?),
+			ObjectType::Loop => ExampleDatabaseObject::Loop(setup_loop(object, &mut values)?),
+			object_type => return Err(ConfigError::Invalid(format!("{object_type} objects are not supported by the example database")))
+		};
+		values.finish()?;
+		if database.insert(ObjectKey::new(config.device.instance, object.object_type, object.instance), database_object).is_some() {
+			return Err(ConfigError::Invalid(format!("{} {} is configured more than once", object.object_type, object.instance)));
+		}
+	}
+	for (key, object) in &database {
+		if let ExampleDatabaseObject::TrendLog(trend_log) = object {
+			let reference = trend_log.log_device_object_property;
+			if !database.contains_key(&ObjectKey::new(key.device_instance, reference.object_type, reference.instance)) {
+				return Err(ConfigError::Invalid(format!("{} {} logs {} {}, which is not configured", key.object_type, key.object_instance, reference.object_type, reference.instance)));
+			}
+		}
+		if let Some(intrinsic_reporting) = object.intrinsic_reporting() {
+			if !database.contains_key(&ObjectKey::new(key.device_instance, ObjectType::NotificationClass, intrinsic_reporting.notification_class)) {
+				return Err(ConfigError::Invalid(format!("{} {} reports to notification-class {}, which is not configured", key.object_type, key.object_instance, intrinsic_reporting.notification_class)));
+			}
+		}
+		if let ExampleDatabaseObject::Schedule(schedule) = object {
+			for reference in &schedule.list_of_object_property_references {
+				if !database.contains_key(&ObjectKey::new(key.device_instance, reference.object_type, reference.instance)) {
+					return Err(ConfigError::Invalid(format!("{} {} writes to {} {}, which is not configured", key.object_type, key.object_instance, reference.object_type, reference.instance)));
+				}
+			}
+			for special_event in &schedule.timetable.exception_schedule {
+				if let SpecialEventPeriod::CalendarReference(instance) = special_event.period {
+					if !matches!(database.get(&ObjectKey::new(key.device_instance, ObjectType::Calendar, instance)), Some(ExampleDatabaseObject::Calendar(_))) {
+						return Err(ConfigError::Invalid(format!("{} {} refers to calendar {instance}, which is not configured", key.object_type, key.object_instance)));
+					}
+				}
+			}
+		}
+		if let ExampleDatabaseObject::Loop(control_loop) = object {
+			let references = [Some(control_loop.manipulated_variable_reference), Some(control_loop.controlled_variable_reference), control_loop.setpoint_reference];
+			for reference in references.into_iter().flatten() {
+				if !database.contains_key(&ObjectKey::new(key.device_instance, reference.object_type, reference.instance)) {
+					return Err(ConfigError::Invalid(format!("{} {} refers to {} {}, which is not configured", key.object_type, key.object_instance, reference.object_type, reference.instance)));
+				}
+			}
+		}
+	}
+	let device = (ObjectType::Device, config.device.instance, &config.device.character_sets);
+	for (object_type, object_instance, character_sets) in std::iter::once(device).chain(config.objects.iter().map(|object| (object.object_type, object.instance, &object.character_sets))) {
+		let object = &database[&ObjectKey::new(config.device.instance, object_type, object_instance)];
+		for (&property_identifier, &character_set) in character_sets {
+			check_character_set(object, property_identifier, character_set)
+				.map_err(|message| ConfigError::Invalid(format!("{object_type} {object_instance} {property_identifier} {message}")))?;
+		}
+	}
+	Ok(database)
+}
+
+// A string property, or array of them, read in a character set must only hold characters it has
+fn check_character_set(object: &ExampleDatabaseObject, property_identifier: PropertyIdentifier, character_set: CharacterSet) -> Result<(), String> {
+	if !character_set.is_supported() {
+		return Err(format!("cannot be read in {character_set}, only utf-8, iso-8859-1 and iso-10646-ucs-2 are supported"));
+	}
+	let value = object.get_property(property_identifier, None).map_err(|_| "is not a character string".to_string())?;
+	let elements = match &value {
+		PropertyValue::Array(elements) => elements.iter().collect(),
+		value => vec![value]
 	};
-	device
-}
-
-fn setup_analog_input() -> ExampleDatabaseAnalogInput {
-	let mut analog_input = ExampleDatabaseAnalogInput {
-		object_name: "Dungeness AnalogInput".to_string(),
-		instance: 0,
-		present_value: 1.001,
-		cov_incurment: 2.0,
-		reliability: 0,
-		description: "Incurments once every 5 seconds".to_string(),
-		proprietary_year: 122,
-		proprietary_month: 3,
-		proprietary_day: 20,
-		proprietary_weekday: 7,
-		proprietary_hour: 12,
-		proprietary_minute: 34,
-		proprietary_second: 23,
-		proprietary_hundredth_seconds: 45,
-		proprietary_real: 1.23,
-		proprietary_array_of_real: vec![1.00; 5]
+	for element in elements {
+		match element {
+			PropertyValue::CharacterString(text) if character_set.encode(text).is_none() => return Err(format!("{text:?} has characters that {character_set} cannot encode")),
+			PropertyValue::CharacterString(_) => {},
+			_ => return Err("is not a character string".to_string())
+		}
+	}
+	Ok(())
+}
+
+// Evaluates the event state of every object with intrinsic reporting, now in milliseconds since the
+// Unix epoch. Returns the objects whose event state changed.
+pub fn update_event_states(database: &mut Database, now: u64) -> Vec<(ObjectKey, EventState)> {
+	let mut transitions = Vec::new();
+	for (key, object) in database.iter_mut() {
+		let event_state = match object {
+			ExampleDatabaseObject::AnalogInput(analog_input) => analog_input.update_event_state(now),
+			ExampleDatabaseObject::BinaryInput(binary_input) => binary_input.update_event_state(now),
+			_ => None
+		};
+		if let Some(event_state) = event_state {
+			transitions.push((*key, event_state));
+		}
+	}
+	transitions
+}
+
+// A value a schedule or loop wrote to one of its referenced properties, and the result of the write
+pub struct ReferenceWrite {
+	pub source: ObjectKey,
+	pub target: ObjectKey,
+	pub property_identifier: PropertyIdentifier,
+	pub value: PropertyValue,
+	pub result: Result<(), ErrorCode>
+}
+
+// Evaluates every calendar and schedule at now, in milliseconds since the Unix epoch, in the local time
+// of its device. Returns the calendars and schedules whose present value changed, and the values the
+// schedules wrote. A schedule writes its present value when it changes, and once at startup.
+pub fn update_schedules(database: &mut Database, now: u64) -> (Vec<ObjectKey>, Vec<ReferenceWrite>) {
+	let mut changed = Vec::new();
+	let mut writes = Vec::new();
+	let keys: Vec<ObjectKey> = database.iter().filter(|(_, object)| matches!(object, ExampleDatabaseObject::Calendar(_))).map(|(key, _)| *key).collect();
+	for key in keys {
+		let (date, _) = local_date_time(database, key.device_instance, now);
+		if let Some(ExampleDatabaseObject::Calendar(calendar)) = database.get_mut(&key) {
+			let present_value = calendar.lists(&date);
+			if present_value != calendar.present_value {
+				calendar.present_value = present_value;
+				changed.push(key);
+			}
+		}
+	}
+
+	let keys: Vec<ObjectKey> = database.iter().filter(|(_, object)| matches!(object, ExampleDatabaseObject::Schedule(_))).map(|(key, _)| *key).collect();
+	for key in keys {
+		let (date, time) = local_date_time(database, key.device_instance, now);
+		let is_calendar_date = |instance| matches!(database.get(&ObjectKey::new(key.device_instance, ObjectType::Calendar, instance)), Some(ExampleDatabaseObject::Calendar(calendar)) if calendar.lists(&date));
+		let present_value = match database.get(&key) {
+			Some(ExampleDatabaseObject::Schedule(schedule)) if !schedule.out_of_service => schedule.timetable.effective_value(&date, &time, is_calendar_date),
+			_ => continue
+		};
+		let Some(ExampleDatabaseObject::Schedule(schedule)) = database.get_mut(&key) else {
+			continue;
+		};
+		if schedule.evaluated && schedule.present_value == present_value {
+			continue;
+		}
+		if schedule.present_value != present_value {
+			changed.push(key);
+		}
+		schedule.present_value = present_value.clone();
+		schedule.evaluated = true;
+		let (references, priority) = (schedule.list_of_object_property_references.clone(), schedule.priority_for_writing);
+		writes.extend(references.into_iter().filter_map(|reference| write_reference(database, key, reference, priority, &present_value)));
+	}
+	(changed, writes)
+}
+
+// Evaluates every loop whose update interval has passed at now, in milliseconds since the Unix epoch.
+// Returns the loops whose present value changed, and the values they wrote. A loop writes its output
+// when it changes, and on its first update.
+pub fn update_loops(database: &mut Database, now: u64) -> (Vec<ObjectKey>, Vec<ReferenceWrite>) {
+	let mut changed = Vec::new();
+	let mut writes = Vec::new();
+	let keys: Vec<ObjectKey> = database.iter().filter(|(_, object)| matches!(object, ExampleDatabaseObject::Loop(_))).map(|(key, _)| *key).collect();
+	for key in keys {
+		let control_loop = match database.get(&key) {
+			Some(ExampleDatabaseObject::Loop(control_loop)) if !control_loop.out_of_service => control_loop,
+			_ => continue
+		};
+		if control_loop.last_update.is_some_and(|last_update| now.saturating_sub(last_update) < u64::from(control_loop.update_interval)) {
+			continue;
+		}
+		let read = |reference: DeviceObjectPropertyReference| database.get(&ObjectKey::new(key.device_instance, reference.object_type, reference.instance))
+			.and_then(|object| object.get_property(reference.property, reference.array_index).ok())
+			.and_then(|value| real_value(&value))
+			.filter(|value| value.is_finite());
+		let controlled_variable_value = read(control_loop.controlled_variable_reference);
+		let setpoint = match control_loop.setpoint_reference {
+			Some(reference) => read(reference),
+			None => Some(control_loop.setpoint)
+		};
+		let Some(ExampleDatabaseObject::Loop(control_loop)) = database.get_mut(&key) else {
+			continue;
+		};
+		let first_update = control_loop.last_update.is_none();
+		control_loop.last_update = Some(now);
+		let (Some(controlled_variable_value), Some(setpoint)) = (controlled_variable_value, setpoint) else {
+			// The output holds its last value
+			control_loop.reliability = Reliability::UnreliableOther;
+			continue;
+		};
+		control_loop.reliability = Reliability::NoFaultDetected;
+		control_loop.controlled_variable_value = controlled_variable_value;
+		control_loop.setpoint = setpoint;
+		let present_value = control_loop.pid.update(now, setpoint, controlled_variable_value);
+		if !first_update && present_value == control_loop.present_value {
+			continue;
+		}
+		if present_value != control_loop.present_value {
+			changed.push(key);
+		}
+		control_loop.present_value = present_value;
+		let (reference, priority) = (control_loop.manipulated_variable_reference, control_loop.priority_for_writing);
+		writes.extend(write_reference(database, key, reference, priority, &PropertyValue::Real(present_value)));
+	}
+	(changed, writes)
+}
+
+// Writes a referenced property of an object on the source's device, as a command at the priority when
+// the property is commandable. Null relinquishes a command, other properties keep their value.
+fn write_reference(database: &mut Database, source: ObjectKey, reference: DeviceObjectPropertyReference, priority: u8, value: &PropertyValue) -> Option<ReferenceWrite> {
+	let target = ObjectKey::new(source.device_instance, reference.object_type, reference.instance);
+	let object = database.get_mut(&target)?;
+	let result = if object.is_commandable(reference.property) {
+		object.command(reference.property, priority, value)
+	} else if *value == PropertyValue::Null {
+		return None;
+	} else {
+		object.set_property(reference.property, reference.array_index, value)
 	};
-	analog_input
+	Some(ReferenceWrite { source, target, property_identifier: reference.property, value: value.clone(), result })
+}
+
+// A numeric value as a real, e.g. a controlled variable or setpoint read by a loop
+fn real_value(value: &PropertyValue) -> Option<f32> {
+	match *value {
+		PropertyValue::Real(value) => Some(value),
+		PropertyValue::Double(value) => Some(value as f32),
+		PropertyValue::Unsigned(value) => Some(value as f32),
+		PropertyValue::Signed(value) => Some(value as f32),
+		_ => None
+	}
+}
+
+// Local date and time of a device at now, in milliseconds since the Unix epoch
+fn local_date_time(database: &Database, device_instance: u32, now: u64) -> (BacnetDate, BacnetTime) {
+	match database.get(&ObjectKey::device(device_instance)) {
+		Some(ExampleDatabaseObject::Device(device)) => device.clock.local_date_time(now),
+		_ => millis_to_date_time(now)
+	}
+}
+
+// Milliseconds since the Unix epoch on a device's clock, the host clock for an unknown device
+pub fn device_time(database: &Database, device_instance: u32) -> u64 {
+	match database.get(&ObjectKey::device(device_instance)) {
+		Some(ExampleDatabaseObject::Device(device)) => device.clock.now(),
+		_ => DeviceClock::host_time().max(0) as u64
+	}
+}
+
+// Samples the logged property of every trend log, now in milliseconds since the Unix epoch
+pub fn update_trend_logs(database: &mut Database, now: u64) {
+	let trend_logs: Vec<ObjectKey> = database.iter().filter(|(_, object)| matches!(object, ExampleDatabaseObject::TrendLog(_))).map(|(key, _)| *key).collect();
+	for key in trend_logs {
+		let Some(ExampleDatabaseObject::TrendLog(trend_log)) = database.get(&key) else {
+			continue;
+		};
+		let reference = trend_log.log_device_object_property;
+		let value = database.get(&ObjectKey::new(key.device_instance, reference.object_type, reference.instance))
+			.and_then(|object| object.get_property(reference.property, reference.array_index).ok());
+		if let Some(ExampleDatabaseObject::TrendLog(trend_log)) = database.get_mut(&key) {
+			trend_log.update(now, value);
+		}
+	}
 }
 
-fn setup_analog_output() -> ExampleDatabaseAnalogOutput {
-	let mut analog_output = ExampleDatabaseAnalogOutput {
-		object_name: "Snow AnalogOutput".to_string(),
-		instance: 1,
-		priority_array_nulls: [true; PRIORITY_ARRAY_LENGTH],
-		priority_array_values: [false; PRIORITY_ARRAY_LENGTH]
+// Set up objects from their configured properties
+
+fn setup_device(device: &DeviceConfig) -> Result<ExampleDatabaseDevice, ConfigError> {
+	if !(-1440..=1440).contains(&device.utc_offset) {
+		return Err(ConfigError::Invalid(format!("device utc_offset {} is not between -1440 and 1440 minutes", device.utc_offset)));
+	}
+	// A name in the system's time zone database, or a POSIX TZ string. There is no such database on
+	// Windows, so only POSIX TZ strings work there.
+	let time_zone = match &device.time_zone {
+		Some(name) => Some(TimeZone::from_posix_tz(name).map_err(|err| ConfigError::Invalid(format!("device time_zone {name:?} is neither a known time zone nor a POSIX TZ string: {err}")))?),
+		None => None
 	};
-	analog_output
+	Ok(ExampleDatabaseDevice {	
+		object_name: device.name.clone(),
+		instance: device.instance,
+		clock: DeviceClock::new(device.utc_offset, time_zone),
+		description: device.description.clone(),
+		system_status: 0,
+		proprietary_properties: ProprietaryProperties::default()
+	})
+}
+
+fn setup_analog_input(object: &ObjectConfig, values: &mut ObjectValues) -> Result<ExampleDatabaseAnalogInput, ConfigError> {
+	let present_value = values.take(PropertyIdentifier::PresentValue, 0.0)?;
+	Ok(ExampleDatabaseAnalogInput {
+		object_name: object.name.clone(),
+		instance: object.instance,
+		out_of_service: values.take(PropertyIdentifier::OutOfService, false)?,
+		present_value,
+		analog: setup_analog_properties(object, values, PropertyIdentifier::PresentValue, present_value)?,
+		reliability: values.take(PropertyIdentifier::Reliability, Reliability::NoFaultDetected)?,
+		description: values.take(PropertyIdentifier::Description, String::new())?,
+		intrinsic_reporting: setup_intrinsic_reporting(object, values, setup_out_of_range)?,
+		proprietary_properties: setup_proprietary_properties(object)?
+	})
+}
+
+fn setup_analog_output(object: &ObjectConfig, values: &mut ObjectValues) -> Result<ExampleDatabaseAnalogOutput, ConfigError> {
+	let relinquish_default = values.take(PropertyIdentifier::RelinquishDefault, 0.0)?;
+	Ok(ExampleDatabaseAnalogOutput {
+		object_name: object.name.clone(),
+		instance: object.instance,
+		out_of_service: values.take(PropertyIdentifier::OutOfService, false)?,
+		commandable: Commandable::new(relinquish_default),
+		analog: setup_analog_properties(object, values, PropertyIdentifier::RelinquishDefault, relinquish_default)?,
+		proprietary_properties: setup_proprietary_properties(object)?
+	})
+}
+
+fn setup_analog_value(object: &ObjectConfig, values: &mut ObjectValues) -> Result<ExampleDatabaseAnalogValue, ConfigError> {
+	let present_value = values.take(PropertyIdentifier::PresentValue, 0.0)?;
+	Ok(ExampleDatabaseAnalogValue {
+		object_name: object.name.clone(),
+		instance: object.instance,
+		out_of_service: values.take(PropertyIdentifier::OutOfService, false)?,
+		present_value,
+		analog: setup_analog_properties(object, values, PropertyIdentifier::PresentValue, present_value)?,
+		proprietary_properties: setup_proprietary_properties(object)?
+	})
+}
+
+fn setup_binary_input(object: &ObjectConfig, values: &mut ObjectValues) -> Result<ExampleDatabaseBinaryInput, ConfigError> {
+	Ok(ExampleDatabaseBinaryInput {
+		object_name: object.name.clone(),
+		instance: object.instance,
+		out_of_service: values.take(PropertyIdentifier::OutOfService, false)?,
+		present_value: values.take(PropertyIdentifier::PresentValue, false)?,
+		polarity: values.take(PropertyIdentifier::Polarity, Polarity::Normal)?,
+		active_text: values.take(PropertyIdentifier::ActiveText, "active".to_string())?,
+		inactive_text: values.take(PropertyIdentifier::InactiveText, "inactive".to_string())?,
+		description: values.take(PropertyIdentifier::Description, String::new())?,
+		intrinsic_reporting: setup_intrinsic_reporting(object, values, |_, values| Ok(EventAlgorithm::ChangeOfState { alarm_value: values.take(PropertyIdentifier::AlarmValue, true)? }))?,
+		proprietary_properties: setup_proprietary_properties(object)?
+	})
+}
+
+fn setup_binary_output(object: &ObjectConfig, values: &mut ObjectValues) -> Result<ExampleDatabaseBinaryOutput, ConfigError> {
+	Ok(ExampleDatabaseBinaryOutput {
+		object_name: object.name.clone(),
+		instance: object.instance,
+		out_of_service: values.take(PropertyIdentifier::OutOfService, false)?,
+		commandable: Commandable::new(values.take(PropertyIdentifier::RelinquishDefault, false)?),
+		polarity: values.take(PropertyIdentifier::Polarity, Polarity::Normal)?,
+		active_text: values.take(PropertyIdentifier::ActiveText, "active".to_string())?,
+		inactive_text: values.take(PropertyIdentifier::InactiveText, "inactive".to_string())?,
+		proprietary_properties: setup_proprietary_properties(object)?
+	})
+}
+
+fn setup_binary_value(object: &ObjectConfig, values: &mut ObjectValues) -> Result<ExampleDatabaseBinaryValue, ConfigError> {
+	Ok(ExampleDatabaseBinaryValue {
+		object_name: object.name.clone(),
+		instance: object.instance,
+		out_of_service: values.take(PropertyIdentifier::OutOfService, false)?,
+		commandable: Commandable::new(values.take(PropertyIdentifier::RelinquishDefault, false)?),
+		active_text: values.take(PropertyIdentifier::ActiveText, "active".to_string())?,
+		inactive_text: values.take(PropertyIdentifier::InactiveText, "inactive".to_string())?,
+		proprietary_properties: setup_proprietary_properties(object)?
+	})
+}
+
+// The number of states defaults to the length of the state text, and must match it when both are given
+fn setup_state_set(object: &ObjectConfig, values: &mut ObjectValues) -> Result<StateSet, ConfigError> {
+	let state_text: Vec<String> = values.take(PropertyIdentifier::StateText, Vec::new())?;
+	let number_of_states = values.take(PropertyIdentifier::NumberOfStates, state_text.len() as u32)?;
+	if number_of_states == 0 {
+		return Err(ConfigError::Invalid(format!("{} {} needs a number-of-states or state-text", object.object_type, object.instance)));
+	}
+	if !state_text.is_empty() && state_text.len() != number_of_states as usize {
+		return Err(ConfigError::Invalid(format!("{} {} has {} state-text entries for {number_of_states} states", object.object_type, object.instance, state_text.len())));
+	}
+	Ok(StateSet { number_of_states, state_text })
+}
+
+fn check_configured_state(object: &ObjectConfig, states: &StateSet, property_identifier: PropertyIdentifier, state: u32) -> Result<(), ConfigError> {
+	states.check_state(state).map_err(|_| ConfigError::Invalid(format!("{} {} {property_identifier} is not one of its states", object.object_type, object.instance)))
+}
+
+fn setup_multistate_input(object: &ObjectConfig, values: &mut ObjectValues) -> Result<ExampleDatabaseMultiStateInput, ConfigError> {
+	let present_value = values.take(PropertyIdentifier::PresentValue, 1)?;
+	let states = setup_state_set(object, values)?;
+	check_configured_state(object, &states, PropertyIdentifier::PresentValue, present_value)?;
+	Ok(ExampleDatabaseMultiStateInput {
+		object_name: object.name.clone(),
+		instance: object.instance,
+		out_of_service: values.take(PropertyIdentifier::OutOfService, false)?,
+		present_value,
+		states,
+		proprietary_properties: setup_proprietary_properties(object)?
+	})
+}
+
+fn setup_multistate_output(object: &ObjectConfig, values: &mut ObjectValues) -> Result<ExampleDatabaseMultiStateOutput, ConfigError> {
+	let relinquish_default = values.take(PropertyIdentifier::RelinquishDefault, 1)?;
+	let states = setup_state_set(object, values)?;
+	check_configured_state(object, &states, PropertyIdentifier::RelinquishDefault, relinquish_default)?;
+	Ok(ExampleDatabaseMultiStateOutput {
+		object_name: object.name.clone(),
+		instance: object.instance,
+		out_of_service: values.take(PropertyIdentifier::OutOfService, false)?,
+		commandable: Commandable::new(relinquish_default),
+		states,
+		proprietary_properties: setup_proprietary_properties(object)?
+	})
+}
+
+fn setup_multistate_value(object: &ObjectConfig, values: &mut ObjectValues) -> Result<ExampleDatabaseMultiStateValue, ConfigError> {
+	let relinquish_default = values.take(PropertyIdentifier::RelinquishDefault, 1)?;
+	let states = setup_state_set(object, values)?;
+	check_configured_state(object, &states, PropertyIdentifier::RelinquishDefault, relinquish_default)?;
+	Ok(ExampleDatabaseMultiStateValue {
+		object_name: object.name.clone(),
+		instance: object.instance,
+		out_of_service: values.take(PropertyIdentifier::OutOfService, false)?,
+		commandable: Commandable::new(relinquish_default),
+		states,
+		proprietary_properties: setup_proprietary_properties(object)?
+	})
+}
+
+fn setup_bitstring_value(object: &ObjectConfig, values: &mut ObjectValues) -> Result<ExampleDatabaseBitstringValue, ConfigError> {
+	let present_value: Vec<bool> = values.take(PropertyIdentifier::PresentValue, Vec::new())?;
+	let bit_text: Vec<String> = values.take(PropertyIdentifier::BitText, Vec::new())?;
+	if !bit_text.is_empty() && bit_text.len() != present_value.len() {
+		return Err(ConfigError::Invalid(format!("{} {} bit_text needs one entry for each bit of present_value", object.object_type, object.instance)));
+	}
+	Ok(ExampleDatabaseBitstringValue {	
+		object_name: object.name.clone(),
+		instance: object.instance,
+		out_of_service: values.take(PropertyIdentifier::OutOfService, false)?,
+		present_value,
+		bit_text,
+		proprietary_properties: setup_proprietary_properties(object)?
+	})
+}
+
+fn setup_character_string_value(object: &ObjectConfig, values: &mut ObjectValues) -> Result<ExampleDatabaseCharacterStringValue, ConfigError> {
+	Ok(ExampleDatabaseCharacterStringValue {
+		object_name: object.name.clone(),
+		instance: object.instance,
+		out_of_service: values.take(PropertyIdentifier::OutOfService, false)?,
+		present_value: values.take(PropertyIdentifier::PresentValue, String::new())?,
+		proprietary_properties: setup_proprietary_properties(object)?
+	})
+}
+
+fn setup_integer_value(object: &ObjectConfig, values: &mut ObjectValues) -> Result<ExampleDatabaseIntegerValue, ConfigError> {
+	Ok(ExampleDatabaseIntegerValue {
+		object_name: object.name.clone(),
+		instance: object.instance,
+		out_of_service: values.take(PropertyIdentifier::OutOfService, false)?,
+		present_value: values.take(PropertyIdentifier::PresentValue, 0)?,
+		proprietary_properties: setup_proprietary_properties(object)?
+	})
+}
+
+fn setup_large_analog_value(object: &ObjectConfig, values: &mut ObjectValues) -> Result<ExampleDatabaseLargeAnalogValue, ConfigError> {
+	Ok(ExampleDatabaseLargeAnalogValue {
+		object_name: object.name.clone(),
+		instance: object.instance,
+		out_of_service: values.take(PropertyIdentifier::OutOfService, false)?,
+		present_value: values.take(PropertyIdentifier::PresentValue, 0.0)?,
+		proprietary_properties: setup_proprietary_properties(object)?
+	})
+}
+
+fn setup_octet_string_value(object: &ObjectConfig, values: &mut ObjectValues) -> Result<ExampleDatabaseOctetStringValue, ConfigError> {
+	Ok(ExampleDatabaseOctetStringValue {
+		object_name: object.name.clone(),
+		instance: object.instance,
+		out_of_service: values.take(PropertyIdentifier::OutOfService, false)?,
+		present_value: values.take(PropertyIdentifier::PresentValue, Vec::new())?,
+		proprietary_properties: setup_proprietary_properties(object)?
+	})
+}
+
+fn setup_positive_integer_value(object: &ObjectConfig, values: &mut ObjectValues) -> Result<ExampleDatabasePositiveIntegerValue, ConfigError> {
+	Ok(ExampleDatabasePositiveIntegerValue {
+		object_name: object.name.clone(),
+		instance: object.instance,
+		out_of_service: values.take(PropertyIdentifier::OutOfService, false)?,
+		present_value: values.take(PropertyIdentifier::PresentValue, 0)?,
+		proprietary_properties: setup_proprietary_properties(object)?
+	})
+}
+
+fn setup_network_port(object: &ObjectConfig, values: &mut ObjectValues) -> Result<ExampleDatabaseNetworkPort, ConfigError> {
+	let ip_address: Ipv4Addr = values.take(PropertyIdentifier::IpAddress, Ipv4Addr::UNSPECIFIED)?;
+	let ip_subnet_mask: Ipv4Addr = values.take(PropertyIdentifier::IpSubnetMask, Ipv4Addr::new(255, 255, 255, 0))?;
+	let ip_dns_servers: Vec<Ipv4Addr> = values.take(PropertyIdentifier::IpDnsServer, Vec::new())?;
+	let fd_bbmd_address: Option<SocketAddrV4> = values.take(PropertyIdentifier::FdBbmdAddress, None)?;
+	let broadcast_ip_address = Ipv4Addr::from(u32::from(ip_address) | !u32::from(ip_subnet_mask));
+	Ok(ExampleDatabaseNetworkPort {
+		object_name: object.name.clone(),
+		instance: object.instance,
+		out_of_service: values.take(PropertyIdentifier::OutOfService, false)?,
+		bacnet_ip_udp_port: values.take(PropertyIdentifier::BacnetIpUdpPort, 47808)?,
+		ip_address: ip_address.octets(),
+		ip_address_length: 4,
+		ip_default_gateway: values.take(PropertyIdentifier::IpDefaultGateway, Ipv4Addr::UNSPECIFIED)?.octets(),
+		ip_default_gateway_length: 4,
+		ip_subnet_mask: ip_subnet_mask.octets(),
+		ip_subnet_mask_length: 4,
+		ip_dns_servers: ip_dns_servers.iter().flat_map(Ipv4Addr::octets).collect(),
+		ip_dns_server_length: 4,
+		broadcast_ip_address: broadcast_ip_address.octets(),
+		changes_pending: values.take(PropertyIdentifier::ChangesPending, false)?,
+		fd_bbmd_address_host_type: if fd_bbmd_address.is_some() { bacnet_const::HOST_TYPE_IPADDRESS } else { bacnet_const::HOST_TYPE_NONE },
+		fd_bbmd_address_host_ip: fd_bbmd_address.map_or([0; 4], |address| address.ip().octets()),
+		fd_bbmd_address_port: fd_bbmd_address.map_or(0, |address| address.port()),
+		fd_subscription_lifetime: values.take(PropertyIdentifier::FdSubscriptionLifetime, 0)?,
+		proprietary_properties: setup_proprietary_properties(object)?
+	})
+}
+
+// A configured TOML date, time, or both
+fn take_date_time(object: &ObjectConfig, values: &mut ObjectValues, property_identifier: PropertyIdentifier, expected: &str) -> Result<(Option<toml::value::Date>, Option<toml::value::Time>), ConfigError> {
+	match values.take_raw(property_identifier) {
+		Some(toml::Value::Datetime(date_time)) => Ok((date_time.date, date_time.time)),
+		Some(_) => Err(ConfigError::Invalid(format!("{} {} {property_identifier} must be a TOML {expected}", object.object_type, object.instance))),
+		None => Ok((None, None))
+	}
+}
+
+// Defaults to 1900-01-01
+fn bacnet_date(object: &ObjectConfig, property_identifier: PropertyIdentifier, date: Option<toml::value::Date>) -> Result<BacnetDate, ConfigError> {
+	let year = date.map_or(1900, |date| date.year);
+	if !(1900..=2154).contains(&year) {
+		return Err(ConfigError::Invalid(format!("{} {} {property_identifier} year {year} is outside 1900-2154", object.object_type, object.instance)));
+	}
+	Ok(BacnetDate {
+		year: (year - 1900) as u8,
+		month: date.map_or(1, |date| date.month),
+		day: date.map_or(1, |date| date.day),
+		weekday: date.map_or(1, |date| day_of_week(date.year, date.month, date.day))
+	})
+}
+
+// Defaults to midnight
+fn bacnet_time(time: Option<toml::value::Time>) -> BacnetTime {
+	BacnetTime {
+		hour: time.map_or(0, |time| time.hour),
+		minute: time.map_or(0, |time| time.minute),
+		second: time.map_or(0, |time| time.second),
+		hundredth_seconds: time.map_or(0, |time| (time.nanosecond / 10_000_000) as u8)
+	}
 }
 
-fn setup_analog_value() -> ExampleDatabaseAnalogValue {
-	let mut analog_value = ExampleDatabaseAnalogValue {
-		object_name: "Flower AnalogValue".to_string(),
-		instance: 2,
-		present_value: 5.43,
-		max_pres_value: 1000.0,
-		min_pres_value: -1000.0
+fn setup_date_value(object: &ObjectConfig, values: &mut ObjectValues) -> Result<ExampleDatabaseDateValue, ConfigError> {
+	let date = match take_date_time(object, values, PropertyIdentifier::PresentValue, "date")? {
+		(date, None) => date,
+		(_, Some(_)) => return Err(ConfigError::Invalid(format!("{} {} present-value must be a TOML date without a time", object.object_type, object.instance)))
 	};
-	analog_value
+	Ok(ExampleDatabaseDateValue {
+		object_name: object.name.clone(),
+		instance: object.instance,
+		out_of_service: values.take(PropertyIdentifier::OutOfService, false)?,
+		present_value: bacnet_date(object, PropertyIdentifier::PresentValue, date)?,
+		proprietary_properties: setup_proprietary_properties(object)?
+	})
 }
 
-fn setup_binary_input() -> ExampleDatabaseBinaryInput {
-	let mut binary_input = ExampleDatabaseBinaryInput {
-		object_name: "Chesapeake Blue BinaryInput".to_string(),
-		instance: 3,
-		present_value: true,
-		description: "I am an optional property!".to_string()
+fn setup_date_time_value(object: &ObjectConfig, values: &mut ObjectValues) -> Result<ExampleDatabaseDateTimeValue, ConfigError> {
+	let (date, time) = take_date_time(object, values, PropertyIdentifier::PresentValue, "date and time")?;
+	let date = bacnet_date(object, PropertyIdentifier::PresentValue, date)?;
+	let time = bacnet_time(time);
+	Ok(ExampleDatabaseDateTimeValue {
+		object_name: object.name.clone(),
+		instance: object.instance,
+		out_of_service: values.take(PropertyIdentifier::OutOfService, false)?,
+		present_value_year: date.year,
+		present_value_month: date.month,
+		present_value_day: date.day,
+		present_value_weekday: date.weekday,
+		present_value_hour: time.hour,
+		present_value_minute: time.minute,
+		present_value_second: time.second,
+		present_value_hundredth_seconds: time.hundredth_seconds,
+		proprietary_properties: setup_proprietary_properties(object)?
+	})
+}
+
+fn setup_time_value(object: &ObjectConfig, values: &mut ObjectValues) -> Result<ExampleDatabaseTimeValue, ConfigError> {
+	let time = match take_date_time(object, values, PropertyIdentifier::PresentValue, "time")? {
+		(None, time) => time,
+		(Some(_), _) => return Err(ConfigError::Invalid(format!("{} {} present-value must be a TOML time without a date", object.object_type, object.instance)))
 	};
-	binary_input
+	Ok(ExampleDatabaseTimeValue {
+		object_name: object.name.clone(),
+		instance: object.instance,
+		out_of_service: values.take(PropertyIdentifier::OutOfService, false)?,
+		present_value: bacnet_time(time),
+		proprietary_properties: setup_proprietary_properties(object)?
+	})
+}
+
+fn setup_trend_log(object: &ObjectConfig, values: &mut ObjectValues) -> Result<ExampleDatabaseTrendLog, ConfigError> {
+	let log_device_object_property = values.take(PropertyIdentifier::LogDeviceObjectProperty, None)?
+		.ok_or_else(|| ConfigError::Invalid(format!("{} {} needs a log-device-object-property", object.object_type, object.instance)))?;
+	let logging_type = values.take(PropertyIdentifier::LoggingType, LoggingType::Polled)?;
+	let log_interval = values.take(PropertyIdentifier::LogInterval, 6000)?;
+	let buffer_size = values.take(PropertyIdentifier::BufferSize, bacnet_const::MAX_TREND_LOG_MAX_BUFFER_SIZE)?;
+	if logging_type == LoggingType::Triggered {
+		return Err(ConfigError::Invalid(format!("{} {} triggered logging is not supported", object.object_type, object.instance)));
+	}
+	if logging_type == LoggingType::Polled && log_interval == 0 {
+		return Err(ConfigError::Invalid(format!("{} {} log-interval must be more than 0 for polled logging", object.object_type, object.instance)));
+	}
+	if !(1..=bacnet_const::MAX_TREND_LOG_MAX_BUFFER_SIZE).contains(&buffer_size) {
+		return Err(ConfigError::Invalid(format!("{} {} buffer-size must be from 1 to {}", object.object_type, object.instance, bacnet_const::MAX_TREND_LOG_MAX_BUFFER_SIZE)));
+	}
+	Ok(ExampleDatabaseTrendLog {
+		object_name: object.name.clone(),
+		instance: object.instance,
+		log_device_object_property,
+		logging_type,
+		log_enable: values.take(PropertyIdentifier::Enable, true)?,
+		log_interval,
+		start_time: setup_log_time(object, values, PropertyIdentifier::StartTime)?,
+		stop_time: setup_log_time(object, values, PropertyIdentifier::StopTime)?,
+		stop_when_full: values.take(PropertyIdentifier::StopWhenFull, false)?,
+		client_cov_increment: values.take(PropertyIdentifier::ClientCovIncrement, None)?,
+		log_buffer: LogBuffer::new(buffer_size),
+		last_sample_time: None,
+		last_logged_value: None,
+		proprietary_properties: setup_proprietary_properties(object)?
+	})
+}
+
+// Unspecified when not configured, which does not limit logging
+fn setup_log_time(object: &ObjectConfig, values: &mut ObjectValues, property_identifier: PropertyIdentifier) -> Result<(BacnetDate, BacnetTime), ConfigError> {
+	match take_date_time(object, values, property_identifier, "date and time")? {
+		(None, None) => Ok((BacnetDate::UNSPECIFIED, BacnetTime::UNSPECIFIED)),
+		(date, time) => Ok((bacnet_date(object, property_identifier, date)?, bacnet_time(time)))
+	}
 }
 
-fn setup_multistate_input() -> ExampleDatabaseMultiStateInput {
-	let mut multistate_input = ExampleDatabaseMultiStateInput {
-		object_name: "Pea MultiStateInput".to_string(),
-		instance: 13,
-		present_value: 1,
-		state_text: vec!["one".to_string(), "two".to_string(), "three".to_string()]
+// Intrinsic reporting is enabled by giving the object a notification class
+fn setup_intrinsic_reporting(object: &ObjectConfig, values: &mut ObjectValues, setup_algorithm: impl FnOnce(&ObjectConfig, &mut ObjectValues) -> Result<EventAlgorithm, ConfigError>) -> Result<Option<IntrinsicReporting>, ConfigError> {
+	let Some(notification_class) = values.take(PropertyIdentifier::NotificationClass, None)? else {
+		return Ok(None);
 	};
-	multistate_input
+	let notify_type = values.take(PropertyIdentifier::NotifyType, NotifyType::Alarm)?;
+	if notify_type == NotifyType::AckNotification {
+		return Err(ConfigError::Invalid(format!("{} {} notify_type must be alarm or event", object.object_type, object.instance)));
+	}
+	let event_enable = EventTransitions::from_bits(&values.take(PropertyIdentifier::EventEnable, vec![true; 3])?)
+		.map_err(|_| ConfigError::Invalid(format!("{} {} event_enable needs 3 bits: to-offnormal, to-fault and to-normal", object.object_type, object.instance)))?;
+	let time_delay = values.take(PropertyIdentifier::TimeDelay, 0)?;
+	Ok(Some(IntrinsicReporting::new(notification_class, notify_type, event_enable, time_delay, setup_algorithm(object, values)?)))
 }
 
-fn setup_bitstring_value() -> ExampleDatabaseBitstringValue {
-	let mut bitstring_value = ExampleDatabaseBitstringValue {	
-		object_name: "Yeti BitstringValue".to_string(),
-		instance: 39,
-		present_value: vec![true, false, false, false],
-		bit_text: vec!["A".to_string(), "B".to_string(), "C".to_string(), "D".to_string()]
+fn setup_out_of_range(object: &ObjectConfig, values: &mut ObjectValues) -> Result<EventAlgorithm, ConfigError> {
+	let (Some(high_limit), Some(low_limit)) = (values.take(PropertyIdentifier::HighLimit, None)?, values.take(PropertyIdentifier::LowLimit, None)?) else {
+		return Err(ConfigError::Invalid(format!("{} {} needs a high_limit and a low_limit for intrinsic reporting", object.object_type, object.instance)));
 	};
-	bitstring_value
+	let deadband = values.take(PropertyIdentifier::Deadband, 0.0)?;
+	if deadband < 0.0 {
+		return Err(ConfigError::Invalid(format!("{} {} deadband must not be negative", object.object_type, object.instance)));
+	}
+	let [low_limit_enable, high_limit_enable] = values.take(PropertyIdentifier::LimitEnable, [true, true])?;
+	Ok(EventAlgorithm::OutOfRange { high_limit, low_limit, deadband, low_limit_enable, high_limit_enable })
 }
 
-fn setup_character_string_value() -> ExampleDatabaseCharacterStringValue {
-	let mut character_string_value = ExampleDatabaseCharacterStringValue {
-		object_name: "Coconut CharacterStringValue".to_string(),
-		instance: 40,
-		present_value: "Hello World!".to_string()
+// The present value range defaults to every real value. The configured value of the given property
+// must be in range.
+fn setup_analog_properties(object: &ObjectConfig, values: &mut ObjectValues, property_identifier: PropertyIdentifier, value: f32) -> Result<AnalogProperties, ConfigError> {
+	let analog = AnalogProperties {
+		units: values.take(PropertyIdentifier::Units, EngineeringUnits::NoUnits)?,
+		resolution: values.take(PropertyIdentifier::Resolution, 0.0)?,
+		min_pres_value: values.take(PropertyIdentifier::MinPresValue, f32::MIN)?,
+		max_pres_value: values.take(PropertyIdentifier::MaxPresValue, f32::MAX)?,
+		cov_increment: values.take(PropertyIdentifier::CovIncrement, 0.0)?
 	};
-	character_string_value
+	if analog.min_pres_value > analog.max_pres_value {
+		return Err(ConfigError::Invalid(format!("{} {} min_pres_value is above max_pres_value", object.object_type, object.instance)));
+	}
+	if analog.resolution < 0.0 || analog.cov_increment < 0.0 {
+		return Err(ConfigError::Invalid(format!("{} {} resolution and cov_increment must not be negative", object.object_type, object.instance)));
+	}
+	if analog.check_present_value(value).is_err() {
+		return Err(ConfigError::Invalid(format!("{} {} {property_identifier} {value} is outside min_pres_value and max_pres_value", object.object_type, object.instance)));
+	}
+	Ok(analog)
 }
 
-fn setup_integer_value() -> ExampleDatabaseIntegerValue {
-	let mut integer_value = ExampleDatabaseIntegerValue {
-		object_name: "Spider IntegerValue".to_string(),
-		instance: 45,
-		present_value: 42
+fn setup_proprietary_properties(object: &ObjectConfig) -> Result<ProprietaryProperties, ConfigError> {
+	let mut properties: Vec<ProprietaryProperty> = Vec::new();
+	for property in &object.proprietary_properties {
+		let property_identifier = property.identifier;
+		let invalid = |message: &str| ConfigError::Invalid(format!("{} {} {property_identifier} {message}", object.object_type, object.instance));
+		if !property_identifier.is_proprietary() {
+			return Err(invalid("is not a proprietary property, which are numbered from 512"));
+		}
+		if properties.iter().any(|existing| existing.property_identifier == property_identifier) {
+			return Err(invalid("is declared more than once"));
+		}
+		if matches!(property.data_type, DataType::Null | DataType::BacnetObjectIdentifier) {
+			return Err(invalid(&format!("cannot be of type {}, which has no callback", property.data_type)));
+		}
+		let value = match &property.value {
+			toml::Value::Array(elements) if property.array => elements.iter().map(|element| proprietary_value(object, property, element)).collect::<Result<Vec<PropertyValue>, ConfigError>>().map(PropertyValue::Array)?,
+			_ if property.array => return Err(invalid(&format!("must be an array of {}", property.data_type))),
+			value => proprietary_value(object, property, value)?
+		};
+		properties.push(ProprietaryProperty { property_identifier, data_type: property.data_type, value });
+	}
+	Ok(ProprietaryProperties { properties })
+}
+
+// A configured proprietary value, or one element of an array
+fn proprietary_value(object: &ObjectConfig, property: &ProprietaryPropertyConfig, value: &toml::Value) -> Result<PropertyValue, ConfigError> {
+	let date_time = match value {
+		toml::Value::Datetime(date_time) => Some((date_time.date, date_time.time)),
+		_ => None
 	};
-	integer_value
+	let value = match property.data_type {
+		DataType::Boolean => value.as_bool().map(PropertyValue::Boolean),
+		DataType::UnsignedInteger => value.as_integer().and_then(|value| u32::try_from(value).ok()).map(PropertyValue::Unsigned),
+		DataType::SignedInteger => value.as_integer().and_then(|value| i32::try_from(value).ok()).map(PropertyValue::Signed),
+		DataType::Real => value.as_float().or(value.as_integer().map(|value| value as f64)).map(|value| PropertyValue::Real(value as f32)),
+		DataType::Double => value.as_float().or(value.as_integer().map(|value| value as f64)).map(PropertyValue::Double),
+		DataType::OctetString => value.clone().try_into().ok().map(PropertyValue::OctetString),
+		DataType::CharacterString => value.as_str().map(|value| PropertyValue::CharacterString(value.to_string())),
+		DataType::BitString => value.clone().try_into().ok().map(PropertyValue::BitString),
+		DataType::Enumerated => value.as_integer().and_then(|value| u32::try_from(value).ok()).map(PropertyValue::Enumerated),
+		DataType::Date => match date_time {
+			Some((Some(date), None)) => Some(PropertyValue::Date(bacnet_date(object, property.identifier, Some(date))?)),
+			_ => None
+		},
+		DataType::Time => match date_time {
+			Some((None, Some(time))) => Some(PropertyValue::Time(bacnet_time(Some(time)))),
+			_ => None
+		},
+		DataType::DateTime => match date_time {
+			Some((Some(date), Some(time))) => Some(PropertyValue::DateTime(bacnet_date(object, property.identifier, Some(date))?, bacnet_time(Some(time)))),
+			_ => None
+		},
+		DataType::Null | DataType::BacnetObjectIdentifier => None
+	};
+	value.ok_or_else(|| ConfigError::Invalid(format!("{} {} {} value must be of type {}", object.object_type, object.instance, property.identifier, property.data_type)))
+}
+
+fn setup_notification_class(object: &ObjectConfig, values: &mut ObjectValues) -> Result<ExampleDatabaseNotificationClass, ConfigError> {
+	Ok(ExampleDatabaseNotificationClass {
+		object_name: object.name.clone(),
+		instance: object.instance,
+		priority: values.take(PropertyIdentifier::Priority, [255; 3])?,
+		ack_required: EventTransitions::from_bits(&values.take(PropertyIdentifier::AckRequired, vec![false; 3])?)
+			.map_err(|_| ConfigError::Invalid(format!("{} {} ack_required needs 3 bits: to-offnormal, to-fault and to-normal", object.object_type, object.instance)))?,
+		proprietary_properties: setup_proprietary_properties(object)?
+	})
+}
+
+// A date pattern in the config file, e.g. { month = 12, day = 25 }. Missing fields are unspecified.
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DatePatternConfig {
+	year: Option<u16>,
+	month: Option<u8>,
+	day: Option<u8>,
+	weekday: Option<u8>
+}
+
+// A missing start or end leaves the range open on that side
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DateRangeConfig {
+	start: Option<DatePatternConfig>,
+	end: Option<DatePatternConfig>
+}
+
+// Missing fields match any month, week or day
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct WeekNDayConfig {
+	month: Option<u8>,
+	week_of_month: Option<u8>,
+	day_of_week: Option<u8>
+}
+
+// A calendar entry or special event period, e.g. { week_n_day = { month = 11, week_of_month = 4, day_of_week = 4 } }.
+// A special event may also give the instance of a calendar.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+enum CalendarEntryConfig {
+	Date(DatePatternConfig),
+	DateRange(DateRangeConfig),
+	WeekNDay(WeekNDayConfig),
+	Calendar(u32)
 }
 
-fn setup_large_analog_value() -> ExampleDatabaseLargeAnalogValue {
-	let mut large_analog_value = ExampleDatabaseLargeAnalogValue {
-		object_name: "Tanner LargeAnalogValue".to_string(),
-		instance: 46,
-		present_value: 123456789.85
+// e.g. { time = 07:30:00, value = { real = 21.0 } }
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TimeValueConfig {
+	// A TOML time, which reaches here as a string when deserialized from the raw values table
+	time: toml::Value,
+	value: PropertyValue
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SpecialEventConfig {
+	period: CalendarEntryConfig,
+	time_values: Vec<TimeValueConfig>,
+	// 16, the lowest, when not given
+	priority: Option<u8>
+}
+
+fn setup_schedule(object: &ObjectConfig, values: &mut ObjectValues) -> Result<ExampleDatabaseSchedule, ConfigError> {
+	let weekly_schedule = values.take(PropertyIdentifier::WeeklySchedule, Vec::<Vec<TimeValueConfig>>::new())?.into_iter()
+		.map(|time_values| setup_time_values(object, PropertyIdentifier::WeeklySchedule, time_values))
+		.collect::<Result<Vec<_>, _>>()?;
+	let weekly_schedule = match weekly_schedule.len() {
+		0 => Default::default(),
+		_ => weekly_schedule.try_into().map_err(|_| ConfigError::Invalid(format!("{} {} weekly-schedule needs 7 days, Monday first", object.object_type, object.instance)))?
 	};
-	large_analog_value
+	let exception_schedule = values.take(PropertyIdentifier::ExceptionSchedule, Vec::<SpecialEventConfig>::new())?.into_iter()
+		.map(|special_event| setup_special_event(object, special_event))
+		.collect::<Result<Vec<_>, _>>()?;
+	let effective_period = match values.take(PropertyIdentifier::EffectivePeriod, None)? {
+		Some(range) => setup_date_range(object, PropertyIdentifier::EffectivePeriod, range)?,
+		None => DateRange::ALL
+	};
+	let timetable = Timetable { effective_period, weekly_schedule, exception_schedule, schedule_default: values.take(PropertyIdentifier::ScheduleDefault, PropertyValue::Null)? };
+	if timetable.values().any(|value| timetable.check_value_type(value).is_err()) {
+		return Err(ConfigError::Invalid(format!("{} {} schedule-default and the scheduled values must be primitive values of one data type, or null", object.object_type, object.instance)));
+	}
+	let priority_for_writing = values.take(PropertyIdentifier::PriorityForWriting, 16)?;
+	if !(1..=16).contains(&priority_for_writing) || priority_for_writing == 6 {
+		return Err(ConfigError::Invalid(format!("{} {} priority-for-writing must be from 1 to 16, other than 6 which is reserved for minimum on and off times", object.object_type, object.instance)));
+	}
+	Ok(ExampleDatabaseSchedule {
+		object_name: object.name.clone(),
+		instance: object.instance,
+		out_of_service: values.take(PropertyIdentifier::OutOfService, false)?,
+		present_value: timetable.schedule_default.clone(),
+		timetable,
+		list_of_object_property_references: values.take(PropertyIdentifier::ListOfObjectPropertyReferences, Vec::new())?,
+		priority_for_writing,
+		evaluated: false,
+		proprietary_properties: setup_proprietary_properties(object)?
+	})
+}
+
+fn setup_calendar(object: &ObjectConfig, values: &mut ObjectValues) -> Result<ExampleDatabaseCalendar, ConfigError> {
+	let date_list = values.take(PropertyIdentifier::DateList, Vec::<CalendarEntryConfig>::new())?.into_iter()
+		.map(|entry| match setup_calendar_entry(object, PropertyIdentifier::DateList, entry)? {
+			SpecialEventPeriod::CalendarEntry(entry) => Ok(entry),
+			SpecialEventPeriod::CalendarReference(_) => Err(ConfigError::Invalid(format!("{} {} date-list cannot refer to a calendar", object.object_type, object.instance)))
+		})
+		.collect::<Result<Vec<_>, _>>()?;
+	Ok(ExampleDatabaseCalendar {
+		object_name: object.name.clone(),
+		instance: object.instance,
+		present_value: false,
+		date_list,
+		proprietary_properties: setup_proprietary_properties(object)?
+	})
+}
+
+fn setup_loop(object: &ObjectConfig, values: &mut ObjectValues) -> Result<ExampleDatabaseLoop, ConfigError> {
+	let reference = |values: &mut ObjectValues, property_identifier| values.take::<Option<DeviceObjectPropertyReference>>(property_identifier, None)?
+		.ok_or_else(|| ConfigError::Invalid(format!("{} {} needs a {property_identifier}", object.object_type, object.instance)));
+	let manipulated_variable_reference = reference(values, PropertyIdentifier::ManipulatedVariableReference)?;
+	let controlled_variable_reference = reference(values, PropertyIdentifier::ControlledVariableReference)?;
+	let mut pid = Pid::default();
+	pid.action = values.take(PropertyIdentifier::Action, pid.action)?;
+	pid.proportional_constant = values.take(PropertyIdentifier::ProportionalConstant, pid.proportional_constant)?;
+	pid.proportional_constant_units = values.take(PropertyIdentifier::ProportionalConstantUnits, pid.proportional_constant_units)?;
+	pid.integral_constant = values.take(PropertyIdentifier::IntegralConstant, pid.integral_constant)?;
+	pid.integral_constant_units = values.take(PropertyIdentifier::IntegralConstantUnits, pid.integral_constant_units)?;
+	pid.derivative_constant = values.take(PropertyIdentifier::DerivativeConstant, pid.derivative_constant)?;
+	pid.derivative_constant_units = values.take(PropertyIdentifier::DerivativeConstantUnits, pid.derivative_constant_units)?;
+	pid.bias = values.take(PropertyIdentifier::Bias, pid.bias)?;
+	pid.minimum_output = values.take(PropertyIdentifier::MinimumOutput, pid.minimum_output)?;
+	pid.maximum_output = values.take(PropertyIdentifier::MaximumOutput, pid.maximum_output)?;
+	if Pid::integral_seconds(pid.integral_constant_units).is_none() || Pid::derivative_seconds(pid.derivative_constant_units).is_none() {
+		return Err(ConfigError::Invalid(format!("{} {} integral_constant_units must be per-second, per-minute or per-hour, and derivative_constant_units seconds, minutes or hours", object.object_type, object.instance)));
+	}
+	if pid.minimum_output > pid.maximum_output {
+		return Err(ConfigError::Invalid(format!("{} {} minimum_output is above maximum_output", object.object_type, object.instance)));
+	}
+	let priority_for_writing = values.take(PropertyIdentifier::PriorityForWriting, 16)?;
+	if !(1..=16).contains(&priority_for_writing) || priority_for_writing == 6 {
+		return Err(ConfigError::Invalid(format!("{} {} priority-for-writing must be from 1 to 16, other than 6 which is reserved for minimum on and off times", object.object_type, object.instance)));
+	}
+	Ok(ExampleDatabaseLoop {
+		object_name: object.name.clone(),
+		instance: object.instance,
+		out_of_service: values.take(PropertyIdentifier::OutOfService, false)?,
+		// The output with no error, until the first update
+		present_value: pid.bias.clamp(pid.minimum_output, pid.maximum_output),
+		output_units: values.take(PropertyIdentifier::OutputUnits, EngineeringUnits::NoUnits)?,
+		reliability: Reliability::NoFaultDetected,
+		update_interval: values.take(PropertyIdentifier::UpdateInterval, 1000)?,
+		manipulated_variable_reference,
+		controlled_variable_reference,
+		controlled_variable_value: 0.0,
+		controlled_variable_units: values.take(PropertyIdentifier::ControlledVariableUnits, EngineeringUnits::NoUnits)?,
+		setpoint_reference: values.take(PropertyIdentifier::SetpointReference, None)?,
+		setpoint: values.take(PropertyIdentifier::Setpoint, 0.0)?,
+		priority_for_writing,
+		pid,
+		last_update: None,
+		proprietary_properties: setup_proprietary_properties(object)?
+	})
+}
+
+fn setup_special_event(object: &ObjectConfig, special_event: SpecialEventConfig) -> Result<SpecialEvent, ConfigError> {
+	let priority = special_event.priority.unwrap_or(16);
+	if !(1..=16).contains(&priority) {
+		return Err(ConfigError::Invalid(format!("{} {} exception-schedule priority must be from 1 to 16", object.object_type, object.instance)));
+	}
+	Ok(SpecialEvent {
+		period: setup_calendar_entry(object, PropertyIdentifier::ExceptionSchedule, special_event.period)?,
+		time_values: setup_time_values(object, PropertyIdentifier::ExceptionSchedule, special_event.time_values)?,
+		priority
+	})
+}
+
+fn setup_time_values(object: &ObjectConfig, property_identifier: PropertyIdentifier, time_values: Vec<TimeValueConfig>) -> Result<Vec<TimeValue>, ConfigError> {
+	time_values.into_iter().map(|time_value| {
+		let date_time = match time_value.time {
+			toml::Value::Datetime(date_time) => Some(date_time),
+			toml::Value::String(date_time) => date_time.parse::<toml::value::Datetime>().ok(),
+			_ => None
+		};
+		match date_time.map(|date_time| (date_time.date, date_time.time)) {
+			Some((None, Some(time))) => Ok(TimeValue { time: bacnet_time(Some(time)), value: time_value.value }),
+			_ => Err(ConfigError::Invalid(format!("{} {} {property_identifier} times must be TOML times without a date", object.object_type, object.instance)))
+		}
+	}).collect()
 }
 
-fn setup_octet_string_value() -> ExampleDatabaseOctetStringValue {
-	let mut octet_string_value = ExampleDatabaseOctetStringValue {
-		object_name: "Brown Box OctetStringValue".to_string(),
-		instance: 47,
-		present_value: vec![0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07]
+fn setup_calendar_entry(object: &ObjectConfig, property_identifier: PropertyIdentifier, entry: CalendarEntryConfig) -> Result<SpecialEventPeriod, ConfigError> {
+	let entry = match entry {
+		CalendarEntryConfig::Date(pattern) => CalendarEntry::Date(date_pattern(object, property_identifier, pattern)?),
+		CalendarEntryConfig::DateRange(range) => CalendarEntry::DateRange(setup_date_range(object, property_identifier, range)?),
+		CalendarEntryConfig::WeekNDay(week_n_day) => {
+			let month = week_n_day.month.unwrap_or(UNSPECIFIED);
+			let week_of_month = week_n_day.week_of_month.unwrap_or(UNSPECIFIED);
+			let day_of_week = week_n_day.day_of_week.unwrap_or(UNSPECIFIED);
+			if !matches!(month, 1..=14 | UNSPECIFIED) || !matches!(week_of_month, 1..=9 | UNSPECIFIED) || !matches!(day_of_week, 1..=7 | UNSPECIFIED) {
+				return Err(ConfigError::Invalid(format!("{} {} {property_identifier} has an invalid week-n-day {week_n_day:?}", object.object_type, object.instance)));
+			}
+			CalendarEntry::WeekNDay { month, week_of_month, day_of_week }
+		},
+		CalendarEntryConfig::Calendar(instance) => return Ok(SpecialEventPeriod::CalendarReference(instance))
 	};
-	octet_string_value
+	Ok(SpecialEventPeriod::CalendarEntry(entry))
 }
 
-fn setup_positive_integer_value() -> ExampleDatabasePositiveIntegerValue {
-	let mut positive_integer_value = ExampleDatabasePositiveIntegerValue {
-		object_name: "Strawberry PositiveIntegerValue".to_string(),
-		instance: 48,
-		present_value: 12345
+// Either end of a range is open, or a year, month and day
+fn setup_date_range(object: &ObjectConfig, property_identifier: PropertyIdentifier, range: DateRangeConfig) -> Result<DateRange, ConfigError> {
+	let end = |pattern: Option<DatePatternConfig>| match pattern {
+		None => Ok(BacnetDate::UNSPECIFIED),
+		Some(pattern @ DatePatternConfig { year: Some(_), month: Some(1..=12), day: Some(1..=31), .. }) => date_pattern(object, property_identifier, pattern),
+		Some(_) => Err(ConfigError::Invalid(format!("{} {} {property_identifier} date ranges need a year, month and day at each end they give", object.object_type, object.instance)))
 	};
-	positive_integer_value
+	Ok(DateRange { start: end(range.start)?, end: end(range.end)? })
 }
 
-fn setup_network_port() -> ExampleDatabaseNetworkPort {
-	let mut network_port = ExampleDatabaseNetworkPort {
-		object_name: "Mitten NetworkPort".to_string(),
-		instance: 56,
-		bacnet_ip_udp_port: 47808,
-		ip_address: [198, 168, 68, 105],
-		ip_address_length: 6,
-		ip_default_gateway: [198, 168, 68, 126],
-		ip_default_gateway_length: 4,
-		ip_subnet_mask: [255, 255, 255, 0],
-		ip_subnet_mask_length: 4,
-		ip_dns_servers: Vec::new(),
-		ip_dns_server_length: 4,
-		broadcast_ip_address: [198, 168, 68, 105],
-		changes_pending: false,
-		fd_bbmd_address_host_type: 1,
-		fd_bbmd_address_host_ip: [198, 168, 68, 105],
-		fd_bbmd_address_port: 47809,
-		fd_subscription_lifetime: 3600
+fn date_pattern(object: &ObjectConfig, property_identifier: PropertyIdentifier, pattern: DatePatternConfig) -> Result<BacnetDate, ConfigError> {
+	let year = match pattern.year {
+		None => UNSPECIFIED,
+		Some(year @ 1900..=2154) => (year - 1900) as u8,
+		Some(year) => return Err(ConfigError::Invalid(format!("{} {} {property_identifier} year {year} is outside 1900-2154", object.object_type, object.instance)))
 	};
-	network_port
-}
-
-fn setup_date_time_value() -> ExampleDatabaseDateTimeValue {
-	let mut date_time_value = ExampleDatabaseDateTimeValue {
-		object_name: "Atlantic Rock DateTimeValue".to_string(),
-		instance: 44,
-		present_value_year: 122,
-		present_value_month: 1,
-		present_value_day: 28,
-		present_value_weekday: 5,
-		present_value_hour: 16,
-		present_value_minute: 54,
-		present_value_second: 47,
-		present_value_hundredth_seconds: 55
+	let date = BacnetDate { year, month: pattern.month.unwrap_or(UNSPECIFIED), day: pattern.day.unwrap_or(UNSPECIFIED), weekday: pattern.weekday.unwrap_or(UNSPECIFIED) };
+	if !date.is_valid() {
+		return Err(ConfigError::Invalid(format!("{} {} {property_identifier} has an invalid date {pattern:?}", object.object_type, object.instance)));
+	}
+	Ok(date)
+}
+
+// Days in a month from 1 to 12. February has 29 days when the year is unspecified.
+pub fn days_in_month(year: Option<u16>, month: u8) -> u8 {
+	match month {
+		2 => match year {
+			Some(year) if !(year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)) => 28,
+			_ => 29
+		},
+		4 | 6 | 9 | 11 => 30,
+		_ => 31
+	}
+}
+
+// BACnet day of week, 1 is Monday and 7 is Sunday
+fn day_of_week(year: u16, month: u8, day: u8) -> u8 {
+	const MONTH_OFFSETS: [u32; 12] = [0, 3, 2, 5, 0, 3, 5, 1, 4, 6, 2, 4];
+	let year = u32::from(year) - u32::from(month < 3);
+	let sunday_based = (year + year / 4 - year / 100 + year / 400 + MONTH_OFFSETS[usize::from(month - 1)] + u32::from(day)) % 7;
+	if sunday_based == 0 { 7 } else { sunday_based as u8 }
+}
+
+// Milliseconds since the Unix epoch of a date and time taken as UTC. None unless the date and time are
+// fully specified, dates before the epoch are clamped to it.
+pub fn date_time_to_millis(date: &BacnetDate, time: &BacnetTime) -> Option<u64> {
+	if date.year == UNSPECIFIED || !matches!(date.month, 1..=12) || !matches!(date.day, 1..=31) || time.hour == UNSPECIFIED || time.minute == UNSPECIFIED || time.second == UNSPECIFIED {
+		return None;
+	}
+	let days = days_from_civil(1900 + i64::from(date.year), date.month, date.day);
+	let hundredth_seconds = if time.hundredth_seconds == UNSPECIFIED { 0 } else { i64::from(time.hundredth_seconds) };
+	let millis = ((days * 24 + i64::from(time.hour)) * 60 + i64::from(time.minute)) * 60_000 + i64::from(time.second) * 1000 + hundredth_seconds * 10;
+	Some(millis.max(0) as u64)
+}
+
+// UTC date and time of milliseconds since the Unix epoch
+pub fn millis_to_date_time(millis: u64) -> (BacnetDate, BacnetTime) {
+	let days = (millis / 86_400_000) as i64;
+	let millis_of_day = millis % 86_400_000;
+	// Days since 0000-03-01, from Howard Hinnant's civil_from_days
+	let days = days + 719_468;
+	let era = days / 146_097;
+	let day_of_era = days - era * 146_097;
+	let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+	let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+	let shifted_month = (5 * day_of_year + 2) / 153;
+	let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u8;
+	let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 } as u8;
+	let year = (year_of_era + era * 400 + i64::from(month <= 2)) as u16;
+	let date = BacnetDate { year: year.saturating_sub(1900).min(254) as u8, month, day, weekday: day_of_week(year, month, day) };
+	let time = BacnetTime {
+		hour: (millis_of_day / 3_600_000) as u8,
+		minute: (millis_of_day / 60_000 % 60) as u8,
+		second: (millis_of_day / 1000 % 60) as u8,
+		hundredth_seconds: (millis_of_day / 10 % 100) as u8
 	};
-	date_time_value
+	(date, time)
+}
+
+// Days from 1970-01-01, from Howard Hinnant's days_from_civil
+fn days_from_civil(year: i64, month: u8, day: u8) -> i64 {
+	let year = if month <= 2 { year - 1 } else { year };
+	let era = year.div_euclid(400);
+	let year_of_era = year - era * 400;
+	let shifted_month = i64::from((month + 9) % 12);
+	let day_of_year = (153 * shifted_month + 2) / 5 + i64::from(day) - 1;
+	let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+	era * 146_097 + day_of_era - 719_468
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn text(text: &str) -> PropertyValue {
+		PropertyValue::CharacterString(text.to_string())
+	}
+
+	fn texts(texts: &[&str]) -> PropertyValue {
+		PropertyValue::Array(texts.iter().map(|text| PropertyValue::CharacterString(text.to_string())).collect())
+	}
+
+	fn state_set() -> StateSet {
+		StateSet { number_of_states: 3, state_text: vec!["off".to_string(), "low".to_string(), "high".to_string()] }
+	}
+
+	// An array of reals, as proprietary-513 of the example
+	fn proprietary_array(length: usize) -> ProprietaryProperties {
+		let property_identifier = PropertyIdentifier::try_from(513).unwrap();
+		ProprietaryProperties { properties: vec![ProprietaryProperty { property_identifier, data_type: DataType::Real, value: PropertyValue::Array(vec![PropertyValue::Real(1.0); length]) }] }
+	}
+
+	#[test]
+	fn only_binary_objects_reserve_priority_6() {
+		let mut analog = Commandable::new(0.0);
+		assert_eq!(analog.command(6, &PropertyValue::Real(1.0)), Ok(()));
+		assert_eq!(analog.present_value(), 1.0);
+		let mut multistate = Commandable::new(1);
+		assert_eq!(multistate.command(6, &PropertyValue::Unsigned(2)), Ok(()));
+		assert_eq!(multistate.present_value(), 2);
+		let mut binary = Commandable::new(false);
+		assert_eq!(binary.command(6, &PropertyValue::Enumerated(1)), Err(ErrorCode::WriteAccessDenied));
+		assert_eq!(binary.command(5, &PropertyValue::Enumerated(1)), Ok(()));
+		assert!(binary.present_value());
+	}
+
+	#[test]
+	fn array_index_0_is_the_length_and_elements_count_from_1() {
+		let elements = [10, 20, 30];
+		let to_value = |element: &u32| PropertyValue::Unsigned(*element);
+		assert_eq!(array_property(&elements, None, to_value), Ok(PropertyValue::Array(vec![PropertyValue::Unsigned(10), PropertyValue::Unsigned(20), PropertyValue::Unsigned(30)])));
+		assert_eq!(array_property(&elements, Some(0), to_value), Ok(PropertyValue::Unsigned(3)));
+		assert_eq!(array_property(&elements, Some(1), to_value), Ok(PropertyValue::Unsigned(10)));
+		assert_eq!(array_property(&elements, Some(3), to_value), Ok(PropertyValue::Unsigned(30)));
+		assert_eq!(array_property(&elements, Some(4), to_value), Err(ErrorCode::InvalidArrayIndex));
+		assert_eq!(array_property(&[] as &[u32], Some(0), to_value), Ok(PropertyValue::Unsigned(0)));
+	}
+
+	#[test]
+	fn fixed_arrays_are_written_by_element_or_whole() {
+		let mut states = state_set();
+		assert_eq!(states.set_property(PropertyIdentifier::StateText, Some(2), &text("medium")), Some(Ok(())));
+		assert_eq!(states.get_property(PropertyIdentifier::StateText, Some(2)), Some(Ok(text("medium"))));
+		assert_eq!(states.set_property(PropertyIdentifier::StateText, None, &texts(&["a", "b", "c"])), Some(Ok(())));
+		assert_eq!(states.get_property(PropertyIdentifier::StateText, None), Some(Ok(texts(&["a", "b", "c"]))));
+	}
+
+	#[test]
+	fn fixed_arrays_reject_other_sizes() {
+		let mut states = state_set();
+		assert_eq!(states.set_property(PropertyIdentifier::StateText, Some(4), &text("off")), Some(Err(ErrorCode::InvalidArrayIndex)));
+		assert_eq!(states.set_property(PropertyIdentifier::StateText, None, &texts(&["a", "b"])), Some(Err(ErrorCode::ValueOutOfRange)));
+		// The number of states fixes the size
+		assert_eq!(states.set_property(PropertyIdentifier::StateText, Some(0), &PropertyValue::Unsigned(4)), Some(Err(ErrorCode::WriteAccessDenied)));
+		assert_eq!(states.set_property(PropertyIdentifier::StateText, Some(1), &PropertyValue::Unsigned(4)), Some(Err(ErrorCode::InvalidDataType)));
+		assert_eq!(states.get_property(PropertyIdentifier::StateText, Some(0)), Some(Ok(PropertyValue::Unsigned(3))));
+		assert_eq!(states.get_property(PropertyIdentifier::NumberOfStates, Some(0)), Some(Err(ErrorCode::PropertyIsNotAnArray)));
+	}
+
+	#[test]
+	fn writing_index_0_resizes_a_proprietary_array() {
+		let property_identifier = PropertyIdentifier::try_from(513).unwrap();
+		let mut properties = proprietary_array(5);
+		assert_eq!(properties.set_property(property_identifier, Some(0), &PropertyValue::Unsigned(7)), Some(Ok(())));
+		assert_eq!(properties.get_property(property_identifier, Some(0)), Some(Ok(PropertyValue::Unsigned(7))));
+		assert_eq!(properties.get_property(property_identifier, Some(5)), Some(Ok(PropertyValue::Real(1.0))));
+		assert_eq!(properties.get_property(property_identifier, Some(7)), Some(Ok(PropertyValue::Real(0.0))));
+		assert_eq!(properties.set_property(property_identifier, Some(7), &PropertyValue::Real(9.0)), Some(Ok(())));
+		assert_eq!(properties.get_property(property_identifier, Some(7)), Some(Ok(PropertyValue::Real(9.0))));
+
+		// Shrinking drops the elements past the new size
+		assert_eq!(properties.set_property(property_identifier, Some(0), &PropertyValue::Unsigned(2)), Some(Ok(())));
+		assert_eq!(properties.get_property(property_identifier, None), Some(Ok(PropertyValue::Array(vec![PropertyValue::Real(1.0); 2]))));
+		assert_eq!(properties.get_property(property_identifier, Some(3)), Some(Err(ErrorCode::InvalidArrayIndex)));
+		assert_eq!(properties.set_property(property_identifier, Some(3), &PropertyValue::Real(9.0)), Some(Err(ErrorCode::InvalidArrayIndex)));
+	}
+
+	#[test]
+	fn a_whole_proprietary_array_is_written_at_its_own_size() {
+		let property_identifier = PropertyIdentifier::try_from(513).unwrap();
+		let mut properties = proprietary_array(5);
+		let values = PropertyValue::Array(vec![PropertyValue::Real(2.0), PropertyValue::Real(3.0)]);
+		assert_eq!(properties.set_property(property_identifier, None, &values), Some(Ok(())));
+		assert_eq!(properties.get_property(property_identifier, None), Some(Ok(values)));
+		// Only elements of its data type, and no larger than the limit
+		let mixed = PropertyValue::Array(vec![PropertyValue::Real(2.0), PropertyValue::Unsigned(3)]);
+		assert_eq!(properties.set_property(property_identifier, None, &mixed), Some(Err(ErrorCode::InvalidDataType)));
+		assert_eq!(properties.set_property(property_identifier, Some(0), &PropertyValue::Real(3.0)), Some(Err(ErrorCode::InvalidDataType)));
+		assert_eq!(properties.set_property(property_identifier, Some(0), &PropertyValue::Unsigned(MAX_ARRAY_LENGTH as u32 + 1)), Some(Err(ErrorCode::ValueOutOfRange)));
+		assert_eq!(properties.get_property(property_identifier, Some(0)), Some(Ok(PropertyValue::Unsigned(2))));
+	}
 }
 
 /*
 List of object names (species of Crabs):
 "Dungeness", "Snow", "Flower", "Chesapeake Blue", "Red King", "Pea", "Yeti", "Coconut", 
-"Japanese Spider", "Tanner", "Brown Box", "Strawberry", "Chinese Mitten", "Atlantic Rock"
+"Japanese Spider", "Tanner", "Brown Box", "Strawberry", "Chinese Mitten", "Atlantic Rock",
+"Fiddler", "Hermit", "Ghost", "Coral", "Porcelain", "Sally Lightfoot", "Mud", "Spanner", "Decorator", "Arrow", "Pom-pom", "Kelp"
 */
\ No newline at end of file
@@ -0,0 +1,235 @@
+// Intrinsic reporting of analog and binary inputs. The event state is computed from the object's
+// present value and reliability, the stack sends the notifications through its notification class.
+
+use crate::bacnet_types::{ErrorCode, EventState, EventTransitions, NotifyType, PropertyIdentifier, Reliability};
+use crate::database::PropertyValue;
+
+// The condition that takes the monitored object out of its normal state
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EventAlgorithm {
+	// OUT_OF_RANGE, for analog inputs. Limit_Enable holds the low limit bit first.
+	OutOfRange { high_limit: f32, low_limit: f32, deadband: f32, low_limit_enable: bool, high_limit_enable: bool },
+	// CHANGE_OF_STATE, for binary inputs
+	ChangeOfState { alarm_value: bool }
+}
+
+pub struct IntrinsicReporting {
+	pub notification_class: u32,
+	pub notify_type: NotifyType,
+	pub event_enable: EventTransitions,
+	// Seconds the condition for a transition must hold before the event state changes
+	pub time_delay: u32,
+	pub algorithm: EventAlgorithm,
+	pub event_state: EventState,
+	// The event state the monitored value calls for, and since when in milliseconds since the Unix epoch
+	pending: Option<(EventState, u64)>
+}
+
+impl IntrinsicReporting {
+	pub fn new(notification_class: u32, notify_type: NotifyType, event_enable: EventTransitions, time_delay: u32, algorithm: EventAlgorithm) -> IntrinsicReporting {
+		IntrinsicReporting { notification_class, notify_type, event_enable, time_delay, algorithm, event_state: EventState::Normal, pending: None }
+	}
+
+	// The event state called for by the present value and reliability, and whether the change is
+	// made without waiting for the time delay
+	fn target_state(&self, present_value: &PropertyValue, reliability: Reliability) -> (EventState, bool) {
+		if reliability != Reliability::NoFaultDetected {
+			return (EventState::Fault, true);
+		}
+		if self.event_state == EventState::Fault {
+			return (EventState::Normal, true);
+		}
+		match (self.algorithm, present_value) {
+			(EventAlgorithm::OutOfRange { high_limit, low_limit, deadband, low_limit_enable, high_limit_enable }, PropertyValue::Real(value)) => match self.event_state {
+				EventState::HighLimit if !high_limit_enable => (EventState::Normal, true),
+				EventState::LowLimit if !low_limit_enable => (EventState::Normal, true),
+				EventState::HighLimit if low_limit_enable && *value < low_limit => (EventState::LowLimit, false),
+				EventState::HighLimit if *value < high_limit - deadband => (EventState::Normal, false),
+				EventState::LowLimit if high_limit_enable && *value > high_limit => (EventState::HighLimit, false),
+				EventState::LowLimit if *value > low_limit + deadband => (EventState::Normal, false),
+				EventState::HighLimit | EventState::LowLimit => (self.event_state, false),
+				_ if high_limit_enable && *value > high_limit => (EventState::HighLimit, false),
+				_ if low_limit_enable && *value < low_limit => (EventState::LowLimit, false),
+				_ => (EventState::Normal, false)
+			},
+			(EventAlgorithm::ChangeOfState { alarm_value }, PropertyValue::Enumerated(value)) if (*value == 1) == alarm_value => (EventState::Offnormal, false),
+			_ => (EventState::Normal, false)
+		}
+	}
+
+	// Evaluates the monitored present value at now, in milliseconds since the Unix epoch. Returns the
+	// new event state when it changes.
+	pub fn update(&mut self, now: u64, present_value: &PropertyValue, reliability: Reliability) -> Option<EventState> {
+		let (target, immediate) = self.target_state(present_value, reliability);
+		if target == self.event_state {
+			self.pending = None;
+			return None;
+		}
+		let since = match self.pending {
+			Some((pending, since)) if pending == target => since,
+			_ => now
+		};
+		if !immediate && now.saturating_sub(since) < u64::from(self.time_delay) * 1000 {
+			self.pending = Some((target, since));
+			return None;
+		}
+		self.pending = None;
+		self.event_state = target;
+		Some(target)
+	}
+
+	// Reads the intrinsic reporting properties, None for any other property
+	pub fn get_property(&self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>) -> Option<Result<PropertyValue, ErrorCode>> {
+		let value = match (property_identifier, self.algorithm) {
+			(PropertyIdentifier::NotificationClass, _) => PropertyValue::Unsigned(self.notification_class),
+			(PropertyIdentifier::NotifyType, _) => PropertyValue::Enumerated(self.notify_type.into()),
+			(PropertyIdentifier::EventEnable, _) => PropertyValue::BitString(self.event_enable.to_bits()),
+			(PropertyIdentifier::TimeDelay, _) => PropertyValue::Unsigned(self.time_delay),
+			(PropertyIdentifier::EventState, _) => PropertyValue::Enumerated(self.event_state.into()),
+			(PropertyIdentifier::HighLimit, EventAlgorithm::OutOfRange { high_limit, .. }) => PropertyValue::Real(high_limit),
+			(PropertyIdentifier::LowLimit, EventAlgorithm::OutOfRange { low_limit, .. }) => PropertyValue::Real(low_limit),
+			(PropertyIdentifier::Deadband, EventAlgorithm::OutOfRange { deadband, .. }) => PropertyValue::Real(deadband),
+			(PropertyIdentifier::LimitEnable, EventAlgorithm::OutOfRange { low_limit_enable, high_limit_enable, .. }) => PropertyValue::BitString(vec![low_limit_enable, high_limit_enable]),
+			(PropertyIdentifier::AlarmValue, EventAlgorithm::ChangeOfState { alarm_value }) => PropertyValue::Enumerated(u32::from(alarm_value)),
+			_ => return None
+		};
+		match property_array_index {
+			Some(_) => Some(Err(ErrorCode::PropertyIsNotAnArray)),
+			None => Some(Ok(value))
+		}
+	}
+
+	// Writes the intrinsic reporting properties, None for any other property. The event state is
+	// only changed by the algorithm.
+	pub fn set_property(&mut self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>, value: &PropertyValue) -> Option<Result<(), ErrorCode>> {
+		let current = self.get_property(property_identifier, property_array_index)?;
+		Some(current.and_then(|_| self.write(property_identifier, value)))
+	}
+
+	fn write(&mut self, property_identifier: PropertyIdentifier, value: &PropertyValue) -> Result<(), ErrorCode> {
+		match (property_identifier, &mut self.algorithm, value) {
+			(PropertyIdentifier::NotificationClass, _, PropertyValue::Unsigned(notification_class)) => self.notification_class = *notification_class,
+			(PropertyIdentifier::NotifyType, _, PropertyValue::Enumerated(0)) => self.notify_type = NotifyType::Alarm,
+			(PropertyIdentifier::NotifyType, _, PropertyValue::Enumerated(1)) => self.notify_type = NotifyType::Event,
+			(PropertyIdentifier::NotifyType, _, PropertyValue::Enumerated(_)) => return Err(ErrorCode::ValueOutOfRange),
+			(PropertyIdentifier::EventEnable, _, PropertyValue::BitString(bits)) => self.event_enable = EventTransitions::from_bits(bits)?,
+			(PropertyIdentifier::TimeDelay, _, PropertyValue::Unsigned(time_delay)) => self.time_delay = *time_delay,
+			(PropertyIdentifier::EventState, _, _) => return Err(ErrorCode::WriteAccessDenied),
+			(PropertyIdentifier::HighLimit, EventAlgorithm::OutOfRange { high_limit, .. }, PropertyValue::Real(value)) => *high_limit = *value,
+			(PropertyIdentifier::LowLimit, EventAlgorithm::OutOfRange { low_limit, .. }, PropertyValue::Real(value)) => *low_limit = *value,
+			(PropertyIdentifier::Deadband, EventAlgorithm::OutOfRange { .. }, PropertyValue::Real(value)) if *value < 0.0 => return Err(ErrorCode::ValueOutOfRange),
+			(PropertyIdentifier::Deadband, EventAlgorithm::OutOfRange { deadband, .. }, PropertyValue::Real(value)) => *deadband = *value,
+			(PropertyIdentifier::LimitEnable, EventAlgorithm::OutOfRange { low_limit_enable, high_limit_enable, .. }, PropertyValue::BitString(bits)) => match **bits {
+				[low, high] => (*low_limit_enable, *high_limit_enable) = (low, high),
+				_ => return Err(ErrorCode::ValueOutOfRange)
+			},
+			(PropertyIdentifier::AlarmValue, EventAlgorithm::ChangeOfState { alarm_value }, PropertyValue::Enumerated(value @ (0 | 1))) => *alarm_value = *value == 1,
+			(PropertyIdentifier::AlarmValue, _, PropertyValue::Enumerated(_)) => return Err(ErrorCode::ValueOutOfRange),
+			_ => return Err(ErrorCode::InvalidDataType)
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const ALL_TRANSITIONS: EventTransitions = EventTransitions { to_offnormal: true, to_fault: true, to_normal: true };
+
+	// Out of range above 20 or below 0 with a deadband of 1 and a time delay of 10 seconds, as the
+	// example analog input
+	fn out_of_range(low_limit_enable: bool, high_limit_enable: bool) -> IntrinsicReporting {
+		let algorithm = EventAlgorithm::OutOfRange { high_limit: 20.0, low_limit: 0.0, deadband: 1.0, low_limit_enable, high_limit_enable };
+		IntrinsicReporting::new(1, NotifyType::Alarm, ALL_TRANSITIONS, 10, algorithm)
+	}
+
+	fn step(intrinsic_reporting: &mut IntrinsicReporting, seconds: f64, value: f32) -> Option<EventState> {
+		intrinsic_reporting.update((seconds * 1000.0) as u64, &PropertyValue::Real(value), Reliability::NoFaultDetected)
+	}
+
+	#[test]
+	fn the_high_limit_is_reported_after_the_time_delay() {
+		let mut reporting = out_of_range(true, true);
+		// At the limit is still normal
+		assert_eq!(step(&mut reporting, 0.0, 20.0), None);
+		assert_eq!(step(&mut reporting, 1.0, 20.5), None);
+		assert_eq!(step(&mut reporting, 10.9, 21.0), None);
+		assert_eq!(reporting.event_state, EventState::Normal);
+		assert_eq!(step(&mut reporting, 11.0, 21.0), Some(EventState::HighLimit));
+		assert_eq!(step(&mut reporting, 12.0, 21.0), None);
+	}
+
+	#[test]
+	fn a_condition_that_clears_within_the_time_delay_starts_over() {
+		let mut reporting = out_of_range(true, true);
+		assert_eq!(step(&mut reporting, 0.0, 21.0), None);
+		assert_eq!(step(&mut reporting, 5.0, 19.0), None);
+		assert_eq!(step(&mut reporting, 6.0, 21.0), None);
+		assert_eq!(step(&mut reporting, 15.0, 21.0), None);
+		assert_eq!(step(&mut reporting, 16.0, 21.0), Some(EventState::HighLimit));
+	}
+
+	#[test]
+	fn the_high_limit_returns_to_normal_below_the_deadband() {
+		let mut reporting = out_of_range(true, true);
+		step(&mut reporting, 0.0, 21.0);
+		step(&mut reporting, 10.0, 21.0);
+		assert_eq!(reporting.event_state, EventState::HighLimit);
+		// Back under the limit but within the deadband, and at high_limit - deadband
+		assert_eq!(step(&mut reporting, 11.0, 19.5), None);
+		assert_eq!(step(&mut reporting, 30.0, 19.0), None);
+		assert_eq!(reporting.event_state, EventState::HighLimit);
+		assert_eq!(step(&mut reporting, 31.0, 18.9), None);
+		assert_eq!(step(&mut reporting, 40.9, 18.9), None);
+		assert_eq!(step(&mut reporting, 41.0, 18.9), Some(EventState::Normal));
+	}
+
+	#[test]
+	fn the_low_limit_mirrors_the_high_limit() {
+		let mut reporting = out_of_range(true, true);
+		assert_eq!(step(&mut reporting, 0.0, -0.5), None);
+		assert_eq!(step(&mut reporting, 10.0, -0.5), Some(EventState::LowLimit));
+		assert_eq!(step(&mut reporting, 11.0, 1.0), None);
+		assert_eq!(step(&mut reporting, 30.0, 1.0), None);
+		// Straight to the high limit, after its own time delay
+		assert_eq!(step(&mut reporting, 31.0, 25.0), None);
+		assert_eq!(step(&mut reporting, 41.0, 25.0), Some(EventState::HighLimit));
+	}
+
+	#[test]
+	fn disabled_limits_are_not_reported() {
+		let mut reporting = out_of_range(true, false);
+		assert_eq!(step(&mut reporting, 0.0, 25.0), None);
+		assert_eq!(step(&mut reporting, 60.0, 25.0), None);
+		assert_eq!(reporting.event_state, EventState::Normal);
+
+		// Disabling the limit in effect returns to normal at once
+		let mut reporting = out_of_range(true, true);
+		step(&mut reporting, 0.0, 25.0);
+		step(&mut reporting, 10.0, 25.0);
+		let limit_enable = PropertyValue::BitString(vec![true, false]);
+		assert_eq!(reporting.set_property(PropertyIdentifier::LimitEnable, None, &limit_enable), Some(Ok(())));
+		assert_eq!(step(&mut reporting, 11.0, 25.0), Some(EventState::Normal));
+	}
+
+	#[test]
+	fn faults_are_reported_without_the_time_delay() {
+		let mut reporting = out_of_range(true, true);
+		assert_eq!(reporting.update(0, &PropertyValue::Real(25.0), Reliability::OverRange), Some(EventState::Fault));
+		assert_eq!(reporting.update(1000, &PropertyValue::Real(25.0), Reliability::NoFaultDetected), Some(EventState::Normal));
+		// The high limit then waits for its time delay
+		assert_eq!(step(&mut reporting, 2.0, 25.0), None);
+		assert_eq!(step(&mut reporting, 12.0, 25.0), Some(EventState::HighLimit));
+	}
+
+	#[test]
+	fn change_of_state_reports_the_alarm_value() {
+		let mut reporting = IntrinsicReporting::new(1, NotifyType::Alarm, ALL_TRANSITIONS, 30, EventAlgorithm::ChangeOfState { alarm_value: true });
+		let active = PropertyValue::Enumerated(1);
+		assert_eq!(reporting.update(0, &active, Reliability::NoFaultDetected), None);
+		assert_eq!(reporting.update(30_000, &active, Reliability::NoFaultDetected), Some(EventState::Offnormal));
+		assert_eq!(reporting.update(31_000, &PropertyValue::Enumerated(0), Reliability::NoFaultDetected), None);
+		assert_eq!(reporting.update(61_000, &PropertyValue::Enumerated(0), Reliability::NoFaultDetected), Some(EventState::Normal));
+	}
+}
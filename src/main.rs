@@ -1,18 +1,37 @@
+pub mod bacnet_types;
 pub mod cas_bacnet_stack_example_constants;
 pub mod database;
 pub mod cas_bacnet_stack_adapters;
+pub mod cas_bacnet_stack_mock;
+pub mod config;
+pub mod persistence;
+pub mod trend_log;
+pub mod intrinsic_reporting;
+pub mod schedule;
+pub mod pid;
+pub mod clock;
+pub mod read_range;
 
 use cas_bacnet_stack_example_constants as bacnet_const;
 use cas_bacnet_stack_adapters as adapter;
-use crate::database::ExampleDatabaseObject;
+use cas_bacnet_stack_adapters::{AdapterError, BacnetStack};
+use bacnet_types::{CharacterSet, ErrorCode, EventState, ObjectType, PropertyIdentifier, ServiceSupported};
+use cas_bacnet_stack_mock::MockBacnetStack;
+use clock::DeviceClock;
+use config::DatabaseConfig;
+use persistence::{Journal, JournalRecord};
+use crate::database::{BacnetDate, BacnetObject, BacnetTime, Database, ExampleDatabaseObject, ObjectKey, PropertyValue, ReferenceWrite};
 
 use std::net::UdpSocket;
 
-use std::collections::HashMap;
-
 use once_cell::sync::Lazy;
+use once_cell::sync::OnceCell;
 use std::sync::Mutex;
 use std::sync::MutexGuard;
+use std::sync::PoisonError;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use std::collections::HashMap;
 
 use std::time::SystemTime;
 use std::time::Duration;
@@ -26,6 +45,7 @@ use std::thread;
 use std::os::raw::c_char;
 
 use std::io;
+use std::process;
 use std::sync::mpsc;
 use std::sync::mpsc::Receiver;
 
@@ -34,11 +54,11 @@ const APPLICATION_VERSION: &str = "0.0.1";  // See CHANGELOG.md for a full list
 const MAX_RENDER_BUFFER_LENGTH: usize = 1497;
 
 // Static Variables
-static socket: Lazy<UdpSocket> = Lazy::new(|| {
-    match UdpSocket::bind("192.168.68.109:47808") {
+static SOCKET: Lazy<UdpSocket> = Lazy::new(|| {
+	match UdpSocket::bind("192.168.68.109:47808") {
 		Ok(udp_socket) => {
 			println!("UDP Socket Setup Success");
-			if let Err(err) = udp_socket.set_read_timeout(Some(Duration::from_millis(50))) {
+			if udp_socket.set_read_timeout(Some(Duration::from_millis(50))).is_err() {
 				panic!("UDP Socket Read Timeout Setting Failed");
 			}
 			udp_socket
@@ -49,165 +69,351 @@ static socket: Lazy<UdpSocket> = Lazy::new(|| {
 	}
 });
 
-static db: Lazy<Mutex<HashMap<String, ExampleDatabaseObject>>> = Lazy::new(|| {
-	let mut database = database::setup_database();
-	Mutex::new(database)
+static DB: Lazy<Mutex<Database>> = Lazy::new(|| {
+	Mutex::new(Database::new())
 });
 
+// A panic caught in a callback may have poisoned the lock, the database itself is still usable
+fn lock_database() -> MutexGuard<'static, Database> {
+	DB.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+// Journal of written values, None when persistence is disabled
+static JOURNAL: Lazy<Mutex<Option<Journal>>> = Lazy::new(|| {
+	Mutex::new(None)
+});
+
+fn lock_journal() -> MutexGuard<'static, Option<Journal>> {
+	JOURNAL.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+// The configured character set of each string property, set once at startup
+static CHARACTER_SETS: OnceCell<HashMap<(ObjectKey, PropertyIdentifier), CharacterSet>> = OnceCell::new();
+
+// Objects whose event-enable, notification-class or notify-type was written since the main loop last
+// passed them on to the stack. Callbacks have no stack to call.
+static INTRINSIC_REPORTING_CHANGES: Mutex<Vec<ObjectKey>> = Mutex::new(Vec::new());
+
+// Whether the last received message was a UTCTimeSynchronization. The stack handles each message
+// before receiving the next, and its set system time callback does not say which service called it.
+static UTC_TIME_SYNCHRONIZATION: AtomicBool = AtomicBool::new(false);
+
+// The character set a string property is read in, UTF-8 unless configured
+fn character_set(device_instance: u32, object_type: u16, object_instance: u32, property_identifier: u32) -> CharacterSet {
+	let key = ObjectKey::from_raw(device_instance, object_type, object_instance);
+	let property_identifier = PropertyIdentifier::try_from(property_identifier).ok();
+	key.zip(property_identifier)
+		.and_then(|key| CHARACTER_SETS.get()?.get(&key).copied())
+		.unwrap_or(CharacterSet::Utf8)
+}
+
+// The character set of each string property listed in the config
+fn configured_character_sets(config: &DatabaseConfig) -> HashMap<(ObjectKey, PropertyIdentifier), CharacterSet> {
+	let device = (ObjectKey::device(config.device.instance), &config.device.character_sets);
+	let objects = config.objects.iter().map(|object| (ObjectKey::new(config.device.instance, object.object_type, object.instance), &object.character_sets));
+	std::iter::once(device).chain(objects)
+		.flat_map(|(key, character_sets)| character_sets.iter().map(move |(&property_identifier, &character_set)| ((key, property_identifier), character_set)))
+		.collect()
+}
+
 // Main function
 fn main() {
+	// Resolve configuration from the command line, environment and config file
+	let command_line = match config::parse_command_line() {
+		Ok(command_line) => command_line,
+		Err(err) => {
+			eprintln!("{err}");
+			process::exit(2);
+		}
+	};
+	if command_line.help {
+		println!("{}", config::USAGE);
+		return;
+	}
+	let (database_config, library_options) = match config::load(&command_line) {
+		Ok(loaded) => loaded,
+		Err(err) => {
+			eprintln!("{err}");
+			process::exit(2);
+		}
+	};
+	let mut database = match database::setup_database(&database_config) {
+		Ok(database) => database,
+		Err(err) => {
+			eprintln!("{err}");
+			process::exit(2);
+		}
+	};
+
+	// Restore values written before the last restart
+	if let Some(journal_path) = &database_config.journal {
+		match persistence::restore(journal_path, &database_config, &mut database) {
+			Ok(journal) => *lock_journal() = Some(journal),
+			Err(err) => {
+				eprintln!("{err}");
+				process::exit(1);
+			}
+		}
+	}
+	*lock_database() = database;
+	let _ = CHARACTER_SETS.set(configured_character_sets(&database_config));
+
+	if command_line.mock_stack {
+		// Run against the in-process mock stack, no library or network is used
+		println!("Using the mock BACnet stack");
+		let stack = MockBacnetStack::new();
+		if let Err(err) = setup_bacnet_stack(&stack, &database_config) {
+			eprintln!("ERROR: {err}");
+			process::exit(1);
+		}
+		for (device_instance, object_type, object_instance) in stack.objects() {
+			let object_name = stack.get_property_character_string(device_instance, object_type, object_instance, PropertyIdentifier::ObjectName, None);
+			println!("Mock object {object_type}:{object_instance} Object_Name: {object_name:?}");
+		}
+		run_main_loop(&stack, database_config.device.instance);
+		return;
+	}
+
+	// Load the CAS BACnet Stack library and resolve its functions
+	let stack = match adapter::BacnetStackApi::load(&library_options) {
+		Ok(stack) => stack,
+		Err(err) => {
+			eprintln!("{err}");
+			process::exit(1);
+		}
+	};
+	println!("CAS BACnet Stack library: {}", stack.library_path().display());
+
 	// Print versioning
 	println!("CAS BACnet Stack Version: {:?}.{:?}.{:?}.{:?}", 
-        adapter::get_api_major_version().unwrap(), adapter::get_api_minor_version().unwrap(), adapter::get_api_patch_version().unwrap(), adapter::get_api_build_version().unwrap());
-    println!("Application Version: {:?}", APPLICATION_VERSION);
+		stack.get_api_major_version(), stack.get_api_minor_version(), stack.get_api_patch_version(), stack.get_api_build_version());
+	println!("Application Version: {:?}", APPLICATION_VERSION);
 
-	// Loading CAS BACnet Stack functions
-	if let Err(err) = load_bacnet_functions() {
-		panic!("Unable to load functions from DLL: {:?}", err);
+	if let Err(err) = setup_bacnet_stack(&stack, &database_config) {
+		eprintln!("ERROR: {err}");
+		process::exit(1);
 	}
+	run_main_loop(&stack, database_config.device.instance);
+}
+
+fn setup_bacnet_stack(stack: &dyn BacnetStack, config: &DatabaseConfig) -> Result<(), AdapterError> {
+	// Loading CAS BACnet Stack functions
+	load_bacnet_functions(stack);
 
 	// Print device instance
-	let device_instance;
-	if let Some(ExampleDatabaseObject::Device(device)) = db.lock().unwrap().get("device-389001") {
-		println!("Device Instance: {:?}", device.instance);
-		device_instance = device.instance;
-	} else {
-		println!("Unable to get device instance. Manually setting to 389000.");
-		device_instance = 389000;
-	}
-    // SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_secs();
+	let device_instance = config.device.instance;
+	println!("Device Instance: {:?}", device_instance);
 
 	// Add device and properties
-	if let Ok(x) = adapter::add_device(device_instance) {
-		if x {
-			println!("Device added");
-		} else {
-			println!("ERROR: Device was unable to be added");
-		}
-	} else {
-		println!("ERROR: Add Device failed");
+	stack.add_device(device_instance)?;
+	println!("Device added");
+	for &property_identifier in &config.device.enabled_properties {
+		stack.set_property_enabled(device_instance, ObjectType::Device, device_instance, property_identifier, true)?;
+		println!("Device {property_identifier} enabled");
 	}
-	
-	if let Ok(x) = adapter::set_property_enabled(device_instance, bacnet_const::OBJECT_TYPE_DEVICE, device_instance, bacnet_const::PROPERTY_IDENTIFIER_DESCRIPTION, true) {
-		if x {
-			println!("Device description added");
-		} else {
-			println!("ERROR: Device description was unable to be added");
-		}
-	} else {
-		println!("ERROR: Set Property Enabled failed");
+	for &property_identifier in &config.device.writable_properties {
+		stack.set_property_writable(device_instance, ObjectType::Device, device_instance, property_identifier, true)?;
+		println!("Device {property_identifier} writable");
 	}
 
 	// Set services enabled
-	if let Ok(x) = adapter::set_service_enabled(device_instance, bacnet_const::SERVICE_I_AM.into(), true) {
-		if x {
-			println!("I Am service enabled");
-		} else {
-			println!("ERROR: I Am service was unable to be enabled");
-		}
-	} else {
-		println!("ERROR: Enable service failed");
-	}
-	if let Ok(x) = adapter::set_service_enabled(device_instance, bacnet_const::SERVICE_READ_PROPERTY_MULTIPLE.into(), true) {
-		if x {
-			println!("Read Property Multiple service enabled");
-		} else {
-			println!("ERROR: Read Property Multiple service was unable to be enabled");
-		}
-	} else {
-		println!("ERROR: Enable service failed");
+	stack.set_service_enabled(device_instance, ServiceSupported::IAm, true)?;
+	println!("I Am service enabled");
+	stack.set_service_enabled(device_instance, ServiceSupported::ReadPropertyMultiple, true)?;
+	println!("Read Property Multiple service enabled");
+	stack.set_service_enabled(device_instance, ServiceSupported::WriteProperty, true)?;
+	println!("Write Property service enabled");
+	stack.set_service_enabled(device_instance, ServiceSupported::ReadRange, true)?;
+	println!("Read Range service enabled");
+	stack.set_service_enabled(device_instance, ServiceSupported::AcknowledgeAlarm, true)?;
+	println!("Acknowledge Alarm service enabled");
+	stack.set_service_enabled(device_instance, ServiceSupported::GetEventInformation, true)?;
+	println!("Get Event Information service enabled");
+	stack.set_service_enabled(device_instance, ServiceSupported::TimeSynchronization, true)?;
+	println!("Time Synchronization service enabled");
+	stack.set_service_enabled(device_instance, ServiceSupported::UtcTimeSynchronization, true)?;
+	println!("UTC Time Synchronization service enabled");
+	// A device that can be synchronized reports its clock
+	for property_identifier in [PropertyIdentifier::LocalDate, PropertyIdentifier::LocalTime, PropertyIdentifier::UtcOffset, PropertyIdentifier::DaylightSavingsStatus] {
+		stack.set_property_enabled(device_instance, ObjectType::Device, device_instance, property_identifier, true)?;
 	}
+	println!("Device clock properties enabled");
 
-	// Add object
-	// Object instance is hard-coded
-	// ANALOG INPUT
-	if let Ok(x) = adapter::add_object(device_instance, bacnet_const::OBJECT_TYPE_ANALOG_INPUT, 0) {
-		if x {
-			println!("Analog Input added");
-		} else {
-			println!("ERROR: Analog Input was unable to be added");
+	// Add objects and their optional properties
+	for object in config.objects.iter().filter(|object| object.serve) {
+		match object.object_type {
+			ObjectType::NotificationClass => add_notification_class(stack, device_instance, object.instance)?,
+			_ => stack.add_object(device_instance, object.object_type, object.instance)?
 		}
-	} else {
-		println!("ERROR: Add Analog Input failed");
-	}
-	if let Ok(x) = adapter::set_property_by_object_type_enabled(device_instance, bacnet_const::OBJECT_TYPE_ANALOG_INPUT, bacnet_const::PROPERTY_IDENTIFIER_DESCRIPTION, true) {
-		if x {
-			println!("Analog Input description added");
-		} else {
-			println!("ERROR: Analog Input description was unable to be added");
+		println!("{} {} added", object.object_type, object.instance);
+		for &property_identifier in &object.enabled_properties {
+			stack.set_property_enabled(device_instance, object.object_type, object.instance, property_identifier, true)?;
+			println!("{} {} {property_identifier} enabled", object.object_type, object.instance);
+		}
+		for &property_identifier in &object.writable_properties {
+			stack.set_property_writable(device_instance, object.object_type, object.instance, property_identifier, true)?;
+			println!("{} {} {property_identifier} writable", object.object_type, object.instance);
+		}
+		for property in &object.proprietary_properties {
+			stack.set_proprietary_property(device_instance, object.object_type, object.instance, property.identifier, property.array, property.data_type, property.writable)?;
+			println!("{} {} {} added", object.object_type, object.instance, property.identifier);
 		}
-	} else {
-		println!("ERROR: Set Property Enabled failed");
 	}
-	if let Ok(x) = adapter::set_property_by_object_type_enabled(device_instance, bacnet_const::OBJECT_TYPE_ANALOG_INPUT.into(), bacnet_const::PROPERTY_IDENTIFIER_RELIABILITY.into(), true) {
-		if x {
-			println!("Analog Input reliability added");
-		} else {
-			println!("ERROR: Analog Input reliability was unable to be added");
+
+	// Intrinsic reporting, once every notification class is added
+	for object in config.objects.iter().filter(|object| object.serve) {
+		if enable_intrinsic_reporting(stack, ObjectKey::new(device_instance, object.object_type, object.instance))? {
+			println!("{} {} intrinsic reporting enabled", object.object_type, object.instance);
 		}
-	} else {
-		println!("ERROR: Set Property Enabled failed");
 	}
+	Ok(())
+}
+
+// Passes an object's intrinsic reporting settings to the stack. False for an object without them.
+fn enable_intrinsic_reporting(stack: &dyn BacnetStack, key: ObjectKey) -> Result<bool, AdapterError> {
+	let intrinsic_reporting = lock_database().get(&key)
+		.and_then(ExampleDatabaseObject::intrinsic_reporting)
+		.map(|intrinsic_reporting| (intrinsic_reporting.notification_class, intrinsic_reporting.notify_type, intrinsic_reporting.event_enable));
+	let Some((notification_class, notify_type, event_enable)) = intrinsic_reporting else {
+		return Ok(false);
+	};
+	stack.enable_alarms_and_events_for_object(key.device_instance, key.object_type, key.object_instance, notification_class, notify_type, event_enable, true)?;
+	Ok(true)
+}
 
-	// CHARACTERSTRING VALUE
-	if let Ok(x) = adapter::add_object(device_instance, bacnet_const::OBJECT_TYPE_CHARACTERSTRING_VALUE, 40) {
-		if x {
-			println!("Characterstring Value added");
-		} else {
-			println!("ERROR: Characterstring Value was unable to be added");
+// Passes the intrinsic reporting settings written by clients on to the stack
+fn update_intrinsic_reporting(stack: &dyn BacnetStack) {
+	let keys = std::mem::take(&mut *INTRINSIC_REPORTING_CHANGES.lock().unwrap_or_else(PoisonError::into_inner));
+	for key in keys {
+		if let Err(err) = enable_intrinsic_reporting(stack, key) {
+			println!("WARNING: {} {} intrinsic reporting was not updated: {err}", key.object_type, key.object_instance);
 		}
-	} else {
-		println!("ERROR: Add Characterstring Value failed");
 	}
+}
+
+fn add_notification_class(stack: &dyn BacnetStack, device_instance: u32, object_instance: u32) -> Result<(), AdapterError> {
+	let notification_class = match lock_database().get(&ObjectKey::new(device_instance, ObjectType::NotificationClass, object_instance)) {
+		Some(ExampleDatabaseObject::NotificationClass(notification_class)) => Some((notification_class.priority, notification_class.ack_required)),
+		_ => None
+	};
+	let Some((priority, ack_required)) = notification_class else {
+		return stack.add_object(device_instance, ObjectType::NotificationClass, object_instance);
+	};
+	stack.add_notification_class_object(device_instance, object_instance, priority, ack_required)
+}
 
+fn run_main_loop(stack: &dyn BacnetStack, device_instance: u32) {
 	// Main Loop
 	println!("Entering main loop...");
 	println!("FYI: To quit the application, enter Q");
 	let mut update_once_a_second_timer = SystemTime::now();
 	let stdin_channel = spawn_stdin_channel();
-    loop {
+	loop {
 		// Bacnet Loop
-		adapter::bacnet_loop().unwrap();
+		stack.bacnet_loop();
+		update_intrinsic_reporting(stack);
 		// Check if we want to update analog input
-		database_loop(&mut update_once_a_second_timer);
+		let updates = database_loop(device_instance, &mut update_once_a_second_timer);
+		for (key, event_state) in updates.event_states {
+			println!("{} {} event state {event_state}", key.object_type, key.object_instance);
+			stack.value_updated(key.device_instance, key.object_type, key.object_instance, PropertyIdentifier::EventState);
+			stack.value_updated(key.device_instance, key.object_type, key.object_instance, PropertyIdentifier::StatusFlags);
+		}
+		for key in updates.present_values {
+			stack.value_updated(key.device_instance, key.object_type, key.object_instance, PropertyIdentifier::PresentValue);
+		}
+		for write in updates.writes {
+			let (source, target) = (write.source, write.target);
+			match write.result {
+				Ok(()) => {
+					println!("{} {} wrote {:?} to {} {} {}", source.object_type, source.object_instance, write.value, target.object_type, target.object_instance, write.property_identifier);
+					stack.value_updated(target.device_instance, target.object_type, target.object_instance, write.property_identifier);
+				},
+				Err(error_code) => println!("WARNING: {} {} could not write {:?} to {} {} {}: {error_code}", source.object_type, source.object_instance, write.value, target.object_type, target.object_instance, write.property_identifier)
+			}
+		}
 		// Check for key input
 		if let Ok(key) = stdin_channel.try_recv() {
-            if check_end_loop(&key) {
+			if check_end_loop(&key) {
 				break;
 			}
-        }
+		}
 		thread::sleep(Duration::from_millis(0));
-    }
+	}
 }
 
-fn load_bacnet_functions() -> Result<bool, Box<dyn std::error::Error>> {
-	adapter::register_callback_receive_message(callback_receive_message)?;
-	adapter::register_callback_send_message(callback_send_message)?;
-	adapter::register_callback_get_system_time(callback_get_system_time)?;
-	adapter::register_callback_get_property_character_string(callback_get_character_string)?;
-	adapter::register_callback_get_property_enumerated(callback_get_enumerated)?;
-	adapter::register_callback_get_property_real(callback_get_real)?;
-	adapter::register_callback_get_property_signed_integer(callback_get_signed_integer)?;
-	Ok(true)
+fn load_bacnet_functions(stack: &dyn BacnetStack) {
+	stack.register_callback_receive_message(callback_receive_message);
+	stack.register_callback_send_message(callback_send_message);
+	stack.register_callback_get_system_time(callback_get_system_time);
+	stack.register_callback_set_system_time(callback_set_system_time);
+	stack.register_callback_get_property_bitstring(callback_get_bitstring);
+	stack.register_callback_get_property_bool(callback_get_bool);
+	stack.register_callback_get_property_character_string(callback_get_character_string);
+	stack.register_callback_get_property_date(callback_get_date);
+	stack.register_callback_get_property_double(callback_get_double);
+	stack.register_callback_get_property_enumerated(callback_get_enumerated);
+	stack.register_callback_get_property_octet_string(callback_get_octet_string);
+	stack.register_callback_get_property_real(callback_get_real);
+	stack.register_callback_get_property_signed_integer(callback_get_signed_integer);
+	stack.register_callback_get_property_time(callback_get_time);
+	stack.register_callback_get_property_unsigned_integer(callback_get_unsigned_integer);
+	stack.register_callback_set_property_bitstring(callback_set_bitstring);
+	stack.register_callback_set_property_bool(callback_set_bool);
+	stack.register_callback_set_property_character_string(callback_set_character_string);
+	stack.register_callback_set_property_date(callback_set_date);
+	stack.register_callback_set_property_double(callback_set_double);
+	stack.register_callback_set_property_enumerated(callback_set_enumerated);
+	stack.register_callback_set_property_null(callback_set_null);
+	stack.register_callback_set_property_octet_string(callback_set_octet_string);
+	stack.register_callback_set_property_real(callback_set_real);
+	stack.register_callback_set_property_signed_integer(callback_set_signed_integer);
+	stack.register_callback_set_property_time(callback_set_time);
+	stack.register_callback_set_property_unsigned_integer(callback_set_unsigned_integer);
+}
+
+// Changes made by the main loop to the database. The stack is told after the database is unlocked,
+// as it may read the objects back through the callbacks.
+#[derive(Default)]
+struct DatabaseUpdates {
+	event_states: Vec<(ObjectKey, EventState)>,
+	// Calendars, schedules and loops
+	present_values: Vec<ObjectKey>,
+	// Values written by schedules and loops
+	writes: Vec<ReferenceWrite>
 }
 
-fn database_loop(update_once_a_second_timer: &mut SystemTime) {
-	let mut database = db.lock().unwrap();
+fn database_loop(device_instance: u32, update_once_a_second_timer: &mut SystemTime) -> DatabaseUpdates {
+	let mut database = lock_database();
 	// Get current time and check elapsed time
 	if let Ok(duration) = update_once_a_second_timer.elapsed() {	
 		// Update analog input every 5 seconds
 		if duration.as_secs() >= 5 {
 			*update_once_a_second_timer = SystemTime::now();
-			if let Some(ExampleDatabaseObject::AnalogInput(analog_input)) = database.get_mut("analog_input-0") {
-				analog_input.present_value += 1.001;
-				println!("Analog Input increased!");
+			if let Some(ExampleDatabaseObject::AnalogInput(analog_input)) = database.get_mut(&ObjectKey::new(device_instance, ObjectType::AnalogInput, 0)) {
+				// Out of service decouples the present value from its data source
+				if !analog_input.out_of_service {
+					if analog_input.simulate_step() {
+						println!("Analog Input reset!");
+					} else {
+						println!("Analog Input increased!");
+					}
+				}
 			}
 		}
 	}
+	let now = database::device_time(&database, device_instance);
+	let (mut present_values, mut writes) = database::update_schedules(&mut database, now);
+	let (loop_present_values, loop_writes) = database::update_loops(&mut database, now);
+	present_values.extend(loop_present_values);
+	writes.extend(loop_writes);
+	// Sample the logged properties
+	database::update_trend_logs(&mut database, now);
+	DatabaseUpdates { event_states: database::update_event_states(&mut database, now), present_values, writes }
 }
 
 fn check_end_loop(key: &str) -> bool {
-	if key == "q\r\n" || key == "Q\r\n" {
+	if key.trim().eq_ignore_ascii_case("q") {
 		true
 	} else {
 		println!("Invalid input, enter Q to quit.");
@@ -217,16 +423,22 @@ fn check_end_loop(key: &str) -> bool {
 
 // Referenced: https://stackoverflow.com/questions/30012995/how-can-i-read-non-blocking-from-stdin
 fn spawn_stdin_channel() -> Receiver<String> {
-    let (tx, rx) = mpsc::channel::<String>();
-    thread::spawn(move || loop {
-        let mut buffer = String::new();
-        io::stdin().read_line(&mut buffer).unwrap();
-        tx.send(buffer).unwrap();
-    });
-    rx
+	let (tx, rx) = mpsc::channel::<String>();
+	thread::spawn(move || loop {
+		let mut buffer = String::new();
+		// Stop reading once stdin is closed, e.g. when running without a terminal
+		if io::stdin().read_line(&mut buffer).unwrap_or(0) == 0 || tx.send(buffer).is_err() {
+			break;
+		}
+	});
+	rx
+}
+
+extern "C" fn callback_receive_message(message: *mut u8, max_message_length: u16, received_connection_string: *mut u8, max_connection_string_length: u8, received_connection_string_length: *mut u8, network_type: *mut u8) -> u16 {
+	adapter::catch_callback_panic("callback_receive_message", 0, || receive_message(message, max_message_length, received_connection_string, max_connection_string_length, received_connection_string_length, network_type))
 }
 
-fn callback_receive_message(message: *mut u8, max_message_length: u16, received_connection_string: *mut u8, max_connection_string_length: u8, received_connection_string_length: *mut u8, network_type: *mut u8) -> u16 {
+fn receive_message(message: *mut u8, max_message_length: u16, received_connection_string: *mut u8, max_connection_string_length: u8, received_connection_string_length: *mut u8, network_type: *mut u8) -> u16 {
 		
 	// Check parameters
 	if message.is_null() || max_message_length == 0 {
@@ -246,16 +458,27 @@ fn callback_receive_message(message: *mut u8, max_message_length: u16, received_
 
 	// Attempt to read bytes
 	let mut buf: [u8; MAX_RENDER_BUFFER_LENGTH] = [0; MAX_RENDER_BUFFER_LENGTH];
-	let (bytes_read, src_addr) = if let Ok((bytes_read, src_addr)) = socket.recv_from(&mut buf) {
+	let (bytes_read, src_addr) = if let Ok((bytes_read, src_addr)) = SOCKET.recv_from(&mut buf) {
 		(bytes_read, src_addr)
 	} else {
-		(0 as usize, SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080))
+		(0, SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080))
 	};
 	if bytes_read > 0 {
 		port = src_addr.port();
 		let ip_address = src_addr.ip().to_canonical();
 		println!("FYI: Received message from [{0}:{1}], length [{2}]", ip_address, port, bytes_read);
 
+		// ReadRange of a trend log buffer is answered here, the stack never sees it
+		if let Some((reply, reply_address)) = serve_read_range(&buf[..bytes_read], src_addr) {
+			if SOCKET.send_to(&reply, reply_address).is_ok() {
+				println!("FYI: Answered ReadRange from [{0}] from the trend log buffer", reply_address);
+			} else {
+				println!("Failed to send message");
+			}
+			return 0;
+		}
+		UTC_TIME_SYNCHRONIZATION.store(read_range::is_utc_time_synchronization(&buf[..bytes_read]), Ordering::Relaxed);
+
 		// Convert the IP Address to the connection string
 		if !convert_ip_address_to_bytes(src_addr.ip(), received_connection_string, max_connection_string_length) {
 			println!("Failed to convert the ip address into a connectionString");
@@ -270,7 +493,7 @@ fn callback_receive_message(message: *mut u8, max_message_length: u16, received_
 		}
 
 		// Check message size
-		if usize::from(max_message_length) < bytes_read || usize::from(MAX_RENDER_BUFFER_LENGTH) < bytes_read {
+		if usize::from(max_message_length) < bytes_read || MAX_RENDER_BUFFER_LENGTH < bytes_read {
 			return 0;
 		}
 
@@ -286,7 +509,11 @@ fn callback_receive_message(message: *mut u8, max_message_length: u16, received_
 	bytes_read.try_into().unwrap()
 }
 
-fn callback_send_message(message: *const u8, message_length: u16, connection_string: *const u8, connection_string_length: u8, network_type: u8, broadcast: bool) -> u16 {
+extern "C" fn callback_send_message(message: *const u8, message_length: u16, connection_string: *const u8, connection_string_length: u8, network_type: u8, broadcast: bool) -> u16 {
+	adapter::catch_callback_panic("callback_send_message", 0, || send_message(message, message_length, connection_string, connection_string_length, network_type, broadcast))
+}
+
+fn send_message(message: *const u8, message_length: u16, connection_string: *const u8, connection_string_length: u8, network_type: u8, broadcast: bool) -> u16 {
 	println!("callback_send_message");
 
 	// Check parameters
@@ -309,8 +536,13 @@ fn callback_send_message(message: *const u8, message_length: u16, connection_str
 	let ip_address: Ipv4Addr;
 	unsafe {
 		if broadcast {
-			let database = db.lock().unwrap();
-			if let Some(ExampleDatabaseObject::NetworkPort(network_port)) = database.get("network_port-56") {
+			let database = lock_database();
+			// The broadcast address comes from the device's network port
+			let network_port = database.values().find_map(|object| match object {
+				ExampleDatabaseObject::NetworkPort(network_port) => Some(network_port),
+				_ => None
+			});
+			if let Some(network_port) = network_port {
 				ip_address = Ipv4Addr::new(
 					*connection_string.add(0) | !network_port.ip_subnet_mask[0], 
 					*connection_string.add(1) | !network_port.ip_subnet_mask[1], 
@@ -330,8 +562,8 @@ fn callback_send_message(message: *const u8, message_length: u16, connection_str
 	// Get the port
 	let mut port: u16 = 0;
 	unsafe {
-		port = port + *connection_string.add(4) as u16 * 256;
-		port = port + *connection_string.add(5) as u16;
+		port += *connection_string.add(4) as u16 * 256;
+		port += *connection_string.add(5) as u16;
 	}
 
 	println!("FYI: Sending message to [{0}:{1}], length [{2}]", ip_address, port, message_length);
@@ -346,27 +578,52 @@ fn callback_send_message(message: *const u8, message_length: u16, connection_str
 		let mut index: usize = 0;
 		unsafe {
 			while index < message_length.into() {
-				buf[index] = *message.add(index.into());
+				buf[index] = *message.add(index);
 				index += 1;
 			}
 		}
 	}
 
-	if let Ok(x) = socket.send_to(&buf[0..usize::from(message_length)], (ip_address, port)) {
-		return message_length;
+	if SOCKET.send_to(&buf[0..usize::from(message_length)], (ip_address, port)).is_ok() {
+		message_length
 	} else {
 		println!("Failed to send message");
-		return 0;
+		0
 	}
 }
 
+// The reply to a ReadRange request for the Log_Buffer of a database trend log, None for any other
+// message. Timestamps are the device clock's local time.
+fn serve_read_range(message: &[u8], src_addr: SocketAddr) -> Option<(Vec<u8>, SocketAddr)> {
+	let database = lock_database();
+	let device = database.values().find_map(|object| match object {
+		ExampleDatabaseObject::Device(device) => Some(device),
+		_ => None
+	})?;
+	let request = read_range::decode_request(message, |date, time| device.clock.local_to_utc(date, time).map(|time| time.max(0) as u64))?;
+	let Some(ExampleDatabaseObject::TrendLog(trend_log)) = database.get(&ObjectKey::new(device.instance, request.object_type, request.object_instance)) else {
+		return None;
+	};
+	let result = match (request.property_array_index, request.range) {
+		(Some(_), _) => Err(ErrorCode::PropertyIsNotAnArray),
+		(None, None) => Err(ErrorCode::ParameterOutOfRange),
+		(None, Some(range)) => trend_log.read_range(range)
+	};
+	let reply = match result {
+		Ok(result) => read_range::encode_ack(&request, result, |time| device.clock.local_date_time(time)),
+		Err(error_code) => read_range::encode_error(&request, error_code)
+	};
+	// A request forwarded by a BBMD is answered to the device that sent it, not to the BBMD
+	Some((reply, request.originating_address.map_or(src_addr, SocketAddr::V4)))
+}
+
 fn convert_ip_address_to_bytes(ip_address: IpAddr, received_connection_string: *mut u8, max_connection_string_length: u8) -> bool {
 	if max_connection_string_length < 4 {
 		return false;
 	}
 	let ip_address_v4 = ip_address.to_canonical();
 	match ip_address_v4 {
-        IpAddr::V4(address) => {
+		IpAddr::V4(address) => {
 			let ip_address_octet = address.octets();
 			unsafe {
 				*received_connection_string.add(0) = ip_address_octet[0];
@@ -374,215 +631,542 @@ fn convert_ip_address_to_bytes(ip_address: IpAddr, received_connection_string: *
 				*received_connection_string.add(2) = ip_address_octet[2];
 				*received_connection_string.add(3) = ip_address_octet[3];
 			}
-			return true;
+			true
 		},
-        _ => {
+		_ => {
 			println!("Invalid IP Address");
-			return false;
+			false
 		},
-     }
+	}
+}
+
+extern "C" fn callback_get_system_time() -> u64 {
+	adapter::catch_callback_panic("callback_get_system_time", 0, get_system_time)
 }
 
-fn callback_get_system_time() -> u64 {
-	let database = db.lock().unwrap();
-	if let Some(ExampleDatabaseObject::Device(device)) = database.get("device-389001") {
-		return SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_secs()/* - x.current_time_offset*/;
+// Seconds since the Unix epoch on the device clock, which time synchronization may have moved from
+// the host clock
+fn get_system_time() -> u64 {
+	let database = lock_database();
+	let device_time = database.values().find_map(|object| match object {
+		ExampleDatabaseObject::Device(device) => Some(device.clock.now()),
+		_ => None
+	});
+	device_time.unwrap_or_else(|| DeviceClock::host_time().max(0) as u64) / 1000
+}
+
+extern "C" fn callback_set_system_time(device_instance: u32, year: u8, month: u8, day: u8, weekday: u8, hour: u8, minute: u8, second: u8, hundredth_seconds: u8) -> bool {
+	adapter::catch_callback_panic("callback_set_system_time", false, || set_system_time(device_instance, BacnetDate { year, month, day, weekday }, BacnetTime { hour, minute, second, hundredth_seconds }))
+}
+
+// TimeSynchronization carries the device's local date and time, UTCTimeSynchronization carries UTC.
+// Which one called is taken from the message being handled. Only the device clock is moved, its
+// offset from the host clock is journaled.
+fn set_system_time(device_instance: u32, date: BacnetDate, time: BacnetTime) -> bool {
+	let utc = UTC_TIME_SYNCHRONIZATION.load(Ordering::Relaxed);
+	let mut database = lock_database();
+	match persistence::set_device_time(&mut database, lock_journal().as_mut(), device_instance, &date, &time, utc) {
+		Ok(_) => {
+			println!("FYI: Device {device_instance} clock set to {}-{:02}-{:02} {:02}:{:02}:{:02}{}", 1900 + u16::from(date.year), date.month, date.day, time.hour, time.minute, time.second, if utc { " UTC" } else { "" });
+			true
+		},
+		Err(error) => {
+			println!("WARNING: Device {device_instance} clock was not set: {error}");
+			false
+		}
 	}
-    SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_secs()
 }
 
-fn get_object_name(device_instance: u32, object_type: u16, object_instance: u32, value: *mut c_char, value_element_count: *mut u32, max_element_count: u32, database: MutexGuard<'_, HashMap<String, ExampleDatabaseObject>>) -> bool {	
-	if object_type == bacnet_const::OBJECT_TYPE_DEVICE {
-		// Get object from database
-		if let Some(ExampleDatabaseObject::Device(device)) = database.get(format!("device-{device_instance}").as_str()) {
-			// Check object name length less than value length and store into value
-			if device.object_name.len() < max_element_count.try_into().unwrap() {
-				unsafe {
-					let mut index = 0;
-					for character in device.object_name.chars() {
-						*value.add(index) = character as c_char;
-						index += 1;
-					}
-					*value.add(index) = '\0' as c_char;
-					*value_element_count = device.object_name.len() as u32;
+// Reads a property of a database object through its BacnetObject impl. None when the object or
+// property is not in the database, the stack then uses its own value if it has one.
+fn get_property(device_instance: u32, object_type: u16, object_instance: u32, property_identifier: u32, use_array_index: bool, property_array_index: u32) -> Option<PropertyValue> {
+	let key = ObjectKey::from_raw(device_instance, object_type, object_instance)?;
+	let property_identifier = PropertyIdentifier::try_from(property_identifier).ok()?;
+	let property_array_index = use_array_index.then_some(property_array_index);
+	lock_database().get(&key)?.get_property(property_identifier, property_array_index).ok()
+}
+
+// Copies encoded text into the stack's buffer followed by a null terminator, with its length in
+// bytes and its character set. Text too long for the buffer is cut after the last whole character
+// that fits. Returns the number of bytes cut, or None when not even the terminator fits.
+fn copy_character_string(bytes: &[u8], character_set: CharacterSet, value: *mut c_char, value_element_count: *mut u32, max_element_count: u32, encoding_type: *mut u8) -> Option<usize> {
+	let terminator_length = character_set.terminator_length();
+	let capacity = (max_element_count as usize).checked_sub(terminator_length)?;
+	let length = character_set.character_boundary(bytes, capacity);
+	unsafe {
+		std::ptr::copy_nonoverlapping(bytes.as_ptr(), value as *mut u8, length);
+		std::ptr::write_bytes(value.add(length), 0, terminator_length);
+		*value_element_count = length as u32;
+		*encoding_type = character_set.into();
+	}
+	Some(bytes.len() - length)
+}
+
+extern "C" fn callback_get_bitstring(device_instance: u32, object_type: u16, object_instance: u32, property_identifier: u32, value: *mut bool, value_element_count: *mut u32, max_element_count: u32, use_array_index: bool, property_array_index: u32) -> bool {
+	adapter::catch_callback_panic("callback_get_bitstring", false, || get_bitstring(device_instance, object_type, object_instance, property_identifier, value, value_element_count, max_element_count, use_array_index, property_array_index))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn get_bitstring(device_instance: u32, object_type: u16, object_instance: u32, property_identifier: u32, value: *mut bool, value_element_count: *mut u32, max_element_count: u32, use_array_index: bool, property_array_index: u32) -> bool {
+	match get_property(device_instance, object_type, object_instance, property_identifier, use_array_index, property_array_index) {
+		Some(PropertyValue::BitString(bits)) if bits.len() <= max_element_count as usize => {
+			unsafe {
+				for (index, bit) in bits.iter().enumerate() {
+					*value.add(index) = *bit;
 				}
-				return true;
+				*value_element_count = bits.len() as u32;
 			}
-		}
+			true
+		},
+		_ => false
 	}
-	else if object_type == bacnet_const::OBJECT_TYPE_ANALOG_INPUT {
-		if let Some(ExampleDatabaseObject::AnalogInput(analog_input)) = database.get(format!("analog_input-{object_instance}").as_str()) {
-			if analog_input.object_name.len() < max_element_count.try_into().unwrap() {
-				unsafe {
-					let mut index = 0;
-					for character in analog_input.object_name.chars() {
-						*value.add(index) = character as c_char;
-						index += 1;
-					}
-					*value.add(index) = '\0' as c_char;
-					*value_element_count = analog_input.object_name.len() as u32;
-				}
-				return true;
+}
+
+extern "C" fn callback_get_bool(device_instance: u32, object_type: u16, object_instance: u32, property_identifier: u32, value: *mut bool, use_array_index: bool, property_array_index: u32) -> bool {
+	adapter::catch_callback_panic("callback_get_bool", false, || get_bool(device_instance, object_type, object_instance, property_identifier, value, use_array_index, property_array_index))
+}
+
+fn get_bool(device_instance: u32, object_type: u16, object_instance: u32, property_identifier: u32, value: *mut bool, use_array_index: bool, property_array_index: u32) -> bool {
+	match get_property(device_instance, object_type, object_instance, property_identifier, use_array_index, property_array_index) {
+		Some(PropertyValue::Boolean(boolean)) => {
+			unsafe {
+				*value = boolean;
 			}
+			true
+		},
+		_ => false
+	}
+}
+
+extern "C" fn callback_get_character_string(device_instance: u32, object_type: u16, object_instance: u32, property_identifier: u32, value: *mut c_char, value_element_count: *mut u32, max_element_count: u32, encoding_type: *mut u8, use_array_index: bool, property_array_index: u32) -> bool {
+	adapter::catch_callback_panic("callback_get_character_string", false, || get_character_string(device_instance, object_type, object_instance, property_identifier, value, value_element_count, max_element_count, encoding_type, use_array_index, property_array_index))
+}
+
+// The string is encoded in the character set of the property. A string the stack's buffer cannot
+// hold is cut to fit, and a string the character set cannot encode is not read.
+#[allow(clippy::too_many_arguments)]
+fn get_character_string(device_instance: u32, object_type: u16, object_instance: u32, property_identifier: u32, value: *mut c_char, value_element_count: *mut u32, max_element_count: u32, encoding_type: *mut u8, use_array_index: bool, property_array_index: u32) -> bool {
+	let text = if object_type == bacnet_const::OBJECT_TYPE_DEVICE && property_identifier == bacnet_const::PROPERTY_IDENTIFIER_APPLICATION_SOFTWARE_VERSION {
+		APPLICATION_VERSION.to_string()
+	} else {
+		match get_property(device_instance, object_type, object_instance, property_identifier, use_array_index, property_array_index) {
+			Some(PropertyValue::CharacterString(text)) => text,
+			_ => return false
+		}
+	};
+	let character_set = character_set(device_instance, object_type, object_instance, property_identifier);
+	let describe = || {
+		let key = ObjectKey::from_raw(device_instance, object_type, object_instance).map_or_else(|| format!("device {device_instance} object type {object_type} {object_instance}"), |key| key.to_string());
+		let property = PropertyIdentifier::try_from(property_identifier).map_or_else(|_| property_identifier.to_string(), |property| property.to_string());
+		if use_array_index { format!("{key} {property}[{property_array_index}]") } else { format!("{key} {property}") }
+	};
+	let Some(bytes) = character_set.encode(&text) else {
+		println!("ERROR: {} {text:?} cannot be encoded in {character_set}", describe());
+		return false;
+	};
+	match copy_character_string(&bytes, character_set, value, value_element_count, max_element_count, encoding_type) {
+		Some(0) => true,
+		Some(cut) => {
+			println!("WARNING: {} is {} bytes in {character_set}, the last {cut} were cut to fit the stack's buffer of {max_element_count}", describe(), bytes.len());
+			true
+		},
+		None => {
+			println!("ERROR: {} was not read, the stack's buffer of {max_element_count} bytes cannot hold a terminated string", describe());
+			false
 		}
 	}
-	else if object_type == bacnet_const::OBJECT_TYPE_CHARACTERSTRING_VALUE {
-		if let Some(ExampleDatabaseObject::CharacterStringValue(character_string_value)) = database.get(format!("character_string_value-{object_instance}").as_str()) {
-			if character_string_value.object_name.len() < max_element_count.try_into().unwrap() {
-				unsafe {
-					let mut index = 0;
-					for character in character_string_value.object_name.chars() {
-						*value.add(index) = character as c_char;
-						index += 1;
-					}
-					*value.add(index) = '\0' as c_char;
-					*value_element_count = character_string_value.object_name.len() as u32;
-				}
-				return true;
+}
+
+extern "C" fn callback_get_date(device_instance: u32, object_type: u16, object_instance: u32, property_identifier: u32, year: *mut u8, month: *mut u8, day: *mut u8, weekday: *mut u8, use_array_index: bool, property_array_index: u32) -> bool {
+	adapter::catch_callback_panic("callback_get_date", false, || get_date(device_instance, object_type, object_instance, property_identifier, year, month, day, weekday, use_array_index, property_array_index))
+}
+
+// A date and time present value is read as its date and its time
+#[allow(clippy::too_many_arguments)]
+fn get_date(device_instance: u32, object_type: u16, object_instance: u32, property_identifier: u32, year: *mut u8, month: *mut u8, day: *mut u8, weekday: *mut u8, use_array_index: bool, property_array_index: u32) -> bool {
+	match get_property(device_instance, object_type, object_instance, property_identifier, use_array_index, property_array_index) {
+		Some(PropertyValue::Date(date) | PropertyValue::DateTime(date, _)) => {
+			unsafe {
+				*year = date.year;
+				*month = date.month;
+				*day = date.day;
+				*weekday = date.weekday;
 			}
-		}
+			true
+		},
+		_ => false
 	}
-	false
 }
 
-fn callback_get_character_string(device_instance: u32, object_type: u16, object_instance: u32, property_identifier: u32, value: *mut c_char, value_element_count: *mut u32, max_element_count: u32, encoding_type: *mut u8, use_array_index: bool, property_array_index: u32) -> bool {
-	let database = db.lock().unwrap();
-	if property_identifier == bacnet_const::PROPERTY_IDENTIFIER_OBJECT_NAME {
-		return get_object_name(device_instance, object_type, object_instance, value, value_element_count, max_element_count, database);
-	}
-	else if property_identifier == bacnet_const::PROPERTY_IDENTIFIER_DESCRIPTION {
-		if object_type == bacnet_const::OBJECT_TYPE_DEVICE {
-			if let Some(ExampleDatabaseObject::Device(device)) = database.get(format!("device-{device_instance}").as_str()) {
-				if device.description.len() < max_element_count.try_into().unwrap() {
-					unsafe {
-						let mut index = 0;
-						for character in device.description.chars() {
-							*value.add(index) = character as c_char;
-							index += 1;
-						}
-						*value.add(index) = '\0' as c_char;
-						*value_element_count = device.description.len() as u32;
-					}
-					return true;
-				}
+extern "C" fn callback_get_signed_integer(device_instance: u32, object_type: u16, object_instance: u32, property_identifier: u32, value: *mut i32, use_array_index: bool, property_array_index: u32) -> bool {
+	adapter::catch_callback_panic("callback_get_signed_integer", false, || get_signed_integer(device_instance, object_type, object_instance, property_identifier, value, use_array_index, property_array_index))
+}
+
+fn get_signed_integer(device_instance: u32, object_type: u16, object_instance: u32, property_identifier: u32, value: *mut i32, use_array_index: bool, property_array_index: u32) -> bool {
+	match get_property(device_instance, object_type, object_instance, property_identifier, use_array_index, property_array_index) {
+		Some(PropertyValue::Signed(signed_integer)) => {
+			unsafe {
+				*value = signed_integer;
 			}
-		}
-		else if object_type == bacnet_const::OBJECT_TYPE_ANALOG_INPUT {
-			if let Some(ExampleDatabaseObject::AnalogInput(analog_input)) = database.get(format!("analog_input-{object_instance}").as_str()) {
-				if analog_input.description.len() < max_element_count.try_into().unwrap() {
-					unsafe {
-						let mut index = 0;
-						for character in analog_input.description.chars() {
-							*value.add(index) = character as c_char;
-							index += 1;
-						}
-						*value.add(index) = '\0' as c_char;
-						*value_element_count = analog_input.description.len() as u32;
-					}
-					return true;
-				}
+			true
+		},
+		_ => false
+	}
+}
+
+extern "C" fn callback_get_unsigned_integer(device_instance: u32, object_type: u16, object_instance: u32, property_identifier: u32, value: *mut u32, use_array_index: bool, property_array_index: u32) -> bool {
+	adapter::catch_callback_panic("callback_get_unsigned_integer", false, || get_unsigned_integer(device_instance, object_type, object_instance, property_identifier, value, use_array_index, property_array_index))
+}
+
+fn get_unsigned_integer(device_instance: u32, object_type: u16, object_instance: u32, property_identifier: u32, value: *mut u32, use_array_index: bool, property_array_index: u32) -> bool {
+	match get_property(device_instance, object_type, object_instance, property_identifier, use_array_index, property_array_index) {
+		Some(PropertyValue::Unsigned(unsigned_integer)) => {
+			unsafe {
+				*value = unsigned_integer;
 			}
-		}
+			true
+		},
+		_ => false
 	}
-	else if property_identifier == bacnet_const::PROPERTY_IDENTIFIER_PRESENT_VALUE {
-		if object_type == bacnet_const::OBJECT_TYPE_CHARACTERSTRING_VALUE {
-			if let Some(ExampleDatabaseObject::CharacterStringValue(character_string_value)) = database.get(format!("character_string_value-{object_instance}").as_str()) {
-				if character_string_value.present_value.len() < max_element_count.try_into().unwrap() {
-					unsafe {
-						let mut index = 0;
-						for character in character_string_value.present_value.chars() {
-							*value.add(index) = character as c_char;
-							index += 1;
-						}
-						*value.add(index) = '\0' as c_char;
-						*value_element_count = character_string_value.present_value.len() as u32;
-					}
-					return true;
-				}
+}
+
+extern "C" fn callback_get_enumerated(device_instance: u32, object_type: u16, object_instance: u32, property_identifier: u32, value: *mut u32, use_array_index: bool, property_array_index: u32) -> bool {
+	adapter::catch_callback_panic("callback_get_enumerated", false, || get_enumerated(device_instance, object_type, object_instance, property_identifier, value, use_array_index, property_array_index))
+}
+
+fn get_enumerated(device_instance: u32, object_type: u16, object_instance: u32, property_identifier: u32, value: *mut u32, use_array_index: bool, property_array_index: u32) -> bool {
+	match get_property(device_instance, object_type, object_instance, property_identifier, use_array_index, property_array_index) {
+		Some(PropertyValue::Enumerated(enumerated)) => {
+			unsafe {
+				*value = enumerated;
 			}
-		}
+			true
+		},
+		_ => false
 	}
-	else if object_type == bacnet_const::OBJECT_TYPE_DEVICE && property_identifier == bacnet_const::PROPERTY_IDENTIFIER_APPLICATION_SOFTWARE_VERSION {
-		if APPLICATION_VERSION.len() < max_element_count.try_into().unwrap() {
+}
+
+extern "C" fn callback_get_real(device_instance: u32, object_type: u16, object_instance: u32, property_identifier: u32, value: *mut f32, use_array_index: bool, property_array_index: u32) -> bool {
+	adapter::catch_callback_panic("callback_get_real", false, || get_real(device_instance, object_type, object_instance, property_identifier, value, use_array_index, property_array_index))
+}
+
+fn get_real(device_instance: u32, object_type: u16, object_instance: u32, property_identifier: u32, value: *mut f32, use_array_index: bool, property_array_index: u32) -> bool {
+	match get_property(device_instance, object_type, object_instance, property_identifier, use_array_index, property_array_index) {
+		Some(PropertyValue::Real(real)) => {
 			unsafe {
-				let mut index = 0;
-				for character in APPLICATION_VERSION.chars() {
-					*value.add(index) = character as c_char;
-					index += 1;
-				}
-				*value.add(index) = '\0' as c_char;
-				*value_element_count = APPLICATION_VERSION.len() as u32;
+				*value = real;
 			}
-			return true;
-		}
+			true
+		},
+		_ => false
 	}
-	false
 }
 
-fn callback_get_signed_integer(device_instance: u32, object_type: u16, object_instance: u32, property_identifier: u32, value: *mut i32, use_array_index: bool, property_array_index: u32) -> bool {
-	let database = db.lock().unwrap();
-	if property_identifier == bacnet_const::PROPERTY_IDENTIFIER_UTC_OFFSET {
-		if object_type == bacnet_const::OBJECT_TYPE_DEVICE {
-			if let Some(ExampleDatabaseObject::Device(device)) = database.get(format!("device-{device_instance}").as_str()) {
-				unsafe {
-					*value = device.utc_offset; 
-				}
-				return true;
+extern "C" fn callback_get_double(device_instance: u32, object_type: u16, object_instance: u32, property_identifier: u32, value: *mut f64, use_array_index: bool, property_array_index: u32) -> bool {
+	adapter::catch_callback_panic("callback_get_double", false, || get_double(device_instance, object_type, object_instance, property_identifier, value, use_array_index, property_array_index))
+}
+
+fn get_double(device_instance: u32, object_type: u16, object_instance: u32, property_identifier: u32, value: *mut f64, use_array_index: bool, property_array_index: u32) -> bool {
+	match get_property(device_instance, object_type, object_instance, property_identifier, use_array_index, property_array_index) {
+		Some(PropertyValue::Double(double)) => {
+			unsafe {
+				*value = double;
 			}
-			return false;
-		}
+			true
+		},
+		_ => false
 	}
-	false
 }
 
-fn callback_get_enumerated(device_instance: u32, object_type: u16, object_instance: u32, property_identifier: u32, value: *mut u32, use_array_index: bool, property_array_index: u32) -> bool {
-	let database = db.lock().unwrap();
-	if property_identifier == bacnet_const::PROPERTY_IDENTIFIER_RELIABILITY {
-		if object_type == bacnet_const::OBJECT_TYPE_ANALOG_INPUT {
-			if let Some(ExampleDatabaseObject::AnalogInput(analog_input)) = database.get(format!("analog_input-{object_instance}").as_str()) {
-				unsafe {
-					*value = analog_input.reliability; 
-				}	
-				return true;
+extern "C" fn callback_get_octet_string(device_instance: u32, object_type: u16, object_instance: u32, property_identifier: u32, value: *mut u8, value_element_count: *mut u32, max_element_count: u32, use_array_index: bool, property_array_index: u32) -> bool {
+	adapter::catch_callback_panic("callback_get_octet_string", false, || get_octet_string(device_instance, object_type, object_instance, property_identifier, value, value_element_count, max_element_count, use_array_index, property_array_index))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn get_octet_string(device_instance: u32, object_type: u16, object_instance: u32, property_identifier: u32, value: *mut u8, value_element_count: *mut u32, max_element_count: u32, use_array_index: bool, property_array_index: u32) -> bool {
+	match get_property(device_instance, object_type, object_instance, property_identifier, use_array_index, property_array_index) {
+		Some(PropertyValue::OctetString(octets)) if octets.len() <= max_element_count as usize => {
+			unsafe {
+				for (index, octet) in octets.iter().enumerate() {
+					*value.add(index) = *octet;
+				}
+				*value_element_count = octets.len() as u32;
 			}
-			return false;
-		}
+			true
+		},
+		_ => false
 	}
-	else if property_identifier == bacnet_const::PROPERTY_IDENTIFIER_SYSTEM_STATUS &&
-		object_type == bacnet_const::OBJECT_TYPE_DEVICE {
-		println!("Debug: Device:System Status");
-		if let Some(ExampleDatabaseObject::Device(device)) = database.get(format!("device-{device_instance}").as_str()) {
+}
+
+extern "C" fn callback_get_time(device_instance: u32, object_type: u16, object_instance: u32, property_identifier: u32, hour: *mut u8, minute: *mut u8, second: *mut u8, hundredth_seconds: *mut u8, use_array_index: bool, property_array_index: u32) -> bool {
+	adapter::catch_callback_panic("callback_get_time", false, || get_time(device_instance, object_type, object_instance, property_identifier, hour, minute, second, hundredth_seconds, use_array_index, property_array_index))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn get_time(device_instance: u32, object_type: u16, object_instance: u32, property_identifier: u32, hour: *mut u8, minute: *mut u8, second: *mut u8, hundredth_seconds: *mut u8, use_array_index: bool, property_array_index: u32) -> bool {
+	match get_property(device_instance, object_type, object_instance, property_identifier, use_array_index, property_array_index) {
+		Some(PropertyValue::Time(time) | PropertyValue::DateTime(_, time)) => {
 			unsafe {
-				*value = device.system_status; 
+				*hour = time.hour;
+				*minute = time.minute;
+				*second = time.second;
+				*hundredth_seconds = time.hundredth_seconds;
 			}
-			return true;
+			true
+		},
+		_ => false
+	}
+}
+
+// Set Data Functions. The stack only calls these for properties set writable.
+
+// Applies a value written by a client to the database and journals it. A rejected write returns
+// its error code to the stack, which sends it to the client.
+#[allow(clippy::too_many_arguments)]
+fn set_property(device_instance: u32, object_type: u16, object_instance: u32, property_identifier: u32, use_array_index: bool, property_array_index: u32, priority: u8, value: PropertyValue, error_code: *mut u32) -> bool {
+	match write_property(device_instance, object_type, object_instance, property_identifier, use_array_index.then_some(property_array_index), priority, value) {
+		Ok(()) => true,
+		Err(error) => reject_write(error, error_code)
+	}
+}
+
+// Fails a write with its error code, which the stack sends to the client
+fn reject_write(error: ErrorCode, error_code: *mut u32) -> bool {
+	if !error_code.is_null() {
+		unsafe {
+			*error_code = error.into();
 		}
-		return false;
 	}
 	false
 }
 
-fn callback_get_real(device_instance: u32, object_type: u16, object_instance: u32, property_identifier: u32, value: *mut f32, use_array_index: bool, property_array_index: u32) -> bool {
-	let database = db.lock().unwrap();
-	if property_identifier == bacnet_const::PROPERTY_IDENTIFIER_PRESENT_VALUE {
-		if object_type == bacnet_const::OBJECT_TYPE_ANALOG_INPUT {
-			if let Some(ExampleDatabaseObject::AnalogInput(analog_input)) = database.get(format!("analog_input-{object_instance}").as_str()) {
-				unsafe {
-					*value = analog_input.present_value; 
-				}	
-				return true;
-			}
-			return false;
+fn write_property(device_instance: u32, object_type: u16, object_instance: u32, property_identifier: u32, property_array_index: Option<u32>, priority: u8, value: PropertyValue) -> Result<(), ErrorCode> {
+	let object_type = ObjectType::try_from(object_type).map_err(|_| ErrorCode::UnsupportedObjectType)?;
+	let property_identifier = PropertyIdentifier::try_from(property_identifier).map_err(|_| ErrorCode::UnknownProperty)?;
+	let record = JournalRecord { device_instance, object_type, object_instance, property_identifier, property_array_index, priority: Some(priority), value };
+	let mut database = lock_database();
+	let record = persistence::write_property(&mut database, lock_journal().as_mut(), &record)?;
+	println!("FYI: Wrote {record}");
+	// The stack keeps its own copy of the intrinsic reporting settings
+	if matches!(property_identifier, PropertyIdentifier::EventEnable | PropertyIdentifier::NotificationClass | PropertyIdentifier::NotifyType) {
+		INTRINSIC_REPORTING_CHANGES.lock().unwrap_or_else(PoisonError::into_inner).push(ObjectKey::new(device_instance, object_type, object_instance));
+	}
+	Ok(())
+}
+
+// Copies a value the stack passed by pointer and length. A null pointer is only valid when empty.
+fn copy_from_stack<T: Clone>(value: *const T, length: u32) -> Option<Vec<T>> {
+	if length == 0 {
+		return Some(Vec::new());
+	}
+	if value.is_null() {
+		return None;
+	}
+	Some(unsafe { std::slice::from_raw_parts(value, length as usize) }.to_vec())
+}
+
+extern "C" fn callback_set_bitstring(device_instance: u32, object_type: u16, object_instance: u32, property_identifier: u32, value: *const bool, length: u32, use_array_index: bool, property_array_index: u32, priority: u8, error_code: *mut u32) -> bool {
+	adapter::catch_callback_panic("callback_set_bitstring", false, || match copy_from_stack(value, length) {
+		Some(bits) => set_property(device_instance, object_type, object_instance, property_identifier, use_array_index, property_array_index, priority, PropertyValue::BitString(bits), error_code),
+		None => reject_write(ErrorCode::ValueOutOfRange, error_code)
+	})
+}
+
+extern "C" fn callback_set_bool(device_instance: u32, object_type: u16, object_instance: u32, property_identifier: u32, value: bool, use_array_index: bool, property_array_index: u32, priority: u8, error_code: *mut u32) -> bool {
+	adapter::catch_callback_panic("callback_set_bool", false, || set_property(device_instance, object_type, object_instance, property_identifier, use_array_index, property_array_index, priority, PropertyValue::Boolean(value), error_code))
+}
+
+extern "C" fn callback_set_character_string(device_instance: u32, object_type: u16, object_instance: u32, property_identifier: u32, value: *const c_char, length: u32, encoding_type: u8, use_array_index: bool, property_array_index: u32, priority: u8, error_code: *mut u32) -> bool {
+	adapter::catch_callback_panic("callback_set_character_string", false, || {
+		// Decoded from any supported character set, and kept only if the property's own can encode it
+		let text = CharacterSet::try_from(encoding_type).ok()
+			.zip(copy_from_stack(value as *const u8, length))
+			.and_then(|(character_set, bytes)| character_set.decode(&bytes))
+			.filter(|text| character_set(device_instance, object_type, object_instance, property_identifier).encode(text).is_some());
+		match text {
+			Some(text) => set_property(device_instance, object_type, object_instance, property_identifier, use_array_index, property_array_index, priority, PropertyValue::CharacterString(text), error_code),
+			None => reject_write(ErrorCode::CharacterSetNotSupported, error_code)
 		}
+	})
+}
+
+extern "C" fn callback_set_date(device_instance: u32, object_type: u16, object_instance: u32, property_identifier: u32, year: u8, month: u8, day: u8, weekday: u8, use_array_index: bool, property_array_index: u32, priority: u8, error_code: *mut u32) -> bool {
+	adapter::catch_callback_panic("callback_set_date", false, || set_property(device_instance, object_type, object_instance, property_identifier, use_array_index, property_array_index, priority, PropertyValue::Date(BacnetDate { year, month, day, weekday }), error_code))
+}
+
+extern "C" fn callback_set_double(device_instance: u32, object_type: u16, object_instance: u32, property_identifier: u32, value: f64, use_array_index: bool, property_array_index: u32, priority: u8, error_code: *mut u32) -> bool {
+	adapter::catch_callback_panic("callback_set_double", false, || set_property(device_instance, object_type, object_instance, property_identifier, use_array_index, property_array_index, priority, PropertyValue::Double(value), error_code))
+}
+
+extern "C" fn callback_set_enumerated(device_instance: u32, object_type: u16, object_instance: u32, property_identifier: u32, value: u32, use_array_index: bool, property_array_index: u32, priority: u8, error_code: *mut u32) -> bool {
+	adapter::catch_callback_panic("callback_set_enumerated", false, || set_property(device_instance, object_type, object_instance, property_identifier, use_array_index, property_array_index, priority, PropertyValue::Enumerated(value), error_code))
+}
+
+extern "C" fn callback_set_null(device_instance: u32, object_type: u16, object_instance: u32, property_identifier: u32, use_array_index: bool, property_array_index: u32, priority: u8, error_code: *mut u32) -> bool {
+	adapter::catch_callback_panic("callback_set_null", false, || set_property(device_instance, object_type, object_instance, property_identifier, use_array_index, property_array_index, priority, PropertyValue::Null, error_code))
+}
+
+extern "C" fn callback_set_octet_string(device_instance: u32, object_type: u16, object_instance: u32, property_identifier: u32, value: *const u8, length: u32, use_array_index: bool, property_array_index: u32, priority: u8, error_code: *mut u32) -> bool {
+	adapter::catch_callback_panic("callback_set_octet_string", false, || match copy_from_stack(value, length) {
+		Some(octets) => set_property(device_instance, object_type, object_instance, property_identifier, use_array_index, property_array_index, priority, PropertyValue::OctetString(octets), error_code),
+		None => reject_write(ErrorCode::ValueOutOfRange, error_code)
+	})
+}
+
+extern "C" fn callback_set_real(device_instance: u32, object_type: u16, object_instance: u32, property_identifier: u32, value: f32, use_array_index: bool, property_array_index: u32, priority: u8, error_code: *mut u32) -> bool {
+	adapter::catch_callback_panic("callback_set_real", false, || set_property(device_instance, object_type, object_instance, property_identifier, use_array_index, property_array_index, priority, PropertyValue::Real(value), error_code))
+}
+
+extern "C" fn callback_set_signed_integer(device_instance: u32, object_type: u16, object_instance: u32, property_identifier: u32, value: i32, use_array_index: bool, property_array_index: u32, priority: u8, error_code: *mut u32) -> bool {
+	adapter::catch_callback_panic("callback_set_signed_integer", false, || set_property(device_instance, object_type, object_instance, property_identifier, use_array_index, property_array_index, priority, PropertyValue::Signed(value), error_code))
+}
+
+extern "C" fn callback_set_time(device_instance: u32, object_type: u16, object_instance: u32, property_identifier: u32, hour: u8, minute: u8, second: u8, hundredth_seconds: u8, use_array_index: bool, property_array_index: u32, priority: u8, error_code: *mut u32) -> bool {
+	adapter::catch_callback_panic("callback_set_time", false, || set_property(device_instance, object_type, object_instance, property_identifier, use_array_index, property_array_index, priority, PropertyValue::Time(BacnetTime { hour, minute, second, hundredth_seconds }), error_code))
+}
+
+extern "C" fn callback_set_unsigned_integer(device_instance: u32, object_type: u16, object_instance: u32, property_identifier: u32, value: u32, use_array_index: bool, property_array_index: u32, priority: u8, error_code: *mut u32) -> bool {
+	adapter::catch_callback_panic("callback_set_unsigned_integer", false, || set_property(device_instance, object_type, object_instance, property_identifier, use_array_index, property_array_index, priority, PropertyValue::Unsigned(value), error_code))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use bacnet_types::EventTransitions;
+	use cas_bacnet_stack_mock::MockCall;
+	use std::path::PathBuf;
+
+	const DEVICE_INSTANCE: u32 = 389001;
+
+	// The database is global, tests that set it up run one at a time
+	static TEST_LOCK: Mutex<()> = Mutex::new(());
+
+	// The example config served on a mock stack, without persistence
+	fn setup() -> (MutexGuard<'static, ()>, MockBacnetStack) {
+		let guard = TEST_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+		let command_line = config::CommandLine { config_file: Some(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("config.example.toml")), ..Default::default() };
+		let (mut config, _) = config::load(&command_line).unwrap();
+		config.journal = None;
+		*lock_journal() = None;
+		INTRINSIC_REPORTING_CHANGES.lock().unwrap_or_else(PoisonError::into_inner).clear();
+		*lock_database() = database::setup_database(&config).unwrap();
+		let _ = CHARACTER_SETS.set(configured_character_sets(&config));
+		let stack = MockBacnetStack::new();
+		setup_bacnet_stack(&stack, &config).unwrap();
+		(guard, stack)
 	}
-	else if property_identifier == bacnet_const::PROPERTY_IDENTIFIER_COV_INCURMENT && object_type == bacnet_const::OBJECT_TYPE_ANALOG_INPUT {
-		if let Some(ExampleDatabaseObject::AnalogInput(analog_input)) = database.get(format!("analog_input-{object_instance}").as_str()) {
-			unsafe {
-				*value = analog_input.cov_incurment; 
-			}	
-			return true;
+
+	#[test]
+	fn setup_adds_the_configured_device_and_objects() {
+		let (_guard, stack) = setup();
+		let calls = stack.calls();
+		assert_eq!(calls.first(), Some(&MockCall::AddDevice { device_instance: DEVICE_INSTANCE }));
+		assert!(calls.contains(&MockCall::SetServiceEnabled { device_instance: DEVICE_INSTANCE, service: ServiceSupported::ReadRange, enabled: true }));
+		assert!(calls.contains(&MockCall::AddObject { device_instance: DEVICE_INSTANCE, object_type: ObjectType::AnalogInput, object_instance: 0 }));
+		assert!(calls.contains(&MockCall::SetPropertyWritable { device_instance: DEVICE_INSTANCE, object_type: ObjectType::AnalogOutput, object_instance: 1, property_identifier: PropertyIdentifier::PresentValue, writable: true }));
+		assert!(calls.contains(&MockCall::SetPropertyEnabled { device_instance: DEVICE_INSTANCE, object_type: ObjectType::MultiStateValue, object_instance: 15, property_identifier: PropertyIdentifier::StateText, enabled: true }));
+
+		// Trend logs are plain objects, ReadRange is answered from the database buffer
+		assert!(calls.contains(&MockCall::AddObject { device_instance: DEVICE_INSTANCE, object_type: ObjectType::TrendLog, object_instance: 0 }));
+
+		// Intrinsic reporting is enabled after the notification class it reports to is added
+		let notification_class = calls.iter().position(|call| matches!(call, MockCall::AddNotificationClassObject { object_instance: 1, .. })).unwrap();
+		let alarms = calls.iter().position(|call| matches!(call, MockCall::EnableAlarmsAndEventsForObject { object_type: ObjectType::AnalogInput, object_instance: 0, notification_class: 1, .. })).unwrap();
+		assert!(notification_class < alarms);
+
+		// Objects that are not served stay out of the stack
+		assert!(!stack.objects().contains(&(DEVICE_INSTANCE, ObjectType::NetworkPort, 56)));
+	}
+
+	#[test]
+	fn reads_properties_through_the_callbacks() {
+		let (_guard, stack) = setup();
+		assert_eq!(stack.get_property_character_string(DEVICE_INSTANCE, ObjectType::AnalogInput, 0, PropertyIdentifier::ObjectName, None).as_deref(), Some("Dungeness AnalogInput"));
+		assert_eq!(stack.get_property_character_string(DEVICE_INSTANCE, ObjectType::Device, DEVICE_INSTANCE, PropertyIdentifier::Description, None).as_deref(), Some("CAS BACnet Rust Server Device"));
+		assert_eq!(stack.get_property_real(DEVICE_INSTANCE, ObjectType::AnalogValue, 2, PropertyIdentifier::PresentValue, None), Some(5.43));
+		assert_eq!(stack.get_property_enumerated(DEVICE_INSTANCE, ObjectType::BinaryInput, 3, PropertyIdentifier::PresentValue, None), Some(1));
+		assert_eq!(stack.get_property_unsigned_integer(DEVICE_INSTANCE, ObjectType::MultiStateInput, 13, PropertyIdentifier::PresentValue, None), Some(1));
+		assert_eq!(stack.get_property_date(DEVICE_INSTANCE, ObjectType::DateValue, 42, PropertyIdentifier::PresentValue, None), Some((122, 1, 28, 5)));
+
+		// Unknown objects fail the callback
+		assert_eq!(stack.get_property_real(DEVICE_INSTANCE, ObjectType::AnalogValue, 99, PropertyIdentifier::PresentValue, None), None);
+	}
+
+	#[test]
+	fn writes_properties_through_the_callbacks() {
+		let (_guard, stack) = setup();
+		assert_eq!(stack.set_property_real(DEVICE_INSTANCE, ObjectType::AnalogOutput, 1, PropertyIdentifier::PresentValue, 42.5, None, 8), Ok(()));
+		assert_eq!(stack.get_property_real(DEVICE_INSTANCE, ObjectType::AnalogOutput, 1, PropertyIdentifier::PresentValue, None), Some(42.5));
+		assert_eq!(stack.set_property_character_string(DEVICE_INSTANCE, ObjectType::CharacterstringValue, 40, PropertyIdentifier::PresentValue, "Grüße".as_bytes(), CharacterSet::Utf8, None, 16), Ok(()));
+		assert_eq!(stack.get_property_character_string(DEVICE_INSTANCE, ObjectType::CharacterstringValue, 40, PropertyIdentifier::PresentValue, None).as_deref(), Some("Grüße"));
+	}
+
+	#[test]
+	fn rejects_writes_to_properties_not_writable() {
+		let (_guard, stack) = setup();
+		assert_eq!(stack.set_property_real(DEVICE_INSTANCE, ObjectType::AnalogValue, 2, PropertyIdentifier::PresentValue, 1.0, None, 16), Err(ErrorCode::WriteAccessDenied));
+		assert_eq!(stack.get_property_real(DEVICE_INSTANCE, ObjectType::AnalogValue, 2, PropertyIdentifier::PresentValue, None), Some(5.43));
+
+		// The present value of an input is only written out of service
+		assert_eq!(stack.set_property_real(DEVICE_INSTANCE, ObjectType::AnalogInput, 0, PropertyIdentifier::PresentValue, 3.0, None, 16), Err(ErrorCode::WriteAccessDenied));
+		assert_eq!(stack.set_property_bool(DEVICE_INSTANCE, ObjectType::AnalogInput, 0, PropertyIdentifier::OutOfService, true, None, 16), Ok(()));
+		assert_eq!(stack.set_property_real(DEVICE_INSTANCE, ObjectType::AnalogInput, 0, PropertyIdentifier::PresentValue, 3.0, None, 16), Ok(()));
+		assert_eq!(stack.get_property_real(DEVICE_INSTANCE, ObjectType::AnalogInput, 0, PropertyIdentifier::PresentValue, None), Some(3.0));
+	}
+	#[test]
+	fn rejects_non_finite_loop_reals() {
+		let (_guard, stack) = setup();
+		for property_identifier in [PropertyIdentifier::Setpoint, PropertyIdentifier::Bias, PropertyIdentifier::MinimumOutput, PropertyIdentifier::MaximumOutput] {
+			assert_eq!(stack.set_property_real(DEVICE_INSTANCE, ObjectType::Loop, 0, property_identifier, f32::NAN, None, 16), Err(ErrorCode::ValueOutOfRange), "{property_identifier}");
 		}
-		return false;
+		// The control keeps running on the configured limits
+		database::update_loops(&mut lock_database(), 0);
+		let present_value = stack.get_property_real(DEVICE_INSTANCE, ObjectType::Loop, 0, PropertyIdentifier::PresentValue, None).unwrap();
+		assert!((0.0..=100.0).contains(&present_value));
+	}
+	#[test]
+	fn time_synchronization_sets_local_time_and_utc_time_synchronization_utc() {
+		let (_guard, stack) = setup();
+		assert!(stack.calls().contains(&MockCall::SetServiceEnabled { device_instance: DEVICE_INSTANCE, service: ServiceSupported::UtcTimeSynchronization, enabled: true }));
+		// Five hours west of Greenwich
+		if let Some(ExampleDatabaseObject::Device(device)) = lock_database().get_mut(&ObjectKey::device(DEVICE_INSTANCE)) {
+			device.clock.utc_offset = 300;
+		}
+		let device_time = || match lock_database().get(&ObjectKey::device(DEVICE_INSTANCE)) {
+			Some(ExampleDatabaseObject::Device(device)) => device.clock.now() as i64,
+			_ => panic!("no device")
+		};
+		let utc_millis = |hour| database::date_time_to_millis(&BacnetDate { year: 126, month: 10, day: 18, weekday: 7 }, &BacnetTime { hour, minute: 0, second: 0, hundredth_seconds: 0 }).unwrap() as i64;
+
+		UTC_TIME_SYNCHRONIZATION.store(false, Ordering::Relaxed);
+		assert_eq!(stack.set_system_time(DEVICE_INSTANCE, (126, 10, 18, 7), (12, 0, 0, 0)), Some(true));
+		assert!((device_time() - utc_millis(17)).abs() < 5000);
+
+		UTC_TIME_SYNCHRONIZATION.store(true, Ordering::Relaxed);
+		assert_eq!(stack.set_system_time(DEVICE_INSTANCE, (126, 10, 18, 7), (12, 0, 0, 0)), Some(true));
+		assert!((device_time() - utc_millis(12)).abs() < 5000);
+		UTC_TIME_SYNCHRONIZATION.store(false, Ordering::Relaxed);
+	}
+	#[test]
+	fn event_enable_writes_reach_the_stack() {
+		let (_guard, stack) = setup();
+		assert_eq!(stack.set_property_bitstring(DEVICE_INSTANCE, ObjectType::AnalogInput, 0, PropertyIdentifier::EventEnable, &[true, false, false], None, 16), Ok(()));
+		update_intrinsic_reporting(&stack);
+		let event_enable = EventTransitions { to_offnormal: true, to_fault: false, to_normal: false };
+		assert!(matches!(stack.calls().last(), Some(MockCall::EnableAlarmsAndEventsForObject { object_type: ObjectType::AnalogInput, object_instance: 0, event_enable: enabled, .. }) if *enabled == event_enable));
+
+		// Only once
+		let calls = stack.calls().len();
+		update_intrinsic_reporting(&stack);
+		assert_eq!(stack.calls().len(), calls);
+	}
+	#[test]
+	fn a_missing_value_fails_the_write_with_an_error_code() {
+		let mut error_code = 0;
+		assert!(!callback_set_bitstring(DEVICE_INSTANCE, ObjectType::AnalogInput.into(), 0, PropertyIdentifier::EventEnable.into(), std::ptr::null(), 3, false, 0, 16, &mut error_code));
+		assert_eq!(error_code, u32::from(ErrorCode::ValueOutOfRange));
+		error_code = 0;
+		assert!(!callback_set_octet_string(DEVICE_INSTANCE, ObjectType::OctetstringValue.into(), 41, PropertyIdentifier::PresentValue.into(), std::ptr::null(), 2, false, 0, 16, &mut error_code));
+		assert_eq!(error_code, u32::from(ErrorCode::ValueOutOfRange));
 	}
-	false
 }
\ No newline at end of file
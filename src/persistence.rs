@@ -0,0 +1,473 @@
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::bacnet_types::{ErrorCode, ObjectType, PropertyIdentifier};
+use crate::config::DatabaseConfig;
+use crate::database::{BacnetDate, BacnetObject, BacnetTime, Database, ExampleDatabaseObject, ObjectKey, PropertyValue};
+
+// One line of the journal, "<crc32 in hex> <json>", so a torn or edited line is detected when it is
+// read back. The kinds are told apart by their fields.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum JournalEntry {
+	Property(JournalRecord),
+	TimeOffset(TimeOffsetRecord)
+}
+
+// One written property value
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct JournalRecord {
+	pub device_instance: u32,
+	pub object_type: ObjectType,
+	pub object_instance: u32,
+	pub property_identifier: PropertyIdentifier,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub property_array_index: Option<u32>,
+	// Command priority, only kept for commandable properties
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub priority: Option<u8>,
+	pub value: PropertyValue
+}
+
+// The offset of a device clock from the host clock in milliseconds, set by time synchronization
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TimeOffsetRecord {
+	pub device_instance: u32,
+	pub time_offset: i64
+}
+
+impl JournalRecord {
+	// Whether this record overwrites an earlier one: the same property and priority, and the same
+	// array element or the whole array. A new array size overwrites nothing, as elements written
+	// after an earlier size are only replayed if it comes first.
+	fn supersedes(&self, earlier: &JournalRecord) -> bool {
+		let key = |record: &JournalRecord| (record.device_instance, record.object_type, record.object_instance, record.property_identifier, record.priority);
+		key(self) == key(earlier) && self.property_array_index != Some(0) && (self.property_array_index.is_none() || self.property_array_index == earlier.property_array_index)
+	}
+}
+
+impl JournalEntry {
+	// Whether this entry overwrites an earlier one, a time offset overwrites the device's last one
+	fn supersedes(&self, earlier: &JournalEntry) -> bool {
+		match (self, earlier) {
+			(JournalEntry::Property(record), JournalEntry::Property(earlier)) => record.supersedes(earlier),
+			(JournalEntry::TimeOffset(record), JournalEntry::TimeOffset(earlier)) => record.device_instance == earlier.device_instance,
+			_ => false
+		}
+	}
+
+	fn encode(&self) -> Result<String, JournalError> {
+		let json = serde_json::to_string(self).map_err(JournalError::Encode)?;
+		Ok(format!("{:08x} {json}\n", crc32fast::hash(json.as_bytes())))
+	}
+
+	fn decode(line: &str) -> Option<JournalEntry> {
+		let (checksum, json) = line.split_once(' ')?;
+		if u32::from_str_radix(checksum, 16).ok()? != crc32fast::hash(json.as_bytes()) {
+			return None;
+		}
+		serde_json::from_str(json).ok()
+	}
+}
+
+impl fmt::Display for JournalRecord {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "device {} {} {} {}", self.device_instance, self.object_type, self.object_instance, self.property_identifier)?;
+		if let Some(property_array_index) = self.property_array_index {
+			write!(f, "[{property_array_index}]")?;
+		}
+		if let Some(priority) = self.priority {
+			write!(f, " at priority {priority}")?;
+		}
+		Ok(())
+	}
+}
+
+impl fmt::Display for TimeOffsetRecord {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "device {} clock offset", self.device_instance)
+	}
+}
+
+impl fmt::Display for JournalEntry {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			JournalEntry::Property(record) => record.fmt(f),
+			JournalEntry::TimeOffset(record) => record.fmt(f)
+		}
+	}
+}
+
+#[derive(Debug)]
+pub enum JournalError {
+	Io(PathBuf, io::Error),
+	Encode(serde_json::Error)
+}
+
+impl fmt::Display for JournalError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			JournalError::Io(path, err) => write!(f, "Journal {}: {}", path.display(), err),
+			JournalError::Encode(err) => write!(f, "Unable to encode journal record: {err}")
+		}
+	}
+}
+
+impl std::error::Error for JournalError {}
+
+// Valid entries read from a journal, and the line numbers that failed their checksum
+pub struct JournalContents {
+	pub records: Vec<JournalEntry>,
+	pub corrupt_lines: Vec<usize>
+}
+
+// Append-only log of written property values
+pub struct Journal {
+	path: PathBuf,
+	file: File
+}
+
+impl Journal {
+	// Reads every record that passes its checksum. A missing journal is empty.
+	pub fn read(path: &Path) -> Result<JournalContents, JournalError> {
+		let io_error = |err| JournalError::Io(path.to_path_buf(), err);
+		let mut contents = JournalContents { records: Vec::new(), corrupt_lines: Vec::new() };
+		let file = match File::open(path) {
+			Ok(file) => file,
+			Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(contents),
+			Err(err) => return Err(io_error(err))
+		};
+		for (index, line) in BufReader::new(file).split(b'\n').enumerate() {
+			let line = line.map_err(io_error)?;
+			if line.is_empty() {
+				continue;
+			}
+			match std::str::from_utf8(&line).ok().and_then(JournalEntry::decode) {
+				Some(record) => contents.records.push(record),
+				None => contents.corrupt_lines.push(index + 1)
+			}
+		}
+		Ok(contents)
+	}
+
+	// Atomically replaces the journal with the given entries and opens it for appending. The new
+	// contents are written to a temporary file which is synced and then renamed over the journal.
+	pub fn create(path: &Path, records: &[JournalEntry]) -> Result<Journal, JournalError> {
+		let io_error = |err| JournalError::Io(path.to_path_buf(), err);
+		let mut temporary_path = path.as_os_str().to_owned();
+		temporary_path.push(".tmp");
+		let temporary_path = PathBuf::from(temporary_path);
+
+		let mut contents = String::new();
+		for record in records {
+			contents.push_str(&record.encode()?);
+		}
+		let mut temporary_file = File::create(&temporary_path).map_err(io_error)?;
+		temporary_file.write_all(contents.as_bytes()).map_err(io_error)?;
+		temporary_file.sync_all().map_err(io_error)?;
+		drop(temporary_file);
+		fs::rename(&temporary_path, path).map_err(io_error)?;
+		sync_parent_directory(path);
+
+		let file = OpenOptions::new().append(true).open(path).map_err(io_error)?;
+		Ok(Journal { path: path.to_path_buf(), file })
+	}
+
+	// Appends one entry with a single write and syncs it to disk
+	pub fn append(&mut self, record: &JournalEntry) -> Result<(), JournalError> {
+		let line = record.encode()?;
+		self.file.write_all(line.as_bytes()).and_then(|_| self.file.sync_data()).map_err(|err| JournalError::Io(self.path.clone(), err))
+	}
+
+	pub fn path(&self) -> &Path {
+		&self.path
+	}
+}
+
+// Makes the rename durable. Directories cannot be opened for syncing on every platform.
+fn sync_parent_directory(path: &Path) {
+	if let Some(directory) = path.parent().filter(|directory| !directory.as_os_str().is_empty()) {
+		if let Ok(directory) = File::open(directory) {
+			let _ = directory.sync_all();
+		}
+	}
+}
+
+// Reads the journal, applies its records over the configured values and rewrites it with only the
+// latest record for each property. A journal with corrupt lines is kept as <journal>.corrupt first.
+pub fn restore(path: &Path, config: &DatabaseConfig, database: &mut Database) -> Result<Journal, JournalError> {
+	let contents = Journal::read(path)?;
+	if !contents.corrupt_lines.is_empty() {
+		let mut backup_path = path.as_os_str().to_owned();
+		backup_path.push(".corrupt");
+		fs::copy(path, &backup_path).map_err(|err| JournalError::Io(path.to_path_buf(), err))?;
+		println!("WARNING: Journal {} has corrupt lines {:?}, which were skipped. The original was copied to {}", path.display(), contents.corrupt_lines, PathBuf::from(backup_path).display());
+	}
+
+	let mut latest: Vec<JournalEntry> = Vec::new();
+	for record in contents.records {
+		if let Err(reason) = replay(config, database, &record) {
+			println!("WARNING: Journaled value for {record} was not restored: {reason}");
+			continue;
+		}
+		// Kept in the order written, so an element written after the whole array is replayed after it
+		latest.retain(|existing| !record.supersedes(existing));
+		latest.push(record);
+	}
+	if !latest.is_empty() {
+		println!("Restored {} journaled value(s) from {}", latest.len(), path.display());
+	}
+	Journal::create(path, &latest)
+}
+
+// Applies a journaled entry, a property value only if the property is still configured as writable
+fn replay(config: &DatabaseConfig, database: &mut Database, entry: &JournalEntry) -> Result<(), String> {
+	let record = match entry {
+		JournalEntry::Property(record) => record,
+		JournalEntry::TimeOffset(record) => return replay_time_offset(database, record)
+	};
+	if record.device_instance != config.device.instance {
+		return Err(format!("device {} is not configured", record.device_instance));
+	}
+	let is_writable = if record.object_type == ObjectType::Device && record.object_instance == config.device.instance {
+		config.device.writable_properties.contains(&record.property_identifier)
+	} else {
+		match config.objects.iter().find(|object| object.object_type == record.object_type && object.instance == record.object_instance) {
+			Some(object) => object.is_writable(record.property_identifier),
+			None => return Err("the object is not configured".to_string())
+		}
+	};
+	if !is_writable {
+		return Err("the property is not writable".to_string());
+	}
+	write_property(database, None, record).map(|_| ()).map_err(|error_code| error_code.to_string())
+}
+
+fn replay_time_offset(database: &mut Database, record: &TimeOffsetRecord) -> Result<(), String> {
+	match database.get_mut(&ObjectKey::device(record.device_instance)) {
+		Some(ExampleDatabaseObject::Device(device)) => {
+			device.clock.time_offset = record.time_offset;
+			Ok(())
+		},
+		_ => Err(format!("device {} is not configured", record.device_instance))
+	}
+}
+
+// Sets a device clock from time synchronization, to a UTC date and time for UTCTimeSynchronization
+// or a local one otherwise, and journals its offset from the host clock, returning the record
+pub fn set_device_time(database: &mut Database, journal: Option<&mut Journal>, device_instance: u32, date: &BacnetDate, time: &BacnetTime, utc: bool) -> Result<TimeOffsetRecord, ErrorCode> {
+	let Some(ExampleDatabaseObject::Device(device)) = database.get_mut(&ObjectKey::device(device_instance)) else {
+		return Err(ErrorCode::UnknownObject);
+	};
+	if utc {
+		device.clock.set_utc_date_time(date, time)?;
+	} else {
+		device.clock.set_local_date_time(date, time)?;
+	}
+	let record = TimeOffsetRecord { device_instance, time_offset: device.clock.time_offset };
+	if let Some(journal) = journal {
+		if let Err(err) = journal.append(&JournalEntry::TimeOffset(record.clone())) {
+			println!("ERROR: Time offset for {record} was set but not journaled: {err}");
+		}
+	}
+	Ok(record)
+}
+
+// Applies a client write to the database and journals it, returning the record as applied. A value
+// that cannot be journaled is still written, the failure is logged since the live value is correct.
+pub fn write_property(database: &mut Database, journal: Option<&mut Journal>, record: &JournalRecord) -> Result<JournalRecord, ErrorCode> {
+	let key = ObjectKey::new(record.device_instance, record.object_type, record.object_instance);
+	let object = database.get_mut(&key).ok_or(ErrorCode::UnknownObject)?;
+	let priority = record.priority.filter(|_| object.is_commandable(record.property_identifier));
+	match priority {
+		// Commandable properties are not arrays
+		Some(_) if record.property_array_index.is_some() => return Err(ErrorCode::PropertyIsNotAnArray),
+		Some(priority) => object.command(record.property_identifier, priority, &record.value)?,
+		None => object.set_property(record.property_identifier, record.property_array_index, &record.value)?
+	}
+	// A date and time is written as two partial values, so the resulting value is journaled
+	let value = match record.value {
+		PropertyValue::Date(_) | PropertyValue::Time(_) => object.get_property(record.property_identifier, record.property_array_index).unwrap_or_else(|_| record.value.clone()),
+		_ => record.value.clone()
+	};
+	let record = JournalRecord { priority, value, ..record.clone() };
+	if let Some(journal) = journal {
+		if let Err(err) = journal.append(&JournalEntry::Property(record.clone())) {
+			println!("ERROR: Value for {record} was written but not journaled: {err}");
+		}
+	}
+	Ok(record)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::config::{self, CommandLine};
+	use crate::database;
+
+	const DEVICE_INSTANCE: u32 = 389001;
+
+	fn example_config() -> DatabaseConfig {
+		let command_line = CommandLine { config_file: Some(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("config.example.toml")), ..Default::default() };
+		config::load(&command_line).unwrap().0
+	}
+
+	// A journal path in an empty directory of its own under the system temporary directory
+	fn journal_path(test: &str) -> PathBuf {
+		let directory = std::env::temp_dir().join(format!("bacnet-persistence-{}-{test}", std::process::id()));
+		let _ = fs::remove_dir_all(&directory);
+		fs::create_dir_all(&directory).unwrap();
+		directory.join("bacnet_values.jsonl")
+	}
+
+	fn record(object_type: ObjectType, object_instance: u32, property_identifier: PropertyIdentifier, property_array_index: Option<u32>, priority: Option<u8>, value: PropertyValue) -> JournalRecord {
+		JournalRecord { device_instance: DEVICE_INSTANCE, object_type, object_instance, property_identifier, property_array_index, priority, value }
+	}
+
+	fn present_value(value: f32) -> JournalRecord {
+		record(ObjectType::AnalogOutput, 1, PropertyIdentifier::PresentValue, None, Some(8), PropertyValue::Real(value))
+	}
+
+	fn relinquish_default(value: f32) -> JournalRecord {
+		record(ObjectType::AnalogOutput, 1, PropertyIdentifier::RelinquishDefault, None, None, PropertyValue::Real(value))
+	}
+
+	// The configured database with the journal at path restored over it
+	fn restored(path: &Path, config: &DatabaseConfig) -> (Journal, Database) {
+		let mut database = database::setup_database(config).unwrap();
+		let journal = restore(path, config, &mut database).unwrap();
+		(journal, database)
+	}
+
+	fn property(database: &Database, object_type: ObjectType, object_instance: u32, property_identifier: PropertyIdentifier, property_array_index: Option<u32>) -> PropertyValue {
+		database[&ObjectKey::new(DEVICE_INSTANCE, object_type, object_instance)].get_property(property_identifier, property_array_index).unwrap()
+	}
+
+	fn write_journal(path: &Path, config: &DatabaseConfig, records: &[JournalRecord]) {
+		let mut database = database::setup_database(config).unwrap();
+		let mut journal = Journal::create(path, &[]).unwrap();
+		for record in records {
+			write_property(&mut database, Some(&mut journal), record).unwrap();
+		}
+	}
+
+	#[test]
+	fn restores_written_values() {
+		let config = example_config();
+		let path = journal_path("restores_written_values");
+		write_journal(&path, &config, &[present_value(42.5), relinquish_default(3.0)]);
+		let (_, database) = restored(&path, &config);
+		assert_eq!(property(&database, ObjectType::AnalogOutput, 1, PropertyIdentifier::PresentValue, None), PropertyValue::Real(42.5));
+		assert_eq!(property(&database, ObjectType::AnalogOutput, 1, PropertyIdentifier::RelinquishDefault, None), PropertyValue::Real(3.0));
+	}
+
+	#[test]
+	fn a_missing_journal_restores_nothing_and_is_created() {
+		let config = example_config();
+		let path = journal_path("a_missing_journal");
+		let (mut journal, database) = restored(&path, &config);
+		assert_eq!(property(&database, ObjectType::AnalogOutput, 1, PropertyIdentifier::RelinquishDefault, None), PropertyValue::Real(0.0));
+		assert_eq!(fs::read_to_string(&path).unwrap(), "");
+		journal.append(&JournalEntry::Property(relinquish_default(1.0))).unwrap();
+		assert_eq!(Journal::read(&path).unwrap().records.len(), 1);
+	}
+
+	#[test]
+	fn skips_a_torn_last_line() {
+		let config = example_config();
+		let path = journal_path("skips_a_torn_last_line");
+		write_journal(&path, &config, &[present_value(42.5), relinquish_default(3.0)]);
+		// A write cut short by a crash
+		let torn = JournalEntry::Property(relinquish_default(7.0)).encode().unwrap();
+		OpenOptions::new().append(true).open(&path).unwrap().write_all(&torn.as_bytes()[..torn.len() / 2]).unwrap();
+
+		let contents = Journal::read(&path).unwrap();
+		assert_eq!((contents.records.len(), contents.corrupt_lines), (2, vec![3]));
+		let (_, database) = restored(&path, &config);
+		assert_eq!(property(&database, ObjectType::AnalogOutput, 1, PropertyIdentifier::RelinquishDefault, None), PropertyValue::Real(3.0));
+
+		// The original is kept, the journal is rewritten without the torn line
+		let backup = fs::read_to_string(path.with_extension("jsonl.corrupt")).unwrap();
+		assert_eq!(backup.lines().count(), 3);
+		assert_eq!(Journal::read(&path).unwrap().corrupt_lines, Vec::<usize>::new());
+	}
+
+	#[test]
+	fn skips_a_line_with_a_flipped_checksum() {
+		let config = example_config();
+		let path = journal_path("skips_a_line_with_a_flipped_checksum");
+		write_journal(&path, &config, &[present_value(42.5), relinquish_default(3.0)]);
+		let contents = fs::read_to_string(&path).unwrap();
+		let flipped = if contents.starts_with('0') { "1" } else { "0" };
+		fs::write(&path, format!("{flipped}{}", &contents[1..])).unwrap();
+
+		assert_eq!(Journal::read(&path).unwrap().corrupt_lines, vec![1]);
+		let (_, database) = restored(&path, &config);
+		assert_eq!(property(&database, ObjectType::AnalogOutput, 1, PropertyIdentifier::PriorityArray, Some(8)), PropertyValue::Null);
+		assert_eq!(property(&database, ObjectType::AnalogOutput, 1, PropertyIdentifier::RelinquishDefault, None), PropertyValue::Real(3.0));
+	}
+
+	#[test]
+	fn compaction_keeps_the_latest_value_of_each_property() {
+		let config = example_config();
+		let path = journal_path("compaction_keeps_the_latest_value");
+		write_journal(&path, &config, &[relinquish_default(1.0), present_value(42.5), relinquish_default(2.0), relinquish_default(3.0)]);
+		let (_, database) = restored(&path, &config);
+		assert_eq!(property(&database, ObjectType::AnalogOutput, 1, PropertyIdentifier::RelinquishDefault, None), PropertyValue::Real(3.0));
+		let entries = Journal::read(&path).unwrap().records;
+		assert_eq!(entries, vec![JournalEntry::Property(present_value(42.5)), JournalEntry::Property(relinquish_default(3.0))]);
+	}
+
+	#[test]
+	fn an_element_written_after_the_whole_array_survives_compaction() {
+		let config = example_config();
+		let path = journal_path("an_element_written_after_the_whole_array");
+		let proprietary_array = PropertyIdentifier::try_from(513).unwrap();
+		let whole_array = record(ObjectType::AnalogInput, 0, proprietary_array, None, None, PropertyValue::Array(vec![PropertyValue::Real(2.0); 5]));
+		let element = record(ObjectType::AnalogInput, 0, proprietary_array, Some(3), None, PropertyValue::Real(9.0));
+		write_journal(&path, &config, &[element.clone(), whole_array.clone(), element.clone()]);
+
+		// The element written before the whole array is superseded by it, the one after is kept after it
+		let (_, database) = restored(&path, &config);
+		assert_eq!(Journal::read(&path).unwrap().records, vec![JournalEntry::Property(whole_array), JournalEntry::Property(element)]);
+		let expected = [2.0, 2.0, 9.0, 2.0, 2.0].map(PropertyValue::Real).to_vec();
+		assert_eq!(property(&database, ObjectType::AnalogInput, 0, proprietary_array, None), PropertyValue::Array(expected));
+
+		// And again after restoring the compacted journal
+		let (_, database) = restored(&path, &config);
+		assert_eq!(property(&database, ObjectType::AnalogInput, 0, proprietary_array, Some(3)), PropertyValue::Real(9.0));
+	}
+
+	#[test]
+	fn elements_written_after_a_resize_are_restored() {
+		let config = example_config();
+		let path = journal_path("elements_written_after_a_resize");
+		let proprietary_array = PropertyIdentifier::try_from(513).unwrap();
+		let resize = |length| record(ObjectType::AnalogInput, 0, proprietary_array, Some(0), None, PropertyValue::Unsigned(length));
+		let element = record(ObjectType::AnalogInput, 0, proprietary_array, Some(7), None, PropertyValue::Real(9.0));
+		write_journal(&path, &config, &[resize(7), element, resize(7)]);
+		for _ in 0..2 {
+			let (_, database) = restored(&path, &config);
+			assert_eq!(property(&database, ObjectType::AnalogInput, 0, proprietary_array, Some(0)), PropertyValue::Unsigned(7));
+			assert_eq!(property(&database, ObjectType::AnalogInput, 0, proprietary_array, Some(7)), PropertyValue::Real(9.0));
+		}
+	}
+
+	#[test]
+	fn restores_the_latest_time_offset() {
+		let config = example_config();
+		let path = journal_path("restores_the_latest_time_offset");
+		let entries = [-5000, 60_000].map(|time_offset| JournalEntry::TimeOffset(TimeOffsetRecord { device_instance: DEVICE_INSTANCE, time_offset }));
+		Journal::create(&path, &entries).unwrap();
+		let (_, database) = restored(&path, &config);
+		let Some(ExampleDatabaseObject::Device(device)) = database.get(&ObjectKey::device(DEVICE_INSTANCE)) else {
+			panic!("the device is not in the database");
+		};
+		assert_eq!(device.clock.time_offset, 60_000);
+		assert_eq!(Journal::read(&path).unwrap().records, entries[1..]);
+	}
+}
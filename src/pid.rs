@@ -0,0 +1,264 @@
+// PID control of the Loop objects. The output is the bias plus the proportional, integral and
+// derivative terms of the error between the setpoint and the controlled variable, within the limits.
+
+use crate::bacnet_types::{Action, EngineeringUnits, ErrorCode, PropertyIdentifier};
+use crate::database::PropertyValue;
+
+pub struct Pid {
+	// Direct action raises the output as the controlled variable rises above the setpoint, reverse
+	// action as it falls below it
+	pub action: Action,
+	pub proportional_constant: f32,
+	pub proportional_constant_units: EngineeringUnits,
+	// Per second, minute or hour
+	pub integral_constant: f32,
+	pub integral_constant_units: EngineeringUnits,
+	// Seconds, minutes or hours
+	pub derivative_constant: f32,
+	pub derivative_constant_units: EngineeringUnits,
+	pub bias: f32,
+	pub minimum_output: f32,
+	pub maximum_output: f32,
+	// The integral of the error over time in seconds
+	integral: f32,
+	// The error at the last update, and when in milliseconds since the Unix epoch
+	last_error: Option<(f32, u64)>
+}
+
+// No control action until the constants are set, integral per minute and derivative in minutes
+impl Default for Pid {
+	fn default() -> Pid {
+		Pid {
+			action: Action::Direct,
+			proportional_constant: 0.0,
+			proportional_constant_units: EngineeringUnits::NoUnits,
+			integral_constant: 0.0,
+			integral_constant_units: EngineeringUnits::PerMinute,
+			derivative_constant: 0.0,
+			derivative_constant_units: EngineeringUnits::Minutes,
+			bias: 0.0,
+			minimum_output: 0.0,
+			maximum_output: 100.0,
+			integral: 0.0,
+			last_error: None
+		}
+	}
+}
+
+impl Pid {
+	// Seconds in the time unit of the integral constant
+	pub fn integral_seconds(units: EngineeringUnits) -> Option<f32> {
+		match units {
+			EngineeringUnits::PerSecond => Some(1.0),
+			EngineeringUnits::PerMinute => Some(60.0),
+			EngineeringUnits::PerHour => Some(3600.0),
+			_ => None
+		}
+	}
+
+	// Seconds in the time unit of the derivative constant
+	pub fn derivative_seconds(units: EngineeringUnits) -> Option<f32> {
+		match units {
+			EngineeringUnits::Seconds => Some(1.0),
+			EngineeringUnits::Minutes => Some(60.0),
+			EngineeringUnits::Hours => Some(3600.0),
+			_ => None
+		}
+	}
+
+	// Forgets the integral and the last error, so the next update starts over
+	pub fn reset(&mut self) {
+		self.integral = 0.0;
+		self.last_error = None;
+	}
+
+	// The output at now, in milliseconds since the Unix epoch. The integral only accumulates while the
+	// output is within the limits, so it does not wind up while the output is held at one.
+	pub fn update(&mut self, now: u64, setpoint: f32, controlled_variable_value: f32) -> f32 {
+		let error = match self.action {
+			Action::Direct => controlled_variable_value - setpoint,
+			Action::Reverse => setpoint - controlled_variable_value
+		};
+		let integral_seconds = Pid::integral_seconds(self.integral_constant_units).unwrap_or(1.0);
+		let derivative_seconds = Pid::derivative_seconds(self.derivative_constant_units).unwrap_or(1.0);
+		let (integral, derivative) = match self.last_error {
+			Some((last_error, last_update)) if now > last_update => {
+				let elapsed = (now - last_update) as f32 / 1000.0;
+				(self.integral + error * elapsed, (error - last_error) / elapsed)
+			},
+			_ => (self.integral, 0.0)
+		};
+		let output = self.bias
+			+ self.proportional_constant * error
+			+ self.integral_constant / integral_seconds * integral
+			+ self.derivative_constant * derivative_seconds * derivative;
+		if (self.minimum_output..=self.maximum_output).contains(&output) {
+			self.integral = integral;
+		}
+		self.last_error = Some((error, now));
+		output.clamp(self.minimum_output, self.maximum_output)
+	}
+
+	// Reads the control properties, None for any other property
+	pub fn get_property(&self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>) -> Option<Result<PropertyValue, ErrorCode>> {
+		let value = match property_identifier {
+			PropertyIdentifier::Action => PropertyValue::Enumerated(self.action.into()),
+			PropertyIdentifier::ProportionalConstant => PropertyValue::Real(self.proportional_constant),
+			PropertyIdentifier::ProportionalConstantUnits => PropertyValue::Enumerated(self.proportional_constant_units.into()),
+			PropertyIdentifier::IntegralConstant => PropertyValue::Real(self.integral_constant),
+			PropertyIdentifier::IntegralConstantUnits => PropertyValue::Enumerated(self.integral_constant_units.into()),
+			PropertyIdentifier::DerivativeConstant => PropertyValue::Real(self.derivative_constant),
+			PropertyIdentifier::DerivativeConstantUnits => PropertyValue::Enumerated(self.derivative_constant_units.into()),
+			PropertyIdentifier::Bias => PropertyValue::Real(self.bias),
+			PropertyIdentifier::MinimumOutput => PropertyValue::Real(self.minimum_output),
+			PropertyIdentifier::MaximumOutput => PropertyValue::Real(self.maximum_output),
+			_ => return None
+		};
+		match property_array_index {
+			Some(_) => Some(Err(ErrorCode::PropertyIsNotAnArray)),
+			None => Some(Ok(value))
+		}
+	}
+
+	// Writes the control properties, None for any other property
+	pub fn set_property(&mut self, property_identifier: PropertyIdentifier, property_array_index: Option<u32>, value: &PropertyValue) -> Option<Result<(), ErrorCode>> {
+		let current = self.get_property(property_identifier, property_array_index)?;
+		Some(current.and_then(|_| self.write(property_identifier, value)))
+	}
+
+	fn write(&mut self, property_identifier: PropertyIdentifier, value: &PropertyValue) -> Result<(), ErrorCode> {
+		match (property_identifier, value) {
+			// A NaN or infinite limit would make the output undefined
+			(_, PropertyValue::Real(value)) if !value.is_finite() => return Err(ErrorCode::ValueOutOfRange),
+			(PropertyIdentifier::Action, PropertyValue::Enumerated(action)) => self.action = Action::try_from(*action).map_err(|_| ErrorCode::ValueOutOfRange)?,
+			(PropertyIdentifier::ProportionalConstant, PropertyValue::Real(value)) => self.proportional_constant = *value,
+			(PropertyIdentifier::IntegralConstant, PropertyValue::Real(value)) => self.integral_constant = *value,
+			(PropertyIdentifier::DerivativeConstant, PropertyValue::Real(value)) => self.derivative_constant = *value,
+			(PropertyIdentifier::Bias, PropertyValue::Real(value)) => self.bias = *value,
+			(PropertyIdentifier::MinimumOutput, PropertyValue::Real(value)) if *value > self.maximum_output => return Err(ErrorCode::ValueOutOfRange),
+			(PropertyIdentifier::MinimumOutput, PropertyValue::Real(value)) => self.minimum_output = *value,
+			(PropertyIdentifier::MaximumOutput, PropertyValue::Real(value)) if *value < self.minimum_output => return Err(ErrorCode::ValueOutOfRange),
+			(PropertyIdentifier::MaximumOutput, PropertyValue::Real(value)) => self.maximum_output = *value,
+			(PropertyIdentifier::ProportionalConstantUnits | PropertyIdentifier::IntegralConstantUnits | PropertyIdentifier::DerivativeConstantUnits, PropertyValue::Enumerated(units)) => {
+				let units = EngineeringUnits::try_from(*units).map_err(|_| ErrorCode::ValueOutOfRange)?;
+				match property_identifier {
+					PropertyIdentifier::ProportionalConstantUnits => self.proportional_constant_units = units,
+					PropertyIdentifier::IntegralConstantUnits if Pid::integral_seconds(units).is_some() => self.integral_constant_units = units,
+					PropertyIdentifier::DerivativeConstantUnits if Pid::derivative_seconds(units).is_some() => self.derivative_constant_units = units,
+					_ => return Err(ErrorCode::ValueOutOfRange)
+				}
+			},
+			_ => return Err(ErrorCode::InvalidDataType)
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn proportional(action: Action) -> Pid {
+		Pid { action, proportional_constant: 2.0, bias: 50.0, ..Pid::default() }
+	}
+
+	// Only an integral term of 1 per time unit
+	fn integral(units: EngineeringUnits) -> Pid {
+		Pid { integral_constant: 1.0, integral_constant_units: units, ..Pid::default() }
+	}
+
+	// Only a derivative term of 1 time unit, around a bias of 50
+	fn derivative(units: EngineeringUnits) -> Pid {
+		Pid { derivative_constant: 1.0, derivative_constant_units: units, bias: 50.0, ..Pid::default() }
+	}
+
+	#[test]
+	fn direct_action_raises_the_output_above_the_setpoint() {
+		let mut pid = proportional(Action::Direct);
+		assert_eq!(pid.update(0, 20.0, 25.0), 60.0);
+		assert_eq!(pid.update(1000, 20.0, 15.0), 40.0);
+	}
+
+	#[test]
+	fn reverse_action_raises_the_output_below_the_setpoint() {
+		let mut pid = proportional(Action::Reverse);
+		assert_eq!(pid.update(0, 20.0, 25.0), 40.0);
+		assert_eq!(pid.update(1000, 20.0, 15.0), 60.0);
+	}
+
+	#[test]
+	fn the_integral_constant_is_per_its_time_unit() {
+		// An error of 1 held for a minute
+		for (units, expected) in [(EngineeringUnits::PerSecond, 60.0), (EngineeringUnits::PerMinute, 1.0), (EngineeringUnits::PerHour, 1.0 / 60.0)] {
+			let mut pid = integral(units);
+			assert_eq!(pid.update(0, 20.0, 21.0), 0.0);
+			assert!((pid.update(60_000, 20.0, 21.0) - expected).abs() < 1e-4, "{units}");
+		}
+	}
+
+	#[test]
+	fn the_derivative_constant_is_in_its_time_unit() {
+		// The error rising by 1 over a minute
+		for (units, expected) in [(EngineeringUnits::Seconds, 50.0 + 1.0 / 60.0), (EngineeringUnits::Minutes, 51.0), (EngineeringUnits::Hours, 110.0)] {
+			let mut pid = Pid { maximum_output: 200.0, ..derivative(units) };
+			assert_eq!(pid.update(0, 20.0, 20.0), 50.0);
+			assert!((pid.update(60_000, 20.0, 21.0) - expected).abs() < 1e-4, "{units}");
+		}
+	}
+
+	#[test]
+	fn the_integral_stops_growing_while_the_output_is_saturated() {
+		let mut pid = integral(EngineeringUnits::PerSecond);
+		// An error of 10 each second reaches the maximum of 100 after 10 seconds
+		for second in 0..=30 {
+			let output = pid.update(second * 1000, 20.0, 30.0);
+			assert_eq!(output, (second as f32 * 10.0).min(100.0));
+		}
+		assert_eq!(pid.integral, 100.0);
+
+		// So the output leaves the limit as soon as the error reverses
+		assert_eq!(pid.update(31_000, 20.0, 10.0), 90.0);
+	}
+
+	#[test]
+	fn the_integral_does_not_wind_below_the_minimum() {
+		let mut pid = Pid { minimum_output: -10.0, ..integral(EngineeringUnits::PerSecond) };
+		for second in 0..=10 {
+			assert_eq!(pid.update(second * 1000, 20.0, 15.0), (second as f32 * -5.0).max(-10.0));
+		}
+		assert_eq!(pid.integral, -10.0);
+	}
+
+	#[test]
+	fn reset_forgets_the_integral_and_last_error() {
+		let mut pid = integral(EngineeringUnits::PerSecond);
+		pid.update(0, 20.0, 30.0);
+		assert_eq!(pid.update(5000, 20.0, 30.0), 50.0);
+		pid.reset();
+		assert_eq!(pid.update(6000, 20.0, 30.0), 0.0);
+	}
+
+	#[test]
+	fn constant_units_must_be_time_units() {
+		let mut pid = Pid::default();
+		let seconds = PropertyValue::Enumerated(EngineeringUnits::Seconds.into());
+		let per_hour = PropertyValue::Enumerated(EngineeringUnits::PerHour.into());
+		assert_eq!(pid.set_property(PropertyIdentifier::IntegralConstantUnits, None, &seconds), Some(Err(ErrorCode::ValueOutOfRange)));
+		assert_eq!(pid.set_property(PropertyIdentifier::IntegralConstantUnits, None, &per_hour), Some(Ok(())));
+		assert_eq!(pid.set_property(PropertyIdentifier::DerivativeConstantUnits, None, &per_hour), Some(Err(ErrorCode::ValueOutOfRange)));
+		assert_eq!(pid.set_property(PropertyIdentifier::DerivativeConstantUnits, None, &seconds), Some(Ok(())));
+		assert_eq!(pid.integral_constant_units, EngineeringUnits::PerHour);
+		assert_eq!(pid.derivative_constant_units, EngineeringUnits::Seconds);
+	}
+
+	#[test]
+	fn non_finite_reals_are_rejected() {
+		let mut pid = proportional(Action::Direct);
+		for property_identifier in [PropertyIdentifier::ProportionalConstant, PropertyIdentifier::IntegralConstant, PropertyIdentifier::DerivativeConstant, PropertyIdentifier::Bias, PropertyIdentifier::MinimumOutput, PropertyIdentifier::MaximumOutput] {
+			for value in [f32::NAN, f32::INFINITY, f32::NEG_INFINITY] {
+				assert_eq!(pid.set_property(property_identifier, None, &PropertyValue::Real(value)), Some(Err(ErrorCode::ValueOutOfRange)), "{property_identifier}");
+			}
+		}
+		assert_eq!(pid.update(0, 20.0, 25.0), 60.0);
+	}
+}
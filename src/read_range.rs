@@ -0,0 +1,646 @@
+// ReadRange of the trend log buffers. The library has no callback for log records, so a ReadRange
+// request for the Log_Buffer of a database trend log is taken out of the received messages before
+// the stack sees it, and answered from the database log buffer. Only unsegmented requests from the
+// local network or routed to it are answered here, the stack handles the rest. A request forwarded
+// by a BBMD is answered directly to the device that sent it. The same decoding tells a received
+// UTCTimeSynchronization from a TimeSynchronization.
+
+use std::net::{Ipv4Addr, SocketAddrV4};
+
+use crate::bacnet_types::{ErrorCode, ObjectType, PropertyIdentifier};
+use crate::database::{BacnetDate, BacnetTime, PropertyValue};
+use crate::trend_log::{ReadRange, ReadRangeResult};
+
+const BVLC_TYPE_BACNET_IP: u8 = 0x81;
+const BVLC_FORWARDED_NPDU: u8 = 0x04;
+const BVLC_ORIGINAL_UNICAST_NPDU: u8 = 0x0A;
+const BVLC_ORIGINAL_BROADCAST_NPDU: u8 = 0x0B;
+const NPDU_VERSION: u8 = 0x01;
+const PDU_TYPE_CONFIRMED_REQUEST: u8 = 0x00;
+const PDU_TYPE_UNCONFIRMED_REQUEST: u8 = 0x10;
+const PDU_TYPE_COMPLEX_ACK: u8 = 0x30;
+const PDU_TYPE_ERROR: u8 = 0x50;
+const SERVICE_CHOICE_READ_RANGE: u8 = 26;
+const SERVICE_CHOICE_UTC_TIME_SYNCHRONIZATION: u8 = 9;
+
+// Maximum APDU lengths by the code in a confirmed request
+const MAX_APDU_LENGTHS: [usize; 6] = [50, 128, 206, 480, 1024, 1476];
+
+// Application tag numbers
+const TAG_NULL: u8 = 0;
+const TAG_BOOLEAN: u8 = 1;
+const TAG_UNSIGNED: u8 = 2;
+const TAG_SIGNED: u8 = 3;
+const TAG_REAL: u8 = 4;
+const TAG_DOUBLE: u8 = 5;
+const TAG_OCTET_STRING: u8 = 6;
+const TAG_CHARACTER_STRING: u8 = 7;
+const TAG_BIT_STRING: u8 = 8;
+const TAG_ENUMERATED: u8 = 9;
+const TAG_DATE: u8 = 10;
+const TAG_TIME: u8 = 11;
+
+// A ReadRange request for the Log_Buffer of an object
+#[derive(Debug, PartialEq)]
+pub struct ReadRangeRequest {
+	// Source network and address of a routed request, the reply is sent back through the router
+	source: Option<(u16, Vec<u8>)>,
+	// B/IP address of the original sender of a request forwarded by a BBMD
+	pub originating_address: Option<SocketAddrV4>,
+	invoke_id: u8,
+	max_apdu_length: usize,
+	pub object_type: ObjectType,
+	pub object_instance: u32,
+	pub property_array_index: Option<u32>,
+	// None when a by time reference is not a complete date and time
+	pub range: Option<ReadRange>
+}
+
+// Decodes a ReadRange request for a Log_Buffer from a received BACnet/IP message. None for any other
+// message, which is left to the stack. The reference time of a by time range is local time, and is
+// converted to milliseconds since the Unix epoch by to_millis.
+pub fn decode_request(message: &[u8], to_millis: impl Fn(&BacnetDate, &BacnetTime) -> Option<u64>) -> Option<ReadRangeRequest> {
+	let mut reader = Reader { bytes: message, position: 0 };
+	let header = decode_header(&mut reader)?;
+	// Messages for other networks are left to the stack
+	if header.for_other_network {
+		return None;
+	}
+	let (originating_address, source) = (header.originating_address, header.source);
+
+	// APDU header of an unsegmented confirmed request
+	let pdu_type = reader.byte()?;
+	if pdu_type & 0xF0 != PDU_TYPE_CONFIRMED_REQUEST || pdu_type & 0x08 != 0 {
+		return None;
+	}
+	let max_apdu_length = *MAX_APDU_LENGTHS.get((reader.byte()? & 0x0F) as usize)?;
+	let invoke_id = reader.byte()?;
+	if reader.byte()? != SERVICE_CHOICE_READ_RANGE {
+		return None;
+	}
+
+	// [0] objectIdentifier, [1] propertyIdentifier, [2] propertyArrayIndex OPTIONAL
+	let object_identifier = reader.context_unsigned(0)?;
+	let object_type = ObjectType::try_from((object_identifier >> 22) as u16).ok()?;
+	let object_instance = object_identifier & 0x3F_FFFF;
+	if reader.context_unsigned(1)? != u32::from(PropertyIdentifier::LogBuffer) {
+		return None;
+	}
+	let property_array_index = match reader.peek_tag() {
+		Some(Tag { number: 2, context: true, .. }) => Some(reader.context_unsigned(2)?),
+		_ => None
+	};
+
+	// The range, all records when left out
+	let range = match reader.peek_tag() {
+		None => Some(ReadRange::All),
+		Some(tag @ Tag { number: 3 | 6 | 7, context: true, opening: true, .. }) => {
+			reader.tag()?;
+			let range = match tag.number {
+				3 => Some(ReadRange::ByPosition { reference_index: reader.application_unsigned()?, count: reader.application_signed()? }),
+				6 => Some(ReadRange::BySequenceNumber { reference_sequence_number: reader.application_unsigned()?, count: reader.application_signed()? }),
+				_ => {
+					let (date, time) = (reader.application_date()?, reader.application_time()?);
+					let count = reader.application_signed()?;
+					to_millis(&date, &time).map(|reference_time| ReadRange::ByTime { reference_time, count })
+				}
+			};
+			match reader.tag()? {
+				Tag { closing: true, number, .. } if number == tag.number => range,
+				_ => return None
+			}
+		},
+		Some(_) => return None
+	};
+	Some(ReadRangeRequest { source, originating_address, invoke_id, max_apdu_length, object_type, object_instance, property_array_index, range })
+}
+
+// Whether a received BACnet/IP message is a UTCTimeSynchronization request. The stack answers time
+// synchronization itself, this only tells which of the two services its callback was called for.
+pub fn is_utc_time_synchronization(message: &[u8]) -> bool {
+	let mut reader = Reader { bytes: message, position: 0 };
+	decode_header(&mut reader).is_some()
+		&& reader.byte() == Some(PDU_TYPE_UNCONFIRMED_REQUEST)
+		&& reader.byte() == Some(SERVICE_CHOICE_UTC_TIME_SYNCHRONIZATION)
+}
+
+// BVLC and NPDU of a received application layer message
+struct Header {
+	// B/IP address of the original sender of a message forwarded by a BBMD
+	originating_address: Option<SocketAddrV4>,
+	// Source network and address of a routed message
+	source: Option<(u16, Vec<u8>)>,
+	// Whether the message has a destination network, even if a broadcast that includes this one
+	for_other_network: bool
+}
+
+// Decodes the BVLC and NPDU, leaving the reader at the APDU. None for anything but an application
+// layer message over BACnet/IP.
+fn decode_header(reader: &mut Reader) -> Option<Header> {
+	// BVLC, a forwarded NPDU carries the address of the original source
+	if reader.byte()? != BVLC_TYPE_BACNET_IP {
+		return None;
+	}
+	let function = reader.byte()?;
+	reader.take(2)?;
+	let originating_address = match function {
+		BVLC_ORIGINAL_UNICAST_NPDU | BVLC_ORIGINAL_BROADCAST_NPDU => None,
+		BVLC_FORWARDED_NPDU => {
+			let address = reader.take(6)?;
+			Some(SocketAddrV4::new(Ipv4Addr::new(address[0], address[1], address[2], address[3]), u16::from_be_bytes([address[4], address[5]])))
+		},
+		_ => return None
+	};
+
+	// NPDU, network layer messages are left to the stack
+	if reader.byte()? != NPDU_VERSION {
+		return None;
+	}
+	let control = reader.byte()?;
+	if control & 0x80 != 0 {
+		return None;
+	}
+	let for_other_network = control & 0x20 != 0;
+	if for_other_network {
+		reader.take(2)?;
+		let length = reader.byte()? as usize;
+		reader.take(length)?;
+	}
+	let source = if control & 0x08 != 0 {
+		let network = u16::from_be_bytes([reader.byte()?, reader.byte()?]);
+		let length = reader.byte()? as usize;
+		Some((network, reader.take(length)?.to_vec()))
+	} else {
+		None
+	};
+	// Hop count
+	if for_other_network {
+		reader.byte()?;
+	}
+	Some(Header { originating_address, source, for_other_network })
+}
+
+// The ReadRange-ACK for a request, as a BACnet/IP message. Timestamps are converted to local time by
+// to_local. Records that do not fit the client's maximum APDU are left out, from the end for a
+// positive count or the start for a negative one, and more items is set.
+pub fn encode_ack(request: &ReadRangeRequest, result: ReadRangeResult, to_local: impl Fn(u64) -> (BacnetDate, BacnetTime)) -> Vec<u8> {
+	let mut items: Vec<Vec<u8>> = result.records.iter().map(|record| {
+		let mut item = Vec::new();
+		let (date, time) = to_local(record.timestamp);
+		encode_opening_tag(&mut item, 0);
+		encode_date(&mut item, &date);
+		encode_time(&mut item, &time);
+		encode_closing_tag(&mut item, 0);
+		encode_opening_tag(&mut item, 1);
+		encode_log_datum(&mut item, &record.value);
+		encode_closing_tag(&mut item, 1);
+		item
+	}).collect();
+	let mut sequence_numbers: Vec<u32> = result.records.iter().map(|record| record.sequence_number).collect();
+	let from_start = matches!(request.range, Some(ReadRange::ByPosition { count, .. } | ReadRange::BySequenceNumber { count, .. } | ReadRange::ByTime { count, .. }) if count < 0);
+
+	// Object identifier, property, flags, count, both item data tags and the first sequence number
+	let fixed_length = 3 + 5 + 3 + 4 + 6 + 2 + 6;
+	let mut length = fixed_length;
+	let fitting = if from_start {
+		items.iter().rev().take_while(|item| { length += item.len(); length <= request.max_apdu_length }).count()
+	} else {
+		items.iter().take_while(|item| { length += item.len(); length <= request.max_apdu_length }).count()
+	};
+	let dropped = items.len() - fitting;
+	if from_start {
+		items.drain(..dropped);
+		sequence_numbers.drain(..dropped);
+	} else {
+		items.truncate(fitting);
+		sequence_numbers.truncate(fitting);
+	}
+	let first_item = result.first_item && (dropped == 0 || !from_start);
+	let last_item = result.last_item && (dropped == 0 || from_start);
+	let more_items = result.more_items || dropped > 0;
+
+	let mut apdu = vec![PDU_TYPE_COMPLEX_ACK, request.invoke_id, SERVICE_CHOICE_READ_RANGE];
+	encode_context_unsigned(&mut apdu, 0, (u32::from(u16::from(request.object_type)) << 22) | request.object_instance);
+	encode_context_unsigned(&mut apdu, 1, PropertyIdentifier::LogBuffer.into());
+	if let Some(property_array_index) = request.property_array_index {
+		encode_context_unsigned(&mut apdu, 2, property_array_index);
+	}
+	encode_context_bit_string(&mut apdu, 3, &[first_item, last_item, more_items]);
+	encode_context_unsigned(&mut apdu, 4, items.len() as u32);
+	encode_opening_tag(&mut apdu, 5);
+	for item in &items {
+		apdu.extend_from_slice(item);
+	}
+	encode_closing_tag(&mut apdu, 5);
+	if let (Some(_), Some(first_sequence_number)) = (result.first_sequence_number, sequence_numbers.first()) {
+		encode_context_unsigned(&mut apdu, 6, *first_sequence_number);
+	}
+	encode_message(request, &apdu)
+}
+
+// The Error PDU for a request, as a BACnet/IP message
+pub fn encode_error(request: &ReadRangeRequest, error_code: ErrorCode) -> Vec<u8> {
+	let error_class = match error_code {
+		ErrorCode::UnknownObject => 1,
+		ErrorCode::UnknownProperty | ErrorCode::PropertyIsNotAnArray => 2,
+		_ => 5
+	};
+	let mut apdu = vec![PDU_TYPE_ERROR, request.invoke_id, SERVICE_CHOICE_READ_RANGE];
+	encode_application_unsigned(&mut apdu, TAG_ENUMERATED, error_class);
+	encode_application_unsigned(&mut apdu, TAG_ENUMERATED, error_code.into());
+	encode_message(request, &apdu)
+}
+
+// Wraps an APDU in an NPDU, routed back to the source network of a routed request, and the BVLC
+fn encode_message(request: &ReadRangeRequest, apdu: &[u8]) -> Vec<u8> {
+	let mut npdu = vec![NPDU_VERSION];
+	match &request.source {
+		Some((network, address)) => {
+			npdu.push(0x20);
+			npdu.extend_from_slice(&network.to_be_bytes());
+			npdu.push(address.len() as u8);
+			npdu.extend_from_slice(address);
+			npdu.push(0xFF);
+		},
+		None => npdu.push(0x00)
+	}
+	npdu.extend_from_slice(apdu);
+	let mut message = vec![BVLC_TYPE_BACNET_IP, BVLC_ORIGINAL_UNICAST_NPDU];
+	message.extend_from_slice(&((npdu.len() + 4) as u16).to_be_bytes());
+	message.extend_from_slice(&npdu);
+	message
+}
+
+// A logged value as a BACnetLogRecord logDatum. Values without a choice of their own are sent as
+// any-value.
+fn encode_log_datum(buffer: &mut Vec<u8>, value: &PropertyValue) {
+	match value {
+		PropertyValue::Boolean(value) => {
+			encode_tag(buffer, 1, true, 1);
+			buffer.push(u8::from(*value));
+		},
+		PropertyValue::Real(value) => {
+			encode_tag(buffer, 2, true, 4);
+			buffer.extend_from_slice(&value.to_be_bytes());
+		},
+		PropertyValue::Enumerated(value) => encode_context_unsigned(buffer, 3, *value),
+		PropertyValue::Unsigned(value) => encode_context_unsigned(buffer, 4, *value),
+		PropertyValue::Signed(value) => {
+			let bytes = signed_bytes(*value);
+			encode_tag(buffer, 5, true, bytes.len());
+			buffer.extend_from_slice(&bytes);
+		},
+		PropertyValue::BitString(bits) => encode_context_bit_string(buffer, 6, bits),
+		PropertyValue::Null => encode_tag(buffer, 7, true, 0),
+		value => {
+			encode_opening_tag(buffer, 10);
+			encode_application_value(buffer, value);
+			encode_closing_tag(buffer, 10);
+		}
+	}
+}
+
+fn encode_application_value(buffer: &mut Vec<u8>, value: &PropertyValue) {
+	match value {
+		PropertyValue::Null => encode_tag(buffer, TAG_NULL, false, 0),
+		PropertyValue::Boolean(value) => encode_tag(buffer, TAG_BOOLEAN, false, usize::from(*value)),
+		PropertyValue::Unsigned(value) => encode_application_unsigned(buffer, TAG_UNSIGNED, *value),
+		PropertyValue::Signed(value) => {
+			let bytes = signed_bytes(*value);
+			encode_tag(buffer, TAG_SIGNED, false, bytes.len());
+			buffer.extend_from_slice(&bytes);
+		},
+		PropertyValue::Real(value) => {
+			encode_tag(buffer, TAG_REAL, false, 4);
+			buffer.extend_from_slice(&value.to_be_bytes());
+		},
+		PropertyValue::Double(value) => {
+			encode_tag(buffer, TAG_DOUBLE, false, 8);
+			buffer.extend_from_slice(&value.to_be_bytes());
+		},
+		PropertyValue::OctetString(value) => {
+			encode_tag(buffer, TAG_OCTET_STRING, false, value.len());
+			buffer.extend_from_slice(value);
+		},
+		// UTF-8
+		PropertyValue::CharacterString(value) => {
+			encode_tag(buffer, TAG_CHARACTER_STRING, false, value.len() + 1);
+			buffer.push(0);
+			buffer.extend_from_slice(value.as_bytes());
+		},
+		PropertyValue::BitString(bits) => {
+			let bytes = bit_string_bytes(bits);
+			encode_tag(buffer, TAG_BIT_STRING, false, bytes.len());
+			buffer.extend_from_slice(&bytes);
+		},
+		PropertyValue::Enumerated(value) => encode_application_unsigned(buffer, TAG_ENUMERATED, *value),
+		PropertyValue::Date(date) => encode_date(buffer, date),
+		PropertyValue::Time(time) => encode_time(buffer, time),
+		PropertyValue::DateTime(date, time) => {
+			encode_date(buffer, date);
+			encode_time(buffer, time);
+		},
+		PropertyValue::Array(values) => {
+			for value in values {
+				encode_application_value(buffer, value);
+			}
+		}
+	}
+}
+
+fn encode_date(buffer: &mut Vec<u8>, date: &BacnetDate) {
+	encode_tag(buffer, TAG_DATE, false, 4);
+	buffer.extend_from_slice(&[date.year, date.month, date.day, date.weekday]);
+}
+
+fn encode_time(buffer: &mut Vec<u8>, time: &BacnetTime) {
+	encode_tag(buffer, TAG_TIME, false, 4);
+	buffer.extend_from_slice(&[time.hour, time.minute, time.second, time.hundredth_seconds]);
+}
+
+// Tag numbers up to 14, with the length in the tag or the extended length after it
+fn encode_tag(buffer: &mut Vec<u8>, number: u8, context: bool, length: usize) {
+	let class = if context { 0x08 } else { 0x00 };
+	match length {
+		0..=4 => buffer.push((number << 4) | class | length as u8),
+		5..=253 => buffer.extend_from_slice(&[(number << 4) | class | 5, length as u8]),
+		254..=65535 => {
+			buffer.extend_from_slice(&[(number << 4) | class | 5, 254]);
+			buffer.extend_from_slice(&(length as u16).to_be_bytes());
+		},
+		_ => {
+			buffer.extend_from_slice(&[(number << 4) | class | 5, 255]);
+			buffer.extend_from_slice(&(length as u32).to_be_bytes());
+		}
+	}
+}
+
+fn encode_opening_tag(buffer: &mut Vec<u8>, number: u8) {
+	buffer.push((number << 4) | 0x0E);
+}
+
+fn encode_closing_tag(buffer: &mut Vec<u8>, number: u8) {
+	buffer.push((number << 4) | 0x0F);
+}
+
+fn encode_context_unsigned(buffer: &mut Vec<u8>, number: u8, value: u32) {
+	let bytes = unsigned_bytes(value);
+	encode_tag(buffer, number, true, bytes.len());
+	buffer.extend_from_slice(&bytes);
+}
+
+fn encode_application_unsigned(buffer: &mut Vec<u8>, tag: u8, value: u32) {
+	let bytes = unsigned_bytes(value);
+	encode_tag(buffer, tag, false, bytes.len());
+	buffer.extend_from_slice(&bytes);
+}
+
+fn encode_context_bit_string(buffer: &mut Vec<u8>, number: u8, bits: &[bool]) {
+	let bytes = bit_string_bytes(bits);
+	encode_tag(buffer, number, true, bytes.len());
+	buffer.extend_from_slice(&bytes);
+}
+
+// The fewest big-endian bytes that hold the value
+fn unsigned_bytes(value: u32) -> Vec<u8> {
+	let bytes = value.to_be_bytes();
+	let skip = bytes.iter().take(3).take_while(|&&byte| byte == 0).count();
+	bytes[skip..].to_vec()
+}
+
+// The fewest big-endian two's complement bytes that hold the value
+fn signed_bytes(value: i32) -> Vec<u8> {
+	let bytes = value.to_be_bytes();
+	let mut skip = 0;
+	while skip < 3 && ((bytes[skip] == 0x00 && bytes[skip + 1] & 0x80 == 0) || (bytes[skip] == 0xFF && bytes[skip + 1] & 0x80 != 0)) {
+		skip += 1;
+	}
+	bytes[skip..].to_vec()
+}
+
+// The number of unused bits in the last byte, then the bits from the most significant bit
+fn bit_string_bytes(bits: &[bool]) -> Vec<u8> {
+	let mut bytes = vec![((8 - bits.len() % 8) % 8) as u8];
+	for chunk in bits.chunks(8) {
+		bytes.push(chunk.iter().enumerate().fold(0, |byte, (index, &bit)| byte | (u8::from(bit) << (7 - index))));
+	}
+	bytes
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Tag {
+	number: u8,
+	context: bool,
+	opening: bool,
+	closing: bool,
+	// The length of the value, or the value of an application boolean
+	length: usize
+}
+
+struct Reader<'a> {
+	bytes: &'a [u8],
+	position: usize
+}
+
+impl Reader<'_> {
+	fn byte(&mut self) -> Option<u8> {
+		let byte = *self.bytes.get(self.position)?;
+		self.position += 1;
+		Some(byte)
+	}
+
+	fn take(&mut self, length: usize) -> Option<&[u8]> {
+		let bytes = self.bytes.get(self.position..self.position.checked_add(length)?)?;
+		self.position += length;
+		Some(bytes)
+	}
+
+	// The next tag without consuming it, None at the end of the message
+	fn peek_tag(&mut self) -> Option<Tag> {
+		let position = self.position;
+		let tag = self.tag();
+		self.position = position;
+		tag
+	}
+
+	fn tag(&mut self) -> Option<Tag> {
+		let first = self.byte()?;
+		let number = match first >> 4 {
+			0x0F => self.byte()?,
+			number => number
+		};
+		let context = first & 0x08 != 0;
+		let length_value_type = first & 0x07;
+		let (opening, closing) = (context && length_value_type == 6, context && length_value_type == 7);
+		let length = match length_value_type {
+			5 => match self.byte()? {
+				254 => u16::from_be_bytes([self.byte()?, self.byte()?]) as usize,
+				255 => u32::from_be_bytes([self.byte()?, self.byte()?, self.byte()?, self.byte()?]) as usize,
+				length => length as usize
+			},
+			6 | 7 if context => 0,
+			length => length as usize
+		};
+		Some(Tag { number, context, opening, closing, length })
+	}
+
+	fn unsigned(&mut self, length: usize) -> Option<u32> {
+		if !(1..=4).contains(&length) {
+			return None;
+		}
+		Some(self.take(length)?.iter().fold(0, |value, &byte| (value << 8) | u32::from(byte)))
+	}
+
+	fn context_unsigned(&mut self, number: u8) -> Option<u32> {
+		match self.tag()? {
+			Tag { number: tag_number, context: true, opening: false, closing: false, length } if tag_number == number => self.unsigned(length),
+			_ => None
+		}
+	}
+
+	fn application_unsigned(&mut self) -> Option<u32> {
+		match self.tag()? {
+			Tag { number: TAG_UNSIGNED, context: false, length, .. } => self.unsigned(length),
+			_ => None
+		}
+	}
+
+	fn application_signed(&mut self) -> Option<i32> {
+		match self.tag()? {
+			Tag { number: TAG_SIGNED, context: false, length: length @ 1..=4, .. } => {
+				let bytes = self.take(length)?;
+				let sign = if bytes[0] & 0x80 != 0 { -1 } else { 0 };
+				Some(bytes.iter().fold(sign, |value, &byte| (value << 8) | i32::from(byte)))
+			},
+			_ => None
+		}
+	}
+
+	fn application_date(&mut self) -> Option<BacnetDate> {
+		match self.tag()? {
+			Tag { number: TAG_DATE, context: false, length: 4, .. } => {
+				let bytes = self.take(4)?;
+				Some(BacnetDate { year: bytes[0], month: bytes[1], day: bytes[2], weekday: bytes[3] })
+			},
+			_ => None
+		}
+	}
+
+	fn application_time(&mut self) -> Option<BacnetTime> {
+		match self.tag()? {
+			Tag { number: TAG_TIME, context: false, length: 4, .. } => {
+				let bytes = self.take(4)?;
+				Some(BacnetTime { hour: bytes[0], minute: bytes[1], second: bytes[2], hundredth_seconds: bytes[3] })
+			},
+			_ => None
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::trend_log::LogRecord;
+
+	// BVLC and NPDU of an unrouted request expecting a reply, then the APDU
+	fn message(apdu: &[u8]) -> Vec<u8> {
+		let mut message = vec![BVLC_TYPE_BACNET_IP, BVLC_ORIGINAL_UNICAST_NPDU];
+		message.extend_from_slice(&((apdu.len() + 6) as u16).to_be_bytes());
+		message.extend_from_slice(&[NPDU_VERSION, 0x04]);
+		message.extend_from_slice(apdu);
+		message
+	}
+
+	fn request(max_apdu_length: usize, range: ReadRange) -> ReadRangeRequest {
+		ReadRangeRequest { source: None, originating_address: None, invoke_id: 7, max_apdu_length, object_type: ObjectType::TrendLog, object_instance: 1, property_array_index: None, range: Some(range) }
+	}
+
+	fn record(sequence_number: u32) -> LogRecord {
+		LogRecord { sequence_number, timestamp: 0, value: PropertyValue::Real(sequence_number as f32) }
+	}
+
+	fn local(_: u64) -> (BacnetDate, BacnetTime) {
+		(BacnetDate { year: 126, month: 10, day: 18, weekday: 7 }, BacnetTime { hour: 12, minute: 0, second: 0, hundredth_seconds: 0 })
+	}
+
+	#[test]
+	fn decodes_a_read_by_position() {
+		// Trend Log 1 Log_Buffer, by position from 1 for -2 records
+		let message = message(&[0x00, 0x05, 0x07, 0x1A, 0x0C, 0x05, 0x00, 0x00, 0x01, 0x19, 0x83, 0x3E, 0x21, 0x01, 0x31, 0xFE, 0x3F]);
+		let request = decode_request(&message, |_, _| None).unwrap();
+		assert_eq!(request, ReadRangeRequest { source: None, originating_address: None, invoke_id: 7, max_apdu_length: 1476, object_type: ObjectType::TrendLog, object_instance: 1, property_array_index: None, range: Some(ReadRange::ByPosition { reference_index: 1, count: -2 }) });
+	}
+
+	#[test]
+	fn decodes_a_read_by_time_in_local_time() {
+		let message = message(&[0x00, 0x05, 0x07, 0x1A, 0x0C, 0x05, 0x00, 0x00, 0x01, 0x19, 0x83, 0x7E, 0xA4, 126, 10, 18, 7, 0xB4, 12, 0, 0, 0, 0x31, 0x05, 0x7F]);
+		let request = decode_request(&message, |date, time| Some(u64::from(date.day) * 100 + u64::from(time.hour))).unwrap();
+		assert_eq!(request.range, Some(ReadRange::ByTime { reference_time: 1812, count: 5 }));
+		assert_eq!(decode_request(&message, |_, _| None).unwrap().range, None);
+	}
+
+	#[test]
+	fn decodes_the_originating_address_of_a_forwarded_request() {
+		let mut message = message(&[0x00, 0x05, 0x07, 0x1A, 0x0C, 0x05, 0x00, 0x00, 0x01, 0x19, 0x83]);
+		message[1] = BVLC_FORWARDED_NPDU;
+		message.splice(4..4, [192, 168, 1, 20, 0xBA, 0xC0]);
+		message[3] += 6;
+		let request = decode_request(&message, |_, _| None).unwrap();
+		assert_eq!(request.originating_address, Some(SocketAddrV4::new(Ipv4Addr::new(192, 168, 1, 20), 47808)));
+		assert_eq!(request.range, Some(ReadRange::All));
+	}
+
+	#[test]
+	fn tells_utc_time_synchronization_from_time_synchronization() {
+		let time = [0xA4, 126, 10, 18, 7, 0xB4, 12, 0, 0, 0];
+		let utc = message(&[&[0x10, 0x09][..], &time].concat());
+		assert!(is_utc_time_synchronization(&utc));
+		assert!(!is_utc_time_synchronization(&message(&[&[0x10, 0x06][..], &time].concat())));
+
+		// Also as a global broadcast
+		let mut global = utc.clone();
+		global[5] = 0x20;
+		global.splice(6..6, [0xFF, 0xFF, 0x00, 0xFF]);
+		global[3] += 4;
+		assert!(is_utc_time_synchronization(&global));
+	}
+
+	#[test]
+	fn leaves_other_messages_to_the_stack() {
+		// ReadProperty, ReadRange of another property and a segmented ReadRange
+		assert_eq!(decode_request(&message(&[0x00, 0x05, 0x07, 0x0C, 0x0C, 0x05, 0x00, 0x00, 0x01, 0x19, 0x83]), |_, _| None), None);
+		assert_eq!(decode_request(&message(&[0x00, 0x05, 0x07, 0x1A, 0x0C, 0x05, 0x00, 0x00, 0x01, 0x19, 0x4C]), |_, _| None), None);
+		assert_eq!(decode_request(&message(&[0x08, 0x05, 0x07, 0x00, 0x01, 0x1A, 0x0C, 0x05, 0x00, 0x00, 0x01, 0x19, 0x83]), |_, _| None), None);
+	}
+
+	#[test]
+	fn encodes_the_records() {
+		let result = ReadRangeResult { first_item: true, last_item: true, more_items: false, first_sequence_number: None, records: vec![record(1)] };
+		let reply = encode_ack(&request(1476, ReadRange::All), result, local);
+		assert_eq!(reply[6..], [
+			0x30, 0x07, 0x1A, 0x0C, 0x05, 0x00, 0x00, 0x01, 0x19, 0x83, 0x3A, 0x05, 0xC0, 0x49, 0x01,
+			0x5E, 0x0E, 0xA4, 126, 10, 18, 7, 0xB4, 12, 0, 0, 0, 0x0F, 0x1E, 0x2C, 0x3F, 0x80, 0x00, 0x00, 0x1F, 0x5F
+		]);
+		assert_eq!(u16::from_be_bytes([reply[2], reply[3]]) as usize, reply.len());
+	}
+
+	#[test]
+	fn leaves_out_the_records_that_do_not_fit() {
+		let result = ReadRangeResult { first_item: true, last_item: true, more_items: false, first_sequence_number: Some(1), records: vec![record(1), record(2)] };
+		let reply = encode_ack(&request(50, ReadRange::BySequenceNumber { reference_sequence_number: 1, count: 2 }), result.clone(), local);
+		// First item only, more items, one record and the first sequence number 1
+		assert_eq!(reply[16..21], [0x3A, 0x05, 0xA0, 0x49, 0x01]);
+		assert_eq!(reply[reply.len() - 2..], [0x69, 0x01]);
+
+		// The newest record is kept for a negative count
+		let reply = encode_ack(&request(50, ReadRange::BySequenceNumber { reference_sequence_number: 2, count: -2 }), result, local);
+		assert_eq!(reply[16..21], [0x3A, 0x05, 0x60, 0x49, 0x01]);
+		assert_eq!(reply[reply.len() - 2..], [0x69, 0x02]);
+	}
+
+	#[test]
+	fn encodes_errors() {
+		let reply = encode_error(&request(1476, ReadRange::All), ErrorCode::ParameterOutOfRange);
+		assert_eq!(reply[6..], [0x50, 0x07, 0x1A, 0x91, 0x05, 0x91, 0x50]);
+	}
+}
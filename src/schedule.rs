@@ -0,0 +1,274 @@
+// Evaluation of Schedule and Calendar objects. A schedule's present value is taken from the highest
+// priority special event in effect today, then from today's weekly schedule, then from its default.
+
+use std::mem;
+
+use crate::bacnet_types::ErrorCode;
+use crate::database::{days_in_month, BacnetDate, BacnetTime, PropertyValue, UNSPECIFIED};
+
+// Dates from start to end, inclusive. A fully unspecified end leaves the range open on that side.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DateRange {
+	pub start: BacnetDate,
+	pub end: BacnetDate
+}
+
+impl DateRange {
+	pub const ALL: DateRange = DateRange { start: BacnetDate::UNSPECIFIED, end: BacnetDate::UNSPECIFIED };
+
+	// The date is fully specified
+	pub fn contains(&self, date: &BacnetDate) -> bool {
+		let key = |date: &BacnetDate| (date.year, date.month, date.day);
+		(self.start == BacnetDate::UNSPECIFIED || key(&self.start) <= key(date)) && (self.end == BacnetDate::UNSPECIFIED || key(date) <= key(&self.end))
+	}
+}
+
+// An entry of a calendar's Date_List, also used as the period of a special event
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CalendarEntry {
+	// A date pattern, any field may be unspecified
+	Date(BacnetDate),
+	DateRange(DateRange),
+	// Month 1-12, odd (13) or even (14). Week of month 1-5 for days 1-7 to 29-31, 6 for the last 7
+	// days of the month, 7 to 9 for the 7 days before those. Day of week 1 (Monday) to 7.
+	WeekNDay { month: u8, week_of_month: u8, day_of_week: u8 }
+}
+
+impl CalendarEntry {
+	// The date is fully specified
+	pub fn matches(&self, date: &BacnetDate) -> bool {
+		match *self {
+			CalendarEntry::Date(pattern) => pattern.matches(date),
+			CalendarEntry::DateRange(range) => range.contains(date),
+			CalendarEntry::WeekNDay { month, week_of_month, day_of_week } => {
+				let last_day = days_in_month(Some(1900 + u16::from(date.year)), date.month);
+				let week_matches = match week_of_month {
+					1..=5 => (date.day - 1) / 7 + 1 == week_of_month,
+					6..=9 => (last_day - date.day) / 7 == week_of_month - 6,
+					_ => true
+				};
+				BacnetDate { year: UNSPECIFIED, month, day: UNSPECIFIED, weekday: day_of_week }.matches(date) && week_matches
+			}
+		}
+	}
+}
+
+// The value a schedule takes from a time of day. Null relinquishes to the next lower source.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TimeValue {
+	pub time: BacnetTime,
+	pub value: PropertyValue
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SpecialEventPeriod {
+	CalendarEntry(CalendarEntry),
+	// Instance of a calendar on the schedule's device, the event applies on the dates it lists
+	CalendarReference(u32)
+}
+
+// An entry of Exception_Schedule, priority 1 is the highest
+#[derive(Clone, Debug, PartialEq)]
+pub struct SpecialEvent {
+	pub period: SpecialEventPeriod,
+	pub time_values: Vec<TimeValue>,
+	pub priority: u8
+}
+
+pub struct Timetable {
+	pub effective_period: DateRange,
+	// Monday first
+	pub weekly_schedule: [Vec<TimeValue>; 7],
+	pub exception_schedule: Vec<SpecialEvent>,
+	pub schedule_default: PropertyValue
+}
+
+impl Timetable {
+	// The schedule default and every scheduled value
+	pub fn values(&self) -> impl Iterator<Item = &PropertyValue> {
+		let weekly = self.weekly_schedule.iter().flatten();
+		let exceptions = self.exception_schedule.iter().flat_map(|special_event| &special_event.time_values);
+		std::iter::once(&self.schedule_default).chain(weekly.chain(exceptions).map(|time_value| &time_value.value))
+	}
+
+	// Values are null or primitive values of the same data type as the others
+	pub fn check_value_type(&self, value: &PropertyValue) -> Result<(), ErrorCode> {
+		match value {
+			PropertyValue::Null => Ok(()),
+			PropertyValue::Array(_) => Err(ErrorCode::InvalidDataType),
+			value => match self.values().find(|scheduled| **scheduled != PropertyValue::Null) {
+				Some(scheduled) if mem::discriminant(scheduled) != mem::discriminant(value) => Err(ErrorCode::InvalidDataType),
+				_ => Ok(())
+			}
+		}
+	}
+
+	// The present value at a fully specified local date and time. is_calendar_date tells whether a
+	// referenced calendar lists the date. Outside the effective period the schedule default applies.
+	pub fn effective_value(&self, date: &BacnetDate, time: &BacnetTime, is_calendar_date: impl Fn(u32) -> bool) -> PropertyValue {
+		if !self.effective_period.contains(date) {
+			return self.schedule_default.clone();
+		}
+		let special_event = self.exception_schedule.iter()
+			.filter(|special_event| match special_event.period {
+				SpecialEventPeriod::CalendarEntry(entry) => entry.matches(date),
+				SpecialEventPeriod::CalendarReference(instance) => is_calendar_date(instance)
+			})
+			.filter_map(|special_event| value_at(&special_event.time_values, time).map(|value| (special_event.priority, value)))
+			.min_by_key(|(priority, _)| *priority);
+		if let Some((_, value)) = special_event {
+			return value.clone();
+		}
+		value_at(&self.weekly_schedule[usize::from(date.weekday - 1)], time).unwrap_or(&self.schedule_default).clone()
+	}
+}
+
+// The value of the latest entry at or before the time, None when there is none or it is null
+fn value_at<'a>(time_values: &'a [TimeValue], time: &BacnetTime) -> Option<&'a PropertyValue> {
+	let key = |time: &BacnetTime| (time.hour, time.minute, time.second, if time.hundredth_seconds == UNSPECIFIED { 0 } else { time.hundredth_seconds });
+	time_values.iter()
+		.filter(|time_value| key(&time_value.time) <= key(time))
+		.max_by_key(|time_value| key(&time_value.time))
+		.map(|time_value| &time_value.value)
+		.filter(|value| **value != PropertyValue::Null)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::database::{date_time_to_millis, millis_to_date_time};
+
+	fn date(year: u16, month: u8, day: u8) -> BacnetDate {
+		let date = BacnetDate { year: (year - 1900) as u8, month, day, weekday: UNSPECIFIED };
+		millis_to_date_time(date_time_to_millis(&date, &time(0, 0)).unwrap()).0
+	}
+
+	fn time(hour: u8, minute: u8) -> BacnetTime {
+		BacnetTime { hour, minute, second: 0, hundredth_seconds: 0 }
+	}
+
+	fn time_value(hour: u8, value: PropertyValue) -> TimeValue {
+		TimeValue { time: time(hour, 0), value }
+	}
+
+	fn week_n_day(month: u8, week_of_month: u8, day_of_week: u8) -> CalendarEntry {
+		CalendarEntry::WeekNDay { month, week_of_month, day_of_week }
+	}
+
+	// 1 from 07:00 to 18:00 on weekdays, 0 otherwise
+	fn office_hours() -> Timetable {
+		let weekday = vec![time_value(7, PropertyValue::Enumerated(1)), time_value(18, PropertyValue::Null)];
+		Timetable {
+			effective_period: DateRange::ALL,
+			weekly_schedule: [weekday.clone(), weekday.clone(), weekday.clone(), weekday.clone(), weekday, Vec::new(), Vec::new()],
+			exception_schedule: Vec::new(),
+			schedule_default: PropertyValue::Enumerated(0)
+		}
+	}
+
+	fn special_event(period: SpecialEventPeriod, time_values: Vec<TimeValue>, priority: u8) -> SpecialEvent {
+		SpecialEvent { period, time_values, priority }
+	}
+
+	#[test]
+	fn week_n_day_weeks_start_on_the_first_of_the_month() {
+		// October 2026 starts on a Thursday
+		let first_thursday = week_n_day(10, 1, 4);
+		assert!(first_thursday.matches(&date(2026, 10, 1)));
+		assert!(!first_thursday.matches(&date(2026, 10, 8)));
+		let first_week = week_n_day(10, 1, UNSPECIFIED);
+		assert!(first_week.matches(&date(2026, 10, 7)));
+		assert!(!first_week.matches(&date(2026, 10, 8)));
+		assert!(week_n_day(10, 2, UNSPECIFIED).matches(&date(2026, 10, 8)));
+		assert!(week_n_day(10, 5, 4).matches(&date(2026, 10, 29)));
+		assert!(!week_n_day(10, 5, UNSPECIFIED).matches(&date(2026, 10, 28)));
+	}
+
+	#[test]
+	fn week_n_day_last_week_is_the_last_seven_days() {
+		let last_week = week_n_day(UNSPECIFIED, 6, UNSPECIFIED);
+		assert!(last_week.matches(&date(2026, 10, 25)));
+		assert!(last_week.matches(&date(2026, 10, 31)));
+		assert!(!last_week.matches(&date(2026, 10, 24)));
+		// February in a leap year and otherwise
+		assert!(last_week.matches(&date(2028, 2, 23)));
+		assert!(!last_week.matches(&date(2028, 2, 22)));
+		assert!(last_week.matches(&date(2027, 2, 22)));
+		// The last Thursday, and the 7 days before the last 7
+		assert!(week_n_day(10, 6, 4).matches(&date(2026, 10, 29)));
+		assert!(!week_n_day(10, 6, 4).matches(&date(2026, 10, 22)));
+		assert!(week_n_day(10, 7, 4).matches(&date(2026, 10, 22)));
+	}
+
+	#[test]
+	fn week_n_day_wildcards() {
+		let any_day = week_n_day(UNSPECIFIED, UNSPECIFIED, UNSPECIFIED);
+		assert!(any_day.matches(&date(2026, 10, 18)));
+		assert!(any_day.matches(&date(2028, 2, 29)));
+		// Sundays in odd months, Mondays in even months
+		assert!(week_n_day(13, UNSPECIFIED, 7).matches(&date(2026, 11, 1)));
+		assert!(!week_n_day(13, UNSPECIFIED, 7).matches(&date(2026, 10, 18)));
+		assert!(week_n_day(14, UNSPECIFIED, 1).matches(&date(2026, 10, 19)));
+		// The fourth Thursday of November
+		assert!(week_n_day(11, 4, 4).matches(&date(2026, 11, 26)));
+		assert!(!week_n_day(11, 4, 4).matches(&date(2026, 11, 19)));
+	}
+
+	#[test]
+	fn date_ranges_include_both_ends() {
+		let holidays = DateRange { start: date(2026, 12, 24), end: date(2027, 1, 2) };
+		assert!(holidays.contains(&date(2026, 12, 24)));
+		assert!(holidays.contains(&date(2027, 1, 2)));
+		assert!(!holidays.contains(&date(2026, 12, 23)));
+		assert!(!holidays.contains(&date(2027, 1, 3)));
+		// An unspecified end leaves the range open
+		let from_2027 = DateRange { start: date(2027, 1, 1), end: BacnetDate::UNSPECIFIED };
+		assert!(from_2027.contains(&date(2099, 12, 31)));
+		assert!(!from_2027.contains(&date(2026, 12, 31)));
+		assert!(DateRange::ALL.contains(&date(2026, 10, 18)));
+	}
+
+	#[test]
+	fn the_weekly_schedule_applies_without_special_events() {
+		let timetable = office_hours();
+		let monday = date(2026, 10, 19);
+		assert_eq!(timetable.effective_value(&monday, &time(6, 59), |_| false), PropertyValue::Enumerated(0));
+		assert_eq!(timetable.effective_value(&monday, &time(7, 0), |_| false), PropertyValue::Enumerated(1));
+		// A null entry relinquishes to the default
+		assert_eq!(timetable.effective_value(&monday, &time(18, 0), |_| false), PropertyValue::Enumerated(0));
+		assert_eq!(timetable.effective_value(&date(2026, 10, 24), &time(12, 0), |_| false), PropertyValue::Enumerated(0));
+	}
+
+	#[test]
+	fn a_special_event_overrides_the_weekly_schedule() {
+		let mut timetable = office_hours();
+		let christmas = BacnetDate { year: UNSPECIFIED, month: 12, day: 25, weekday: UNSPECIFIED };
+		let time_values = vec![time_value(0, PropertyValue::Enumerated(2)), time_value(12, PropertyValue::Null)];
+		timetable.exception_schedule.push(special_event(SpecialEventPeriod::CalendarEntry(CalendarEntry::Date(christmas)), time_values, 10));
+		let friday = date(2026, 12, 25);
+		assert_eq!(timetable.effective_value(&friday, &time(8, 0), |_| false), PropertyValue::Enumerated(2));
+		// After its null entry the weekly schedule applies again
+		assert_eq!(timetable.effective_value(&friday, &time(13, 0), |_| false), PropertyValue::Enumerated(1));
+		assert_eq!(timetable.effective_value(&date(2026, 12, 24), &time(8, 0), |_| false), PropertyValue::Enumerated(1));
+	}
+
+	#[test]
+	fn the_highest_priority_special_event_applies() {
+		let mut timetable = office_hours();
+		let holidays = CalendarEntry::DateRange(DateRange { start: date(2026, 12, 24), end: date(2027, 1, 2) });
+		timetable.exception_schedule.push(special_event(SpecialEventPeriod::CalendarEntry(holidays), vec![time_value(0, PropertyValue::Enumerated(2))], 10));
+		timetable.exception_schedule.push(special_event(SpecialEventPeriod::CalendarReference(0), vec![time_value(0, PropertyValue::Enumerated(3))], 5));
+		let monday = date(2026, 12, 28);
+		assert_eq!(timetable.effective_value(&monday, &time(8, 0), |instance| instance == 0), PropertyValue::Enumerated(3));
+		// Only when the calendar lists the date
+		assert_eq!(timetable.effective_value(&monday, &time(8, 0), |_| false), PropertyValue::Enumerated(2));
+	}
+
+	#[test]
+	fn the_default_applies_outside_the_effective_period() {
+		let mut timetable = office_hours();
+		timetable.effective_period = DateRange { start: date(2026, 1, 1), end: date(2026, 12, 31) };
+		assert_eq!(timetable.effective_value(&date(2026, 10, 19), &time(8, 0), |_| false), PropertyValue::Enumerated(1));
+		assert_eq!(timetable.effective_value(&date(2027, 1, 4), &time(8, 0), |_| false), PropertyValue::Enumerated(0));
+	}
+}
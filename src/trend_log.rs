@@ -0,0 +1,128 @@
+// Log buffer of the Trend Log objects, and the records it returns for a ReadRange request
+
+use std::collections::VecDeque;
+use std::ops::Range;
+
+use crate::bacnet_types::ErrorCode;
+use crate::database::PropertyValue;
+
+// A logged value, timestamped in milliseconds since the Unix epoch (UTC)
+#[derive(Clone, Debug, PartialEq)]
+pub struct LogRecord {
+	pub sequence_number: u32,
+	pub timestamp: u64,
+	pub value: PropertyValue
+}
+
+// The records requested by ReadRange. A positive count selects the reference record and those after
+// it, a negative count the reference record and those before it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReadRange {
+	All,
+	// Position in the buffer, 1 is the oldest record
+	ByPosition { reference_index: u32, count: i32 },
+	BySequenceNumber { reference_sequence_number: u32, count: i32 },
+	// Milliseconds since the Unix epoch. Selects the records newer than the reference time for a
+	// positive count, or older for a negative count.
+	ByTime { reference_time: u64, count: i32 }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ReadRangeResult {
+	// The oldest record in the buffer is returned
+	pub first_item: bool,
+	// The newest record in the buffer is returned
+	pub last_item: bool,
+	// More records matched than the count allowed
+	pub more_items: bool,
+	// Sequence number of the first record returned, for reads by sequence number and by time
+	pub first_sequence_number: Option<u32>,
+	pub records: Vec<LogRecord>
+}
+
+// Ring buffer of log records. When it is full, the oldest record is dropped for each one added.
+pub struct LogBuffer {
+	records: VecDeque<LogRecord>,
+	buffer_size: u32,
+	total_record_count: u32
+}
+
+impl LogBuffer {
+	pub fn new(buffer_size: u32) -> LogBuffer {
+		LogBuffer { records: VecDeque::with_capacity(buffer_size as usize), buffer_size, total_record_count: 0 }
+	}
+
+	pub fn buffer_size(&self) -> u32 {
+		self.buffer_size
+	}
+
+	pub fn record_count(&self) -> u32 {
+		self.records.len() as u32
+	}
+
+	// Records added since the object was created. Also the sequence number of the newest record.
+	pub fn total_record_count(&self) -> u32 {
+		self.total_record_count
+	}
+
+	pub fn is_full(&self) -> bool {
+		self.record_count() >= self.buffer_size
+	}
+
+	// Sequence numbers run from 1, and wrap back to 1 after 2^32 - 1
+	pub fn push(&mut self, timestamp: u64, value: PropertyValue) {
+		self.total_record_count = self.total_record_count.checked_add(1).unwrap_or(1);
+		if self.is_full() {
+			self.records.pop_front();
+		}
+		self.records.push_back(LogRecord { sequence_number: self.total_record_count, timestamp, value });
+	}
+
+	// Deletes every record. Sequence numbers carry on from the last record.
+	pub fn clear(&mut self) {
+		self.records.clear();
+	}
+
+	// Resizing deletes every record
+	pub fn set_buffer_size(&mut self, buffer_size: u32) {
+		self.records = VecDeque::with_capacity(buffer_size as usize);
+		self.buffer_size = buffer_size;
+	}
+
+	pub fn read_range(&self, range: ReadRange) -> Result<ReadRangeResult, ErrorCode> {
+		let (reference, count, with_sequence_number) = match range {
+			ReadRange::All => return Ok(self.result(0..self.records.len(), false, false)),
+			ReadRange::ByPosition { reference_index, count } => (reference_index.checked_sub(1).map(|index| index as usize).filter(|&index| index < self.records.len()), count, false),
+			ReadRange::BySequenceNumber { reference_sequence_number, count } => (self.records.iter().position(|record| record.sequence_number == reference_sequence_number), count, true),
+			ReadRange::ByTime { reference_time, count } if count > 0 => (self.records.iter().position(|record| record.timestamp > reference_time), count, true),
+			ReadRange::ByTime { reference_time, count } => (self.records.iter().rposition(|record| record.timestamp < reference_time), count, true)
+		};
+		if count == 0 {
+			return Err(ErrorCode::ParameterOutOfRange);
+		}
+		let Some(reference) = reference else {
+			return Ok(ReadRangeResult::default());
+		};
+		let selected = if count > 0 {
+			reference..self.records.len().min(reference + count as usize)
+		} else {
+			(reference + 1).saturating_sub(count.unsigned_abs() as usize)..reference + 1
+		};
+		let more_items = if count > 0 { selected.end < self.records.len() } else { selected.start > 0 };
+		Ok(self.result(selected, more_items, with_sequence_number))
+	}
+
+	fn result(&self, selected: Range<usize>, more_items: bool, with_sequence_number: bool) -> ReadRangeResult {
+		if selected.is_empty() {
+			return ReadRangeResult::default();
+		}
+		let records: Vec<LogRecord> = self.records.range(selected.clone()).cloned().collect();
+		ReadRangeResult {
+			first_item: selected.start == 0,
+			last_item: selected.end == self.records.len(),
+			more_items,
+			first_sequence_number: with_sequence_number.then(|| records[0].sequence_number),
+			records
+		}
+	}
+}